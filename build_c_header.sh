@@ -0,0 +1,2 @@
+# Requires cbindgen (`cargo install cbindgen`), regenerates the C header of the `pcm_ffi` crate.
+cbindgen --config pcm_ffi/cbindgen.toml --crate pcm_ffi --output pcm_ffi/include/pcm.h
@@ -0,0 +1,2 @@
+# Requires wasm-pack (https://rustwasm.github.io/wasm-pack/), outputs the JavaScript module into `pcm_wasm/pkg`.
+wasm-pack build pcm_wasm --release --target web
@@ -0,0 +1,220 @@
+use std::{collections::BTreeMap, error::Error, fs, path::Path, process::ExitCode, str::FromStr};
+
+use pcm::{curve_from_csv, curve_from_geojson, curve_from_gpx, curve_from_plt, curve_from_polyline, curve_from_wkt, matching_to_geojson, min_partial_eps, parallel, partial_curve_matching, partial_frechet_distance, Checkpoint, Curve, ExportOptions, PcmError, FSD};
+use serde_json::json;
+
+
+const USAGE: &str = "\
+Usage:
+  pcm match <query> <target> --eps <eps> [--output <file>] [--render <file>] [--render-curves <file>] [--animate <file>] [--scale <scale>]
+  pcm decide <query> <target> --eps <eps>
+  pcm min-eps <query> <target> [--tolerance <tolerance>]
+  pcm batch <query> <directory> --eps <eps> [--output <file>] [--checkpoint <file>]
+
+Checks whether the query curve matches some subcurve of the target curve(s) within distance eps.
+Curves are read by extension: GeoJSON (.geojson, .json), WKT (.wkt), CSV (.csv), encoded polyline (.polyline, precision 5),
+GPX (.gpx, a single track segment) or GeoLife PLT (.plt), all in their own coordinates (longitude and latitude for GPS data).
+Renderings are written as SVG for a .svg extension, as PNG otherwise.
+Animations of the matching walk are written as GIF for a .gif extension, as a sequence of PNGs otherwise.
+Renderings are annotated with the point indices and eps, --scale multiplies their resolution.
+Curve renderings of a match show leashes between matched points, colored from green (close) to red (at eps).
+Batches record their progress in the --checkpoint file (if any) and skip the files it lists when run again.
+Exits with 0 on a match, 1 without a match (for match and decide) and 2 on errors.";
+
+/// Number of files a batch matches (in parallel) before recording them in the checkpoint.
+const BATCH_CHUNK: usize = 64;
+
+/// Positional arguments and `--name value` options.
+struct Args {
+    positional: Vec<String>,
+    options: BTreeMap<String, String>,
+}
+impl Args {
+
+    fn parse(args: &[String]) -> Result<Args, String> {
+        let mut positional = vec![];
+        let mut options = BTreeMap::new();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            if let Some(name) = arg.strip_prefix("--") {
+                let value = iter.next().ok_or(format!("Option --{name} expects a value."))?;
+                options.insert(name.to_string(), value.clone());
+            } else {
+                positional.push(arg.clone());
+            }
+        }
+        Ok(Args { positional, options })
+    }
+
+    /// Reject positional arguments beyond the expected names, missing ones, and options not in allowed.
+    fn expect(&self, names: &[&str], allowed: &[&str]) -> Result<(), String> {
+        if self.positional.len() != names.len() {
+            return Err(format!("Expected arguments {}.", names.iter().map(|name| format!("<{name}>")).collect::<Vec<_>>().join(" ")));
+        }
+        if let Some(name) = self.options.keys().find(|name| !allowed.contains(&name.as_str())) {
+            return Err(format!("Unknown option --{name}."));
+        }
+        Ok(())
+    }
+
+    fn option<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.options.get(name).map(|value| value.parse().map_err(|_| format!("Invalid value {value:?} for --{name}."))).transpose()
+    }
+
+    fn required<T: FromStr>(&self, name: &str) -> Result<T, String> {
+        self.option(name)?.ok_or(format!("Missing option --{name}."))
+    }
+}
+
+/// Read a curve, the format is derived from the file extension.
+fn read_curve(path: &Path) -> Result<Curve, String> {
+    let input = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let extension = path.extension().map_or(String::new(), |extension| extension.to_string_lossy().to_lowercase());
+    match extension.as_str() {
+        "geojson" | "json" => curve_from_geojson(&input),
+        "wkt" => curve_from_wkt(&input),
+        "csv" => curve_from_csv(&input),
+        "polyline" => curve_from_polyline(input.trim(), 5),
+        "gpx" => curve_from_gpx(&input),
+        "plt" => curve_from_plt(&input),
+        _ => Err(PcmError::Parse("Unknown curve format (expected .geojson, .json, .wkt, .csv, .polyline, .gpx or .plt).".to_string())),
+    }.map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Write output to the file (if any), otherwise to stdout.
+fn write_output(output: &str, opt_path: Option<String>) -> Result<(), Box<dyn Error>> {
+    match opt_path {
+        Some(path) => fs::write(path, output)?,
+        None => println!("{output}"),
+    }
+    Ok(())
+}
+
+#[cfg(feature = "render")]
+fn render(args: &Args, ps: &Curve, qs: &Curve, eps: f64, opt_steps: Option<Vec<(f64, f64)>>) -> Result<(), Box<dyn Error>> {
+    let rsd = FSD::try_new(ps.clone(), qs.clone(), eps)?.to_rsd();
+    let options = pcm_vis::DrawOptions::default().with_scale(args.option("scale")?.unwrap_or(1)).with_grid_indices(true).with_legend_eps(eps);
+    if let Some(path) = args.options.get("render") {
+        pcm_vis::draw_fsd_with(&rsd, path, opt_steps.clone(), &options)?;
+    }
+    if let Some(path) = args.options.get("render-curves") {
+        pcm_vis::draw_curves_with(ps.clone(), qs.clone(), path, opt_steps.clone(), &options)?;
+    }
+    // Without a match there is no walk to animate.
+    if let (Some(path), Some(steps)) = (args.options.get("animate"), &opt_steps) {
+        pcm_vis::animate_walk(ps, qs, &rsd, steps, path, 60, &options)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "render"))]
+fn render(args: &Args, _: &Curve, _: &Curve, _: f64, _: Option<Vec<(f64, f64)>>) -> Result<(), Box<dyn Error>> {
+    if ["render", "render-curves", "animate", "scale"].iter().any(|name| args.options.contains_key(*name)) {
+        return Err("Rendering is not available, build with the render feature.".into());
+    }
+    Ok(())
+}
+
+/// Compute the matching and write it as GeoJSON.
+fn run_match(args: &Args) -> Result<ExitCode, Box<dyn Error>> {
+    args.expect(&["query", "target"], &["eps", "output", "render", "render-curves", "animate", "scale"])?;
+    let (ps, qs) = (read_curve(Path::new(&args.positional[0]))?, read_curve(Path::new(&args.positional[1]))?);
+    let eps: f64 = args.required("eps")?;
+    let opt_matching = partial_curve_matching(ps.clone(), qs.clone(), eps)?;
+    render(args, &ps, &qs, eps, opt_matching.as_ref().map(|matching| matching.steps.clone()))?;
+    let Some(matching) = opt_matching else {
+        eprintln!("No match at eps {eps}.");
+        return Ok(ExitCode::from(1));
+    };
+    write_output(&matching_to_geojson(&matching, eps, &ExportOptions::default()), args.option("output")?)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Print whether the query matches.
+fn run_decide(args: &Args) -> Result<ExitCode, Box<dyn Error>> {
+    args.expect(&["query", "target"], &["eps"])?;
+    let (ps, qs) = (read_curve(Path::new(&args.positional[0]))?, read_curve(Path::new(&args.positional[1]))?);
+    let is_match = FSD::try_new(ps, qs, args.required("eps")?)?.check_pcm();
+    println!("{}", if is_match { "match" } else { "no match" });
+    Ok(ExitCode::from(if is_match { 0 } else { 1 }))
+}
+
+/// Print the smallest threshold at which the query matches, exact unless a tolerance is given.
+fn run_min_eps(args: &Args) -> Result<ExitCode, Box<dyn Error>> {
+    args.expect(&["query", "target"], &["tolerance"])?;
+    let (ps, qs) = (read_curve(Path::new(&args.positional[0]))?, read_curve(Path::new(&args.positional[1]))?);
+    let eps = match args.option("tolerance")? {
+        Some(tolerance) => min_partial_eps(&ps, &qs, tolerance)?,
+        None => partial_frechet_distance(&ps, &qs)?.0,
+    };
+    println!("{eps}");
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Match the query against a single curve file, as its entry in the batch output.
+fn match_file(ps: &Curve, path: &Path, eps: f64) -> serde_json::Value {
+    let file = path.display().to_string();
+    match read_curve(path).and_then(|qs| partial_curve_matching(ps.clone(), qs, eps).map_err(|e| e.to_string())) {
+        Ok(Some(matching)) => {
+            let (start, end) = matching.covered_interval();
+            json!({ "file": file, "matched": true, "start": start, "end": end, "coverage": matching.coverage() })
+        },
+        Ok(None) => json!({ "file": file, "matched": false }),
+        Err(e) => json!({ "file": file, "error": e }),
+    }
+}
+
+/// Match the query against every curve file in the directory, writing a JSON array with a result per file.
+///
+/// With a checkpoint the results are recorded in it chunk by chunk, and files recorded by a previous (interrupted) run are not matched again.
+fn run_batch(args: &Args) -> Result<ExitCode, Box<dyn Error>> {
+    args.expect(&["query", "directory"], &["eps", "output", "checkpoint"])?;
+    let ps = read_curve(Path::new(&args.positional[0]))?;
+    let eps: f64 = args.required("eps")?;
+    let mut paths: Vec<_> = fs::read_dir(&args.positional[1])?.map(|entry| entry.map(|entry| entry.path())).collect::<Result<_, _>>()?;
+    paths.retain(|path| path.is_file());
+    paths.sort();
+    let mut opt_checkpoint = args.option::<String>("checkpoint")?.map(|path| Checkpoint::open(path, true)).transpose()?;
+    let is_done = |checkpoint: &Option<Checkpoint>, path: &Path| checkpoint.as_ref().is_some_and(|checkpoint| checkpoint.is_done(&path.display().to_string()));
+    let mut results = vec![];
+    for chunk in paths.chunks(BATCH_CHUNK) {
+        let todo = chunk.iter().filter(|path| !is_done(&opt_checkpoint, path)).cloned().collect();
+        let mut matched = parallel::map(todo, |path| match_file(&ps, &path, eps)).into_iter();
+        for path in chunk {
+            let file = path.display().to_string();
+            let result = match opt_checkpoint.as_ref().and_then(|checkpoint| checkpoint.record(&file)) {
+                Some(record) => serde_json::from_str(record)?,
+                None => matched.next().unwrap(),
+            };
+            // Files failing to read or match are tried again on resume.
+            match &mut opt_checkpoint {
+                Some(checkpoint) if result.get("error").is_none() => checkpoint.mark_done_with(&file, &result.to_string())?,
+                _ => {},
+            }
+            results.push(result);
+        }
+        if let Some(checkpoint) = &mut opt_checkpoint {
+            checkpoint.flush()?;
+        }
+    }
+    write_output(&serde_json::to_string_pretty(&results)?, args.option("output")?)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = Args::parse(args.get(1..).unwrap_or_default()).map_err(Box::<dyn Error>::from).and_then(|parsed| {
+        match args.first().map(String::as_str) {
+            Some("match") => run_match(&parsed),
+            Some("decide") => run_decide(&parsed),
+            Some("min-eps") => run_min_eps(&parsed),
+            Some("batch") => run_batch(&parsed),
+            Some("help" | "--help" | "-h") => { println!("{USAGE}"); Ok(ExitCode::SUCCESS) },
+            _ => Err(USAGE.into()),
+        }
+    });
+    result.unwrap_or_else(|e| {
+        eprintln!("{e}");
+        ExitCode::from(2)
+    })
+}
@@ -0,0 +1,74 @@
+#ifndef PCM_H
+#define PCM_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Opaque curve handle.
+typedef struct PcmCurve PcmCurve;
+
+// Steps of a matching, `len` pairs of parameters `(i, j)` (i on ps, j on qs) stored interleaved in `steps`.
+typedef struct PcmSteps {
+  double *steps;
+  uintptr_t len;
+} PcmSteps;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Construct a curve from `len` points with interleaved coordinates `[x0, y0, x1, y1, ...]` (thus `2 * len` values).
+//
+// Returns null on invalid input (fewer than two points, non-finite coordinates).
+//
+// # Safety
+// `coords` must point to `2 * len` readable values.
+struct PcmCurve *pcm_curve_new(const double *coords, uintptr_t len);
+
+// Number of points of the curve.
+//
+// # Safety
+// `curve` must be a curve returned by `pcm_curve_new`.
+uintptr_t pcm_curve_len(const struct PcmCurve *curve);
+
+// Free a curve (null is ignored).
+//
+// # Safety
+// `curve` must be null or a curve returned by `pcm_curve_new`, which is not used afterwards.
+void pcm_curve_free(struct PcmCurve *curve);
+
+// Check whether ps matches some subcurve of qs within distance threshold eps.
+//
+// Returns 1 on a match, 0 without a match and -1 on error.
+//
+// # Safety
+// `ps` and `qs` must be curves returned by `pcm_curve_new`.
+int32_t pcm_match(const struct PcmCurve *ps, const struct PcmCurve *qs, double eps);
+
+// Compute the steps of a partial curve matching of ps against a subcurve of qs within distance threshold eps.
+//
+// Returns 1 on a match (writing the steps into `out`, to be freed with `pcm_steps_free`), 0 without a match and -1 on error.
+//
+// # Safety
+// `ps` and `qs` must be curves returned by `pcm_curve_new`, `out` must point to writable memory.
+int32_t pcm_match_steps(const struct PcmCurve *ps,
+                        const struct PcmCurve *qs,
+                        double eps,
+                        struct PcmSteps *out);
+
+// Free the steps written by `pcm_match_steps` (resetting them to empty).
+//
+// # Safety
+// `steps` must be null or point to steps written by `pcm_match_steps`.
+void pcm_steps_free(struct PcmSteps *steps);
+
+// Message of the most recent error on this thread (null if none), valid until the next failing call on this thread.
+const char *pcm_last_error(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* PCM_H */
@@ -0,0 +1,134 @@
+//! C API for partial curve matching, the header is generated with cbindgen (see `build_c_header.sh`) into `include/pcm.h`.
+//!
+//! Ownership: Every object returned by the library is freed by its matching `*_free` function.
+//! Errors: Functions signal failure with a null pointer or a negative return value, `pcm_last_error` describes the failure.
+use std::{cell::RefCell, ffi::{c_char, CString}, panic::{catch_unwind, UnwindSafe}, ptr, slice};
+
+use pcm::{curve::validate_curve, Curve, PcmError, Vector, FSD};
+
+
+thread_local! {
+    /// Message of the most recent error on this thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Store the error message for `pcm_last_error`.
+fn set_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+}
+
+/// Run f, converting errors and panics into the error message (panics must not unwind into C).
+fn guard<T>(f: impl FnOnce() -> Result<T, PcmError> + UnwindSafe) -> Option<T> {
+    match catch_unwind(f) {
+        Ok(Ok(value)) => Some(value),
+        Ok(Err(e)) => { set_error(e.to_string()); None }
+        Err(_) => { set_error("Internal error (panic) in partial curve matching.".to_string()); None }
+    }
+}
+
+/// Opaque curve handle.
+pub struct PcmCurve(Curve);
+
+/// Steps of a matching, `len` pairs of parameters `(i, j)` (i on ps, j on qs) stored interleaved in `steps`.
+#[repr(C)]
+pub struct PcmSteps {
+    pub steps: *mut f64,
+    pub len: usize,
+}
+
+/// Construct a curve from `len` points with interleaved coordinates `[x0, y0, x1, y1, ...]` (thus `2 * len` values).
+///
+/// Returns null on invalid input (fewer than two points, non-finite coordinates).
+///
+/// # Safety
+/// `coords` must point to `2 * len` readable values.
+#[no_mangle]
+pub unsafe extern "C" fn pcm_curve_new(coords: *const f64, len: usize) -> *mut PcmCurve {
+    if coords.is_null() {
+        set_error("Coordinates should not be null.".to_string());
+        return ptr::null_mut();
+    }
+    let coords = slice::from_raw_parts(coords, 2 * len);
+    guard(|| {
+        let curve: Curve = coords.chunks_exact(2).map(|c| Vector::new(c[0], c[1])).collect();
+        validate_curve(&curve)?;
+        Ok(Box::into_raw(Box::new(PcmCurve(curve))))
+    }).unwrap_or(ptr::null_mut())
+}
+
+/// Number of points of the curve.
+///
+/// # Safety
+/// `curve` must be a curve returned by `pcm_curve_new`.
+#[no_mangle]
+pub unsafe extern "C" fn pcm_curve_len(curve: *const PcmCurve) -> usize {
+    (*curve).0.len()
+}
+
+/// Free a curve (null is ignored).
+///
+/// # Safety
+/// `curve` must be null or a curve returned by `pcm_curve_new`, which is not used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn pcm_curve_free(curve: *mut PcmCurve) {
+    if !curve.is_null() {
+        drop(Box::from_raw(curve));
+    }
+}
+
+/// Check whether ps matches some subcurve of qs within distance threshold eps.
+///
+/// Returns 1 on a match, 0 without a match and -1 on error.
+///
+/// # Safety
+/// `ps` and `qs` must be curves returned by `pcm_curve_new`.
+#[no_mangle]
+pub unsafe extern "C" fn pcm_match(ps: *const PcmCurve, qs: *const PcmCurve, eps: f64) -> i32 {
+    let (ps, qs) = (&(*ps).0, &(*qs).0);
+    match guard(|| Ok(FSD::try_new(ps.clone(), qs.clone(), eps)?.check_pcm())) {
+        Some(is_match) => is_match as i32,
+        None => -1,
+    }
+}
+
+/// Compute the steps of a partial curve matching of ps against a subcurve of qs within distance threshold eps.
+///
+/// Returns 1 on a match (writing the steps into `out`, to be freed with `pcm_steps_free`), 0 without a match and -1 on error.
+///
+/// # Safety
+/// `ps` and `qs` must be curves returned by `pcm_curve_new`, `out` must point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn pcm_match_steps(ps: *const PcmCurve, qs: *const PcmCurve, eps: f64, out: *mut PcmSteps) -> i32 {
+    let (ps, qs) = (&(*ps).0, &(*qs).0);
+    match guard(|| Ok(FSD::try_new(ps.clone(), qs.clone(), eps)?.to_rsd().pcm_steps())) {
+        Some(Some(steps)) => {
+            let len = steps.len();
+            let flat: Box<[f64]> = steps.into_iter().flat_map(|(i, j)| [i, j]).collect();
+            *out = PcmSteps { steps: Box::into_raw(flat) as *mut f64, len };
+            1
+        }
+        Some(None) => 0,
+        None => -1,
+    }
+}
+
+/// Free the steps written by `pcm_match_steps` (resetting them to empty).
+///
+/// # Safety
+/// `steps` must be null or point to steps written by `pcm_match_steps`.
+#[no_mangle]
+pub unsafe extern "C" fn pcm_steps_free(steps: *mut PcmSteps) {
+    if steps.is_null() || (*steps).steps.is_null() {
+        return;
+    }
+    let PcmSteps { steps: data, len } = *steps;
+    drop(Box::from_raw(ptr::slice_from_raw_parts_mut(data, 2 * len)));
+    *steps = PcmSteps { steps: ptr::null_mut(), len: 0 };
+}
+
+/// Message of the most recent error on this thread (null if none), valid until the next failing call on this thread.
+#[no_mangle]
+pub extern "C" fn pcm_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
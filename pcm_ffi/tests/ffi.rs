@@ -0,0 +1,50 @@
+//! Exercise the C API (ownership and error reporting) from Rust.
+use std::{ffi::CStr, ptr, slice};
+
+use pcm_ffi::*;
+
+fn curve(coords: &[f64]) -> *mut PcmCurve {
+    unsafe { pcm_curve_new(coords.as_ptr(), coords.len() / 2) }
+}
+
+#[test]
+fn match_and_steps() {
+    let ps = curve(&[0., 0.2, 1., 0.2, 2., 0.2]);
+    let qs = curve(&[-1., 0., 3., 0.]);
+    unsafe {
+        assert_eq!(pcm_curve_len(ps), 3);
+        assert_eq!(pcm_match(ps, qs, 0.1), 0);
+        assert_eq!(pcm_match(ps, qs, 0.5), 1);
+
+        let mut out = PcmSteps { steps: ptr::null_mut(), len: 0 };
+        assert_eq!(pcm_match_steps(ps, qs, 0.1, &mut out), 0);
+        assert!(out.steps.is_null());
+        assert_eq!(pcm_match_steps(ps, qs, 0.5, &mut out), 1);
+        let steps = slice::from_raw_parts(out.steps, 2 * out.len);
+        assert_eq!((steps[0], steps[2 * out.len - 2]), (0., 2.));
+        pcm_steps_free(&mut out);
+        assert!(out.steps.is_null());
+        pcm_steps_free(&mut out); // Freeing twice is harmless once reset.
+
+        pcm_curve_free(ps);
+        pcm_curve_free(qs);
+        pcm_curve_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn errors() {
+    unsafe {
+        assert!(curve(&[0., 0.]).is_null());
+        assert!(curve(&[0., 0., f64::NAN, 1.]).is_null());
+        let message = CStr::from_ptr(pcm_last_error()).to_string_lossy();
+        assert!(!message.is_empty());
+
+        let ps = curve(&[0., 0., 1., 0.]);
+        assert_eq!(pcm_match(ps, ps, -1.), -1);
+        let mut out = PcmSteps { steps: ptr::null_mut(), len: 0 };
+        assert_eq!(pcm_match_steps(ps, ps, f64::INFINITY, &mut out), -1);
+        assert!(CStr::from_ptr(pcm_last_error()).to_string_lossy().contains("threshold"));
+        pcm_curve_free(ps);
+    }
+}
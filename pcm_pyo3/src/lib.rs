@@ -4,8 +4,19 @@ use pcm::*;
 #[pymodule]
 fn partial_curve_matching(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Python functions for partial curve matching.
-    m.add_class::<Vector>()?;
+    m.add_class::<PyVector>()?;
     m.add_function(wrap_pyfunction!(pcm::partial_curve::partial_curve, m)?)?;
+    m.add_function(wrap_pyfunction!(pcm::partial_curve::partial_curve_subcurve, m)?)?;
+    m.add_function(wrap_pyfunction!(pcm::partial_curve::partial_curve_all, m)?)?;
+    m.add_function(wrap_pyfunction!(pcm::partial_curve::partial_curve_residuals, m)?)?;
+    m.add_function(wrap_pyfunction!(pcm::partial_curve::partial_curve_distance, m)?)?;
+    m.add_function(wrap_pyfunction!(pcm::partial_curve::frechet_distance, m)?)?;
+    m.add_function(wrap_pyfunction!(pcm::partial_curve::partial_curve_max_coverage, m)?)?;
+    m.add_function(wrap_pyfunction!(pcm::partial_curve::partial_curve_longest_prefix, m)?)?;
+    m.add_function(wrap_pyfunction!(pcm::partial_curve::partial_curve_oriented, m)?)?;
+    m.add_function(wrap_pyfunction!(pcm::partial_curve::partial_curve_or_bound, m)?)?;
+    m.add_function(wrap_pyfunction!(pcm::partial_curve::same_route, m)?)?;
+    m.add_function(wrap_pyfunction!(pcm::calibrate::calibrate_eps, m)?)?;
 
     // Python functions for partial curve to graph matching (defaulting to the linear time complexity implementation).
     m.add_class::<Graph>()?;
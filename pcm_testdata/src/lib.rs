@@ -0,0 +1,165 @@
+//! Storage of test cases (curves and threshold) shared by the visualizer, the regression tests and fuzzers.
+use std::{error::Error, fs, path::{Path, PathBuf}};
+
+use pcm::{curve::content_hash, Curve};
+#[cfg(feature = "proptest")]
+use pcm::arbitrary::{curve, FIELD};
+#[cfg(feature = "proptest")]
+use proptest::{arbitrary::Arbitrary, strategy::{BoxedStrategy, Strategy}};
+use serde_derive::{Deserialize, Serialize};
+
+/// Folder (relative to the working directory) test cases are stored in by default.
+pub const TESTDATA_DIR: &str = "testdata";
+
+/// Testing state for storage/retrieval.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct State {
+    pub ps: Curve,
+    pub qs: Curve,
+    pub eps: f64
+}
+
+impl State {
+
+    /// Identifier derived from the content (FNV-1a over the curve hashes and threshold), equal states share their identifier.
+    pub fn id(&self) -> String {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for word in [content_hash(&self.ps), content_hash(&self.qs), self.eps.to_bits()] {
+            for byte in word.to_le_bytes() {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        }
+        format!("{hash:016x}")
+    }
+}
+
+/// Small random cases (up to 8 points per curve), failing cases shrink to fewer points closer to the origin and a lower threshold.
+#[cfg(feature = "proptest")]
+impl Arbitrary for State {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<State>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (curve(2..=8), curve(2..=8), 0.01..FIELD).prop_map(|(ps, qs, eps)| State { ps, qs, eps }).boxed()
+    }
+}
+
+/// File format a test case is stored in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaseFormat {
+    /// Compact binary (bincode), what failing cases are written in by default.
+    #[default]
+    Bincode,
+    /// Pretty-printed JSON (`{"ps": [{"x": .., "y": ..}, ..], "qs": [..], "eps": ..}`), for cases exchanged with other tools or edited by hand.
+    Json,
+}
+impl CaseFormat {
+
+    /// File extension of the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CaseFormat::Bincode => "bin",
+            CaseFormat::Json => "json",
+        }
+    }
+
+    /// Format of a file, by its extension.
+    pub fn of(path: impl AsRef<Path>) -> Option<CaseFormat> {
+        match path.as_ref().extension()?.to_str()? {
+            "bin" => Some(CaseFormat::Bincode),
+            "json" => Some(CaseFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Filename of a test case.
+pub fn case_filename(state: &State) -> String {
+    case_filename_as(state, CaseFormat::Bincode)
+}
+
+/// Filename of a test case stored in the format.
+pub fn case_filename_as(state: &State, format: CaseFormat) -> String {
+    format!("case_{}.{}", state.id(), format.extension())
+}
+
+/// Listing files in folder.
+pub fn list_files(dir: impl AsRef<Path>) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Write state to the folder as a test case, returns its path.
+/// A state stored before is not written again (so duplicate failures end up in a single file).
+pub fn write_case(dir: impl AsRef<Path>, state: &State) -> Result<PathBuf, Box<dyn Error>> {
+    write_case_as(dir, state, CaseFormat::Bincode)
+}
+
+/// Like [`write_case`], storing the state in the format.
+pub fn write_case_as(dir: impl AsRef<Path>, state: &State, format: CaseFormat) -> Result<PathBuf, Box<dyn Error>> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let path = dir.join(case_filename_as(state, format));
+    if !path.exists() {
+        let buffer = match format {
+            CaseFormat::Bincode => bincode::serialize(state)?,
+            CaseFormat::Json => serde_json::to_vec_pretty(state)?,
+        };
+        fs::write(&path, buffer)?;
+    }
+    Ok(path)
+}
+
+/// Read state from a test case file, in the format its extension names (bincode otherwise).
+pub fn read_case(path: impl AsRef<Path>) -> Result<State, Box<dyn Error>> {
+    let buffer = fs::read(&path)?;
+    match CaseFormat::of(&path).unwrap_or_default() {
+        CaseFormat::Bincode => Ok(bincode::deserialize(&buffer)?),
+        CaseFormat::Json => Ok(serde_json::from_slice(&buffer)?),
+    }
+}
+
+/// Read all test cases from the folder (in either format, sorted by filename), every state is paired with its path.
+/// A missing folder contains no test cases.
+pub fn read_cases(dir: impl AsRef<Path>) -> Result<Vec<(PathBuf, State)>, Box<dyn Error>> {
+    let dir = dir.as_ref();
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+    let mut files = list_files(dir)?;
+    files.retain(|path| CaseFormat::of(path).is_some());
+    files.sort();
+    let mut result = vec![];
+    for path in files {
+        let state = read_case(&path)?;
+        result.push((path, state));
+    }
+    Ok(result)
+}
+
+/// Rename test cases in the folder not named by their content (such as the former `case_{n}.bin` scheme), removing duplicates.
+/// Returns the number of files renamed or removed.
+pub fn migrate(dir: impl AsRef<Path>) -> Result<usize, Box<dyn Error>> {
+    let mut count = 0;
+    for (path, state) in read_cases(&dir)? {
+        let format = CaseFormat::of(&path).unwrap_or_default();
+        let target = dir.as_ref().join(case_filename_as(&state, format));
+        if path == target {
+            continue;
+        }
+        if target.exists() {
+            fs::remove_file(&path)?;
+        } else {
+            fs::rename(&path, &target)?;
+        }
+        count += 1;
+    }
+    Ok(count)
+}
@@ -0,0 +1,142 @@
+use std::{path::PathBuf, str::FromStr};
+
+use clap::{error::ErrorKind, CommandFactory, Parser, ValueEnum};
+use pcm_testdata::TESTDATA_DIR;
+
+
+/// What the executable does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Mode {
+    /// Test random cases, failing cases are written to the input folder.
+    Discover,
+    /// Test the cases stored in the input folder.
+    Replay,
+    /// Minimal matching threshold of a random curve against increasingly perturbed copies.
+    Sweep,
+    /// Minimal matching threshold between two curve families, rendered as heatmap.
+    Heatmap,
+    /// Precision and recall of thresholds on perturbed (matching) and unrelated (non-matching) pairs.
+    Calibrate,
+    /// Interactive viewer of the cases in the input folder (or discovered ones if there are none), needs the viewer feature.
+    View,
+}
+
+/// Artifacts rendered per test case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Artifacts {
+    pub curves: bool,
+    pub fsd: bool,
+    pub rsd: bool,
+    pub path: bool,
+}
+impl FromStr for Artifacts {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Artifacts, String> {
+        let mut artifacts = Artifacts { curves: false, fsd: false, rsd: false, path: false };
+        for name in s.split(',').map(str::trim) {
+            match name {
+                "curves" => artifacts.curves = true,
+                "fsd" => artifacts.fsd = true,
+                "rsd" => artifacts.rsd = true,
+                "path" => artifacts.path = true,
+                "none" => {},
+                _ => return Err(format!("Unknown artifact {name:?}.")),
+            }
+        }
+        Ok(artifacts)
+    }
+}
+
+/// Discovers (or replays) partial curve matching test cases and renders their diagrams.
+#[derive(Debug, Clone, Parser)]
+#[command(name = "pcm_vis")]
+pub struct Config {
+    /// What to run.
+    #[arg(long, value_enum, default_value_t = Mode::Discover)]
+    pub mode: Mode,
+    /// Number of cases to test.
+    #[arg(long, default_value_t = 10)]
+    pub runs: usize,
+    /// Seed of the random curves (default random for discover, 42 otherwise), printed for reproduction.
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Number of points per random curve.
+    #[arg(long, default_value_t = 5)]
+    pub length: usize,
+    /// Random points lie in [0, size) squared.
+    #[arg(long, default_value_t = 2.)]
+    pub field: f64,
+    /// Threshold of discovered cases.
+    #[arg(long, default_value_t = 1.)]
+    pub eps: f64,
+    /// Discover cases on GPS tracks (GPX or GeoLife PLT files in this folder, projected to meters) instead of random curves:
+    /// a window of --length points of a track against the whole track.
+    #[arg(long, value_name = "DIR")]
+    pub tracks: Option<PathBuf>,
+    /// Folder test cases are replayed from and failing cases written to.
+    #[arg(long, value_name = "DIR", default_value = TESTDATA_DIR)]
+    pub input: PathBuf,
+    /// Folder artifacts are rendered into.
+    #[arg(long, value_name = "DIR", default_value = ".")]
+    pub output: PathBuf,
+    /// Comma-separated artifacts to render per case: curves, fsd, rsd, path, or none.
+    #[arg(long, value_name = "ARTIFACTS", default_value = "curves,fsd,rsd,path")]
+    pub render: Artifacts,
+    /// Animate the walk along the steps of every case in this number of frames.
+    #[arg(long, value_name = "FRAMES")]
+    pub animate: Option<usize>,
+    /// Draw the difference between the FSD of every case and its FSD at this threshold.
+    #[arg(long, value_name = "EPS")]
+    pub diff_eps: Option<f64>,
+    /// Run every backend on each case and write their discrepancies to this report.
+    #[arg(long, value_name = "FILE")]
+    pub backend_report: Option<PathBuf>,
+    /// Append every matching decision to this JSON-lines audit log.
+    #[arg(long, value_name = "FILE")]
+    pub audit_log: Option<PathBuf>,
+    /// Progress of replay runs.
+    #[arg(long, value_name = "FILE", default_value = "testdata_checkpoint.txt")]
+    pub checkpoint: PathBuf,
+    /// Skip cases passed in a previous (interrupted) replay run.
+    #[arg(long)]
+    pub resume: bool,
+    /// Additionally run every case in single precision and with exact predicates, and report disagreements.
+    #[arg(long)]
+    pub precision_check: bool,
+    /// Number of perturbation levels of the sweep.
+    #[arg(long, default_value_t = 20)]
+    pub levels: usize,
+    /// Number of curves per family of the heatmap.
+    #[arg(long, default_value_t = 8)]
+    pub size: usize,
+    /// Number of calibration pairs.
+    #[arg(long, default_value_t = 50)]
+    pub pairs: usize,
+}
+impl Config {
+
+    /// Parse the command-line arguments, exits with usage on invalid ones (or prints it when asked for help).
+    pub fn from_args() -> Config {
+        let config = Config::parse();
+        if let Err(e) = config.check() {
+            Config::command().error(ErrorKind::ValueValidation, e).exit();
+        }
+        config
+    }
+
+    /// Reject values the options parse into but cannot be run with.
+    fn check(&self) -> Result<(), String> {
+        if self.length < 2 {
+            return Err("Random curves need at least 2 points (--length).".to_string());
+        }
+        if !(self.field > 0. && self.field.is_finite() && self.eps > 0. && self.eps.is_finite()) {
+            return Err("Field size and eps should be positive and finite.".to_string());
+        }
+        Ok(())
+    }
+
+    /// Path of an artifact in the output folder.
+    pub fn artifact(&self, name: &str) -> PathBuf {
+        self.output.join(name)
+    }
+}
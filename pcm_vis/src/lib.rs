@@ -0,0 +1,445 @@
+//! Drawing curves and free-space diagrams, shared by the visualizer and the command line interface.
+use std::{error::Error, iter::zip, path::Path};
+
+use pcm::{curve::interpolate, Curve, LineBoundary, Vector, FSD};
+use plotters::{coord::Shift, prelude::*};
+use full_palette::{GREEN_400, RED_300};
+
+
+/// Positions (parameter on ps, parameter on qs) walked along.
+pub type Steps = Vec<(f64, f64)>;
+
+/// Image format (plotters backend) to draw to disk with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Raster image (`BitMapBackend`).
+    Png,
+    /// Vector image (`SVGBackend`), e.g. to embed in papers at arbitrary resolution.
+    Svg,
+}
+impl ImageFormat {
+
+    /// SVG for a `.svg` extension, PNG otherwise.
+    pub fn from_path(path: &Path) -> ImageFormat {
+        if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("svg")) { ImageFormat::Svg } else { ImageFormat::Png }
+    }
+}
+
+/// Colors to draw with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub background: RGBColor,
+    /// Free (or reachable) parts of cell boundaries.
+    pub free: RGBAColor,
+    /// Blocked (or unreachable) parts of cell boundaries.
+    pub blocked: RGBAColor,
+    /// Steps walked along.
+    pub path: RGBAColor,
+    /// First curve (ps) and the leash end on it.
+    pub curve1: RGBAColor,
+    /// Second curve (qs) and the leash end on it.
+    pub curve2: RGBAColor,
+    /// Labels, legend, leash and markers.
+    pub text: RGBColor,
+}
+impl Default for Palette {
+    fn default() -> Self {
+        Palette {
+            background: WHITE,
+            free: GREEN_400.mix(0.6),
+            blocked: RED_300.mix(0.6),
+            path: BLACK.mix(1.0),
+            curve1: RED_300.mix(0.6),
+            curve2: GREEN_400.mix(0.6),
+            text: BLACK,
+        }
+    }
+}
+
+/// Layout and styling of the drawings.
+///
+/// Sizes are in pixels before scaling: the scale multiplies all of them (and line widths and fonts),
+/// so thumbnails for bulk inspection and large annotated figures are drawn alike.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrawOptions {
+    /// Width and height of a diagram cell.
+    pub cell_size: u32,
+    /// Margin around the drawing.
+    pub margin: u32,
+    /// Width and height of the curves drawing (excluding margin).
+    pub curves_size: u32,
+    /// Output resolution multiplier.
+    pub scale: u32,
+    pub palette: Palette,
+    /// Draw point indices along the axes of diagrams (in the margin).
+    pub grid_indices: bool,
+    /// Draw the distance threshold in a legend (if set).
+    pub legend_eps: Option<f64>,
+    /// Image format to draw to disk with, derived from the file extension if unset.
+    pub format: Option<ImageFormat>,
+    /// Number of leashes drawn between the curves, evenly spread along the steps (when given).
+    pub leashes: usize,
+}
+impl Default for DrawOptions {
+    fn default() -> Self {
+        DrawOptions { cell_size: 20, margin: 20, curves_size: 400, scale: 1, palette: Palette::default(), grid_indices: false, legend_eps: None, format: None, leashes: 40 }
+    }
+}
+impl DrawOptions {
+
+    pub fn with_cell_size(mut self, cell_size: u32) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    pub fn with_margin(mut self, margin: u32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    pub fn with_curves_size(mut self, curves_size: u32) -> Self {
+        self.curves_size = curves_size;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: u32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    pub fn with_grid_indices(mut self, grid_indices: bool) -> Self {
+        self.grid_indices = grid_indices;
+        self
+    }
+
+    pub fn with_legend_eps(mut self, eps: f64) -> Self {
+        self.legend_eps = Some(eps);
+        self
+    }
+
+    pub fn with_format(mut self, format: ImageFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    pub fn with_leashes(mut self, leashes: usize) -> Self {
+        self.leashes = leashes;
+        self
+    }
+
+    /// Scaled size in pixels.
+    fn px(&self, size: u32) -> i32 {
+        (size * self.scale.max(1)) as i32
+    }
+
+    /// Image size of the diagram of fsd.
+    pub fn fsd_size(&self, fsd: &FSD) -> (u32, u32) {
+        let (cell, margin) = (self.px(self.cell_size), self.px(self.margin));
+        ((fsd.n as i32 * cell + 2 * margin) as u32, (fsd.m as i32 * cell + 2 * margin) as u32)
+    }
+
+    /// Image size of the curves drawing.
+    pub fn curves_size(&self) -> (u32, u32) {
+        let size = (self.px(self.curves_size) + 2 * self.px(self.margin)) as u32;
+        (size, size)
+    }
+
+    fn format_for(&self, path: &Path) -> ImageFormat {
+        self.format.unwrap_or_else(|| ImageFormat::from_path(path))
+    }
+}
+
+/// Drawing Free-Space Diagram as an image to disk (SVG for a `.svg` extension, PNG otherwise). If provided, draw steps along the RSD.
+pub fn draw_fsd<P: AsRef<Path>>(fsd: &FSD, path: P, opt_steps: Option<Steps>) -> Result<(), Box<dyn Error>> {
+    draw_fsd_with(fsd, path, opt_steps, &DrawOptions::default())
+}
+
+/// Drawing Free-Space Diagram as an image to disk in the given format, see [`draw_fsd`].
+pub fn draw_fsd_as<P: AsRef<Path>>(fsd: &FSD, path: P, format: ImageFormat, opt_steps: Option<Steps>) -> Result<(), Box<dyn Error>> {
+    draw_fsd_with(fsd, path, opt_steps, &DrawOptions::default().with_format(format))
+}
+
+/// Drawing Free-Space Diagram as an image to disk with the options, see [`draw_fsd`].
+pub fn draw_fsd_with<P: AsRef<Path>>(fsd: &FSD, path: P, opt_steps: Option<Steps>, options: &DrawOptions) -> Result<(), Box<dyn Error>> {
+    let path = path.as_ref();
+    let size = options.fsd_size(fsd);
+    match options.format_for(path) {
+        ImageFormat::Svg => draw_fsd_on(SVGBackend::new(path, size).into_drawing_area(), fsd, opt_steps, options),
+        ImageFormat::Png => draw_fsd_on(BitMapBackend::new(path, size).into_drawing_area(), fsd, opt_steps, options),
+    }
+}
+
+/// Drawing Free-Space Diagram on any plotters drawing area (of at least [`DrawOptions::fsd_size`]).
+pub fn draw_fsd_on<DB: DrawingBackend>(drawing_area: DrawingArea<DB, Shift>, fsd: &FSD, opt_steps: Option<Steps>, options: &DrawOptions) -> Result<(), Box<dyn Error>> where DB::ErrorType: 'static {
+    fsd_frame(drawing_area, fsd, opt_steps, None, options)
+}
+
+/// Drawing Free-Space Diagram, with the steps and a marker at the current position (if any).
+fn fsd_frame<DB: DrawingBackend>(drawing_area: DrawingArea<DB, Shift>, fsd: &FSD, opt_steps: Option<Steps>, opt_position: Option<(f64, f64)>, options: &DrawOptions) -> Result<(), Box<dyn Error>> where DB::ErrorType: 'static {
+    let palette = options.palette;
+    drawing_area.fill(&palette.background)?;
+
+    let margin = options.px(options.margin);
+    let outer = drawing_area;
+    let drawing_area = outer.margin(margin, margin, margin, margin);
+
+    let n = fsd.n;
+    let m = fsd.m;
+    let cell = options.px(options.cell_size) as f64;
+    let stroke_width = options.px(1) as u32;
+
+    let unreachable = ShapeStyle {
+        color: palette.blocked,
+        filled: true,
+        stroke_width,
+    };
+    let reachable = ShapeStyle {
+        color: palette.free,
+        filled: true,
+        stroke_width,
+    };
+    let path = ShapeStyle {
+        color: palette.path,
+        filled: true,
+        stroke_width,
+    };
+
+    let mut reachable_segments = vec![];
+    let mut unreachable_segments = vec![];
+    
+    // Find reachable and unreachable segments.
+    for j in 0..m {
+        for i in 0..n {
+            for axis in 0..2 {
+                let (_w,h) = fsd.dims[axis];
+                let (x,y) = [(i,j), (j,i)][axis];
+                if y < h {
+                    let curr = (axis, x, y);
+                    if let Some(LineBoundary { a, b }) = fsd.segs[curr] {
+                        if a > 0. { unreachable_segments.push(vec![ (axis, x as f64, y as f64    ), (axis, x as f64, y as f64 + a) ]); }
+                                      reachable_segments.push(vec![ (axis, x as f64, y as f64 + a), (axis, x as f64, y as f64 + b) ]);
+                        if b < 1. { unreachable_segments.push(vec![ (axis, x as f64, y as f64 + b), (axis, x as f64, y as f64 + 1.) ]); }
+                    } else {
+                        unreachable_segments.push(vec![ (axis, x as f64, y as f64), (axis, x as f64, y as f64 + 1.) ])
+                    }
+                }
+            }
+        }
+    }
+
+    // Draw reachable and unreachable line segments.
+    let height = cell as i32 * m as i32;
+    let to_pixel = |(i, j): (f64, f64)| ((cell * i) as i32, height - (cell * j) as i32);
+    for (segments, style) in [(reachable_segments, reachable), (unreachable_segments, unreachable)] {
+        for seg in segments {
+            let seg: Vec<(i32, i32)> = seg.into_iter().map(|(axis, x, y)| {
+                if axis == 0 { to_pixel((x, y)) }
+                else         { to_pixel((y, x)) }
+            }).collect();
+            drawing_area.draw(&Polygon::new(seg, style))?;
+        }
+    }
+
+    if let Some(steps) = opt_steps {
+        for (s1, s2) in zip(&steps,&steps[1..]) {
+            drawing_area.draw(&Polygon::new(vec![to_pixel(*s1), to_pixel(*s2)], path))?;
+        }
+    }
+
+    if let Some(position) = opt_position {
+        drawing_area.draw(&Circle::new(to_pixel(position), options.px(3), palette.text.filled()))?;
+    }
+
+    // Annotations are drawn in the margin (so on the area around the diagram).
+    let font = ("sans-serif", options.px(10)).into_font().color(&palette.text);
+    if options.grid_indices {
+        for i in 0..n {
+            let (x, y) = to_pixel((i as f64, 0.));
+            outer.draw(&Text::new(i.to_string(), (margin + x - options.px(3), margin + y + options.px(4)), font.clone()))?;
+        }
+        for j in 0..m {
+            let (x, y) = to_pixel((0., j as f64));
+            outer.draw(&Text::new(j.to_string(), (margin + x - options.px(15), margin + y - options.px(5)), font.clone()))?;
+        }
+    }
+    if let Some(eps) = options.legend_eps {
+        outer.draw(&Text::new(format!("eps = {eps}"), (margin, options.px(4)), font))?;
+    }
+
+    Ok(())
+}
+
+/// Drawing two curves as an image to disk (SVG for a `.svg` extension, PNG otherwise), c1 in red and c2 in green.
+/// If provided, draw leashes between the points matched by the steps, colored by their length.
+pub fn draw_curves<P: AsRef<Path>>(c1: Curve, c2: Curve, path: P, opt_steps: Option<Steps>) -> Result<(), Box<dyn Error>> {
+    draw_curves_with(c1, c2, path, opt_steps, &DrawOptions::default())
+}
+
+/// Drawing two curves as an image to disk in the given format, see [`draw_curves`].
+pub fn draw_curves_as<P: AsRef<Path>>(c1: Curve, c2: Curve, path: P, format: ImageFormat, opt_steps: Option<Steps>) -> Result<(), Box<dyn Error>> {
+    draw_curves_with(c1, c2, path, opt_steps, &DrawOptions::default().with_format(format))
+}
+
+/// Drawing two curves as an image to disk with the options, see [`draw_curves`].
+pub fn draw_curves_with<P: AsRef<Path>>(c1: Curve, c2: Curve, path: P, opt_steps: Option<Steps>, options: &DrawOptions) -> Result<(), Box<dyn Error>> {
+    let path = path.as_ref();
+    let size = options.curves_size();
+    match options.format_for(path) {
+        ImageFormat::Svg => draw_curves_on(SVGBackend::new(path, size).into_drawing_area(), c1, c2, opt_steps, options),
+        ImageFormat::Png => draw_curves_on(BitMapBackend::new(path, size).into_drawing_area(), c1, c2, opt_steps, options),
+    }
+}
+
+/// Drawing two curves on any plotters drawing area (of at least [`DrawOptions::curves_size`]).
+pub fn draw_curves_on<DB: DrawingBackend>(drawing_area: DrawingArea<DB, Shift>, c1: Curve, c2: Curve, opt_steps: Option<Steps>, options: &DrawOptions) -> Result<(), Box<dyn Error>> where DB::ErrorType: 'static {
+    let leashes = opt_steps.map_or(vec![], |steps| leashes(&c1, &c2, &steps, options.leashes));
+    curves_frame(drawing_area, c1, c2, &leashes, None, options)
+}
+
+/// Leashes between the points on ps and qs matched by the steps, evenly spread along the steps (in the diagram).
+pub fn leashes(ps: &Curve, qs: &Curve, steps: &Steps, count: usize) -> Vec<(Vector, Vector)> {
+    if steps.is_empty() || count == 0 {
+        return vec![];
+    }
+    (0..count).filter_map(|k| {
+        let t = if count > 1 { k as f64 / (count - 1) as f64 } else { 0. };
+        let (_, (i, j)) = walk_at(steps, t);
+        interpolate(ps, i).zip(interpolate(qs, j))
+    }).collect()
+}
+
+/// Color of a leash, from green (length zero) to red (length at least the scale, eps if known).
+fn leash_color(length: f64, scale: f64) -> HSLColor {
+    let t = if scale > 0. { (length / scale).min(1.) } else { 0. };
+    HSLColor((1. - t) / 3., 0.8, 0.45)
+}
+
+/// Drawing two curves, with leashes between matched points (colored by length) and a leash between a point on c1 and a point on c2 (if any).
+fn curves_frame<DB: DrawingBackend>(drawing_area: DrawingArea<DB, Shift>, c1: Curve, c2: Curve, leashes: &[(Vector, Vector)], opt_leash: Option<(Vector, Vector)>, options: &DrawOptions) -> Result<(), Box<dyn Error>> where DB::ErrorType: 'static {
+    let palette = options.palette;
+    drawing_area.fill(&palette.background)?;
+    let margin = options.px(options.margin);
+    let outer = drawing_area;
+    let drawing_area = outer.margin(margin, margin, margin, margin);
+
+    // Computing boundaries.
+    let pmin = c1.clone().into_iter().chain(c2.clone()).reduce(|acc, v| acc.min(&v)).unwrap();
+    let pmax = c1.clone().into_iter().chain(c2.clone()).reduce(|acc, v| acc.max(&v)).unwrap();
+    let pdiff = pmax - pmin;
+
+    // Computing curve point positions on drawing area.
+    let size = options.px(options.curves_size) as f64;
+    let vector_to_point = |v| {
+        let position = Vector::new(size, size) * (v - pmin) / pdiff;
+        (position.x as i32, position.y as i32)
+    };
+    
+    let seg1: Vec<(i32, i32)> = c1.into_iter().map(vector_to_point).collect();
+    let seg2: Vec<(i32, i32)> = c2.into_iter().map(vector_to_point).collect();
+
+    // Drawing the leashes underneath the curves, scaled to eps (or else the longest leash).
+    let stroke_width = options.px(1) as u32;
+    let scale = options.legend_eps.unwrap_or_else(|| leashes.iter().map(|(p, q)| p.distance(*q)).fold(0., f64::max));
+    for &(p, q) in leashes {
+        let style = leash_color(p.distance(q), scale).stroke_width(stroke_width);
+        drawing_area.draw(&PathElement::new(vec![vector_to_point(p), vector_to_point(q)], style))?;
+    }
+
+    // Drawing the two polygonal chains.
+    let colorc1 = ShapeStyle {
+        color: palette.curve1,
+        filled: true,
+        stroke_width,
+    };
+    let colorc2 = ShapeStyle {
+        color: palette.curve2,
+        filled: true,
+        stroke_width,
+    };
+
+    for (p1, p2) in zip(&seg1, &seg1[1..]) {
+        drawing_area.draw(&Polygon::new(vec![*p1, *p2], colorc1))?;
+    }
+
+    for (p1, p2) in zip(&seg2, &seg2[1..]) {
+        drawing_area.draw(&Polygon::new(vec![*p1, *p2], colorc2))?;
+    }
+
+    if let Some((p, q)) = opt_leash {
+        let (p, q) = (vector_to_point(p), vector_to_point(q));
+        drawing_area.draw(&PathElement::new(vec![p, q], palette.text.stroke_width(options.px(2) as u32)))?;
+        drawing_area.draw(&Circle::new(p, options.px(3), palette.curve1.filled()))?;
+        drawing_area.draw(&Circle::new(q, options.px(3), palette.curve2.filled()))?;
+    }
+
+    if let Some(eps) = options.legend_eps {
+        let font = ("sans-serif", options.px(10)).into_font().color(&palette.text);
+        outer.draw(&Text::new(format!("eps = {eps}"), (margin, options.px(4)), font))?;
+    }
+
+    Ok(())
+}
+
+/// Steps walked up to fraction t (of their length in the diagram) and the position reached.
+fn walk_at(steps: &Steps, t: f64) -> (Steps, (f64, f64)) {
+    let length = |(i1, j1): (f64, f64), (i2, j2): (f64, f64)| ((i2 - i1).powi(2) + (j2 - j1).powi(2)).sqrt();
+    let total: f64 = zip(steps, &steps[1..]).map(|(s1, s2)| length(*s1, *s2)).sum();
+    let mut remaining = t * total;
+    let mut walked = vec![steps[0]];
+    for (&s1, &s2) in zip(steps, &steps[1..]) {
+        let d = length(s1, s2);
+        if remaining <= d {
+            let f = if d > 0. { remaining / d } else { 0. };
+            let position = (s1.0 + f * (s2.0 - s1.0), s1.1 + f * (s2.1 - s1.1));
+            walked.push(position);
+            return (walked, position);
+        }
+        remaining -= d;
+        walked.push(s2);
+    }
+    (walked, *steps.last().unwrap())
+}
+
+/// Drawing a single frame of the walk: the RSD with the steps walked so far next to the curves with the current leash.
+fn walk_frame_on<DB: DrawingBackend>(drawing_area: &DrawingArea<DB, Shift>, ps: &Curve, qs: &Curve, rsd: &FSD, steps: &Steps, t: f64, options: &DrawOptions) -> Result<(), Box<dyn Error>> where DB::ErrorType: 'static {
+    drawing_area.fill(&options.palette.background)?;
+    let (walked, (i, j)) = walk_at(steps, t);
+    let (left, right) = drawing_area.split_horizontally(options.fsd_size(rsd).0 as i32);
+    fsd_frame(left, rsd, Some(walked), Some((i, j)), options)?;
+    curves_frame(right, ps.clone(), qs.clone(), &[], interpolate(ps, i).zip(interpolate(qs, j)), options)?;
+    Ok(())
+}
+
+/// Animating the walk along the steps through the RSD in the given number of frames, side by side with the leash between the curves.
+///
+/// Written as GIF for a `.gif` extension, otherwise as a sequence of PNGs named `<stem>_<frame>.png` next to path.
+pub fn animate_walk<P: AsRef<Path>>(ps: &Curve, qs: &Curve, rsd: &FSD, steps: &Steps, path: P, frames: usize, options: &DrawOptions) -> Result<(), Box<dyn Error>> {
+    if steps.is_empty() || frames < 2 {
+        return Err("Animating a walk requires steps and at least two frames.".into());
+    }
+    let path = path.as_ref();
+    let ((fsd_width, fsd_height), (curves_width, curves_height)) = (options.fsd_size(rsd), options.curves_size());
+    let (width, height) = (fsd_width + curves_width, fsd_height.max(curves_height));
+    let t = |k: usize| k as f64 / (frames - 1) as f64;
+    if path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("gif")) {
+        let drawing_area = BitMapBackend::gif(path, (width, height), 100)?.into_drawing_area();
+        for k in 0..frames {
+            walk_frame_on(&drawing_area, ps, qs, rsd, steps, t(k), options)?;
+            drawing_area.present()?;
+        }
+    } else {
+        let stem = path.file_stem().map_or(String::new(), |stem| stem.to_string_lossy().to_string());
+        for k in 0..frames {
+            let frame = path.with_file_name(format!("{stem}_{k:03}.png"));
+            let drawing_area = BitMapBackend::new(&frame, (width, height)).into_drawing_area();
+            walk_frame_on(&drawing_area, ps, qs, rsd, steps, t(k), options)?;
+            drawing_area.present()?;
+        }
+    }
+    Ok(())
+}
@@ -1,13 +1,14 @@
-use std::{fs, io::Read, iter::zip, path::Path};
+use std::{fs, iter::zip};
 extern crate rand;
-use pcm::prelude::*;
+mod config;
+#[cfg(feature = "viewer")]
+mod viewer;
+use config::{Config, Mode};
+use pcm::*;
+use pcm_testdata::{migrate, read_cases, write_case, State};
+use pcm_vis::{animate_walk, draw_curves, draw_fsd, DrawOptions, Steps};
 use rand::Rng;
 
-use serde_derive::{Serialize, Deserialize};
-use std::fs::File;
-use std::io::Write;
-use bincode;
-
 use plotters::prelude::*;
 use full_palette::{GREEN_400, RED_300};
 
@@ -16,33 +17,21 @@ use full_palette::{GREEN_400, RED_300};
 // === Curve ===
 // =============
 
-/// Construct curve with n number of random points in f64 domain.
-/// Chance of generating points which break general position is sufficiently small to ignore testing.
-fn random_curve(n: usize, fieldsize: f64) -> Curve {
-    let mut rng = rand::thread_rng();
-    let c: Curve = (0..n).into_iter().map(|_| Vector {x: rng.gen_range(0.0..fieldsize), y: rng.gen_range(0.0..fieldsize)}).collect();
-    c
-}
-
-/// Translate all points of a curve c1 by a vector q.
-fn translate_curve(c1: Curve, q: Vector) -> Curve {
-    c1.into_iter().map(|p| p + q).collect()
-}
-
-/// Add some random noise to curve points.
-fn perturb_curve(c: Curve, deviation: f64) -> Curve {
-    let mut rng = rand::thread_rng();
-    let d = (1./2.0_f64.sqrt()) * deviation * deviation;
-    c.into_iter().map(|p| p + d * rng.gen::<f64>() * Vector {x: 1., y: 1.} ).collect()
-}
-
-/// Compute curve length.
-fn curve_length(c: &Curve) -> f64 {
-    let mut length = 0.;
-    for (p1, p2) in zip(c, &c[1..]) {
-        length += p1.distance(*p2);
+/// Read the GPS tracks (GPX and GeoLife PLT files) in the folder with at least n points, projected to meters around their common center.
+fn read_tracks(dir: &std::path::Path, n: usize) -> Result<Vec<Curve>, Box<dyn std::error::Error>> {
+    let mut tracks = vec![];
+    for path in pcm_testdata::list_files(dir)? {
+        let extension = path.extension().map_or(String::new(), |extension| extension.to_string_lossy().to_lowercase());
+        let input = || fs::read_to_string(&path);
+        match extension.as_str() {
+            "gpx" => tracks.extend(curves_from_gpx(&input()?)?),
+            "plt" => tracks.push(curve_from_plt(&input()?)?),
+            _ => {},
+        }
     }
-    length
+    tracks.retain(|track| track.len() >= n);
+    let projection = LocalProjection::centered(&tracks).ok_or(format!("No tracks with at least {n} points in {}.", dir.display()))?;
+    Ok(tracks.iter().map(|track| projection.project(track)).collect())
 }
 
 
@@ -50,320 +39,223 @@ fn curve_length(c: &Curve) -> f64 {
 // === Visualization logic ===
 // ===========================
 
-/// Drawing Free-Space Diagram as an image to disk. If provided, draw steps along the RSD.
-fn draw_fsd(fsd: &FSD, filename: &str, opt_steps: Option<Steps>) -> Result<(), Box<dyn std::error::Error>> {
-    let margin = 20; // 20 pixels margin
-    let width = fsd.n * 20 + 2 * margin;
-    let height = fsd.m * 20 + 2 * margin;
-    let filename = format!("{}.png", filename);
-    let drawing_area = BitMapBackend::new(&filename, (width as u32, height as u32)).into_drawing_area();
-    drawing_area.fill(&WHITE)?;
-
-    let drawing_area = drawing_area.margin(20, 20, 20, 20);
-
-    let n = fsd.n;
-    let m = fsd.m;
-
-    let unreachable = ShapeStyle {
-        color: RED_300.mix(0.6),
-        filled: true,
-        stroke_width: 1,
-    };
-    let reachable = ShapeStyle {
-        color: GREEN_400.mix(0.6),
-        filled: true,
-        stroke_width: 1,
-    };
-    let path = ShapeStyle {
-        color: BLACK.mix(1.0),
-        filled: true,
-        stroke_width: 1,
-    };
-
-    let mut reachable_segments = vec![];
-    let mut unreachable_segments = vec![];
-    
-    // Find reachable and unreachable segments.
-    for j in 0..m {
-        for i in 0..n {
-            for axis in 0..2 {
-                let (w,h) = fsd.dims[axis];
-                let (x,y) = [(i,j), (j,i)][axis];
-                if y < h {
-                    let curr = (axis, x, y);
-                    if let Some(LineBoundary { a, b }) = fsd.segs[curr] {
-                        if a > 0. { unreachable_segments.push(vec![ (axis, x as f64, y as f64    ), (axis, x as f64, y as f64 + a) ]); }
-                                      reachable_segments.push(vec![ (axis, x as f64, y as f64 + a), (axis, x as f64, y as f64 + b) ]);
-                        if b < 1. { unreachable_segments.push(vec![ (axis, x as f64, y as f64 + b), (axis, x as f64, y as f64 + 1.) ]); }
-                    } else {
-                        unreachable_segments.push(vec![ (axis, x as f64, y as f64), (axis, x as f64, y as f64 + 1.) ])
-                    }
-                }
-            }
+/// Parts of interval i1 not covered by interval i2.
+fn interval_difference(i1: Option<(f64, f64)>, i2: Option<(f64, f64)>) -> Vec<(f64, f64)> {
+    match (i1, i2) {
+        (None, _) => vec![],
+        (Some(i1), None) => vec![i1],
+        (Some((a1, b1)), Some((a2, b2))) => {
+            [(a1, b1.min(a2)), (a1.max(b2), b1)].into_iter().filter(|(a, b)| a < b).collect()
         }
     }
-
-    // Draw reachable and unreachable line segments.
-    let height = 20*m as i32;
-    // println!("reachable:");
-    for seg in reachable_segments {
-        // println!("{seg:?}");
-        let seg: Vec<(i32, i32)> = seg.into_iter().map(|(axis, x, y)| {
-            // ((20.*x) as i32, (20.*y) as i32)
-            if axis == 0 { ((20.*x) as i32, height - (20.*y) as i32) }
-            else         { ((20.*y) as i32, height - (20.*x) as i32) }
-        }).collect();
-        drawing_area.draw(&Polygon::new(seg, reachable))?;
-    }
-
-    // println!("unreachable:");
-    for seg in unreachable_segments {
-        // println!("{seg:?}");
-        let seg: Vec<(i32, i32)> = seg.into_iter().map(|(axis, x, y)| {
-            // ((20.*x) as i32, (20.*y) as i32)
-            if axis == 0 { ((20.*x) as i32, height - (20.*y) as i32) }
-            else         { ((20.*y) as i32, height - (20.*x) as i32) }
-        }).collect();
-        drawing_area.draw(&Polygon::new(seg, unreachable))?;
-    }
-
-    if let Some(steps) = opt_steps {
-        for ((x1, y1), (x2, y2)) in zip(&steps,&steps[1..]) {
-            let seg: Vec<(i32, i32)> = vec![((20.* x1) as i32, height - (20.*y1) as i32), ((20.* x2) as i32, height - (20.*y2) as i32)];
-            drawing_area.draw(&Polygon::new(seg, path))?;
-        }
-    }
-
-    Ok(())
 }
 
-fn draw_curves(c1: Curve, c2: Curve, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
-
-    // Setting up drawing area.
+/// Drawing the difference between two Free-Space Diagrams (of equal dimensions) as an image to disk.
+/// Free space present in both is drawn gray, only in the first red, and only in the second green (thick, so changed boundaries stand out).
+/// Returns the number of boundaries whose interval changed.
+fn draw_fsd_diff(fsd1: &FSD, fsd2: &FSD, filename: &str) -> Result<usize, Box<dyn std::error::Error>> {
+    if (fsd1.n, fsd1.m) != (fsd2.n, fsd2.m) {
+        return Err(format!("Diagrams differ in size: {}x{} and {}x{}.", fsd1.n, fsd1.m, fsd2.n, fsd2.m).into());
+    }
     let margin = 20; // 20 pixels margin
-    let width = 400 + 2 * margin;
-    let height = 400 + 2 * margin;
-    let filename = format!("{}.png", filename);
+    let width = fsd1.n * 20 + 2 * margin;
+    let height = fsd1.m * 20 + 2 * margin;
+    let filename = format!("{filename}.png");
     let drawing_area = BitMapBackend::new(&filename, (width as u32, height as u32)).into_drawing_area();
     drawing_area.fill(&WHITE)?;
     let drawing_area = drawing_area.margin(20, 20, 20, 20);
 
-    // Computing boundaries.
-    let pmin = c1.clone().into_iter().chain(c2.clone().into_iter()).reduce(|acc, v| acc.min(&v)).unwrap();
-    let pmax = c1.clone().into_iter().chain(c2.clone().into_iter()).reduce(|acc, v| acc.max(&v)).unwrap();
-    let pdiff = pmax - pmin;
+    let unchanged = ShapeStyle { color: BLACK.mix(0.3), filled: true, stroke_width: 1 };
+    let removed = ShapeStyle { color: RED_300.mix(1.0), filled: true, stroke_width: 3 };
+    let added = ShapeStyle { color: GREEN_400.mix(1.0), filled: true, stroke_width: 3 };
 
-    // Computing curve point positions on drawing area.
-    let vector_to_point = |v| {
-        let position = Vector::new(400., 400.) * (v - pmin) / pdiff;
-        (position.x as i32, position.y as i32)
-    };
-    
-    let seg1: Vec<(i32, i32)> = c1.into_iter().map(vector_to_point).collect();
-    let seg2: Vec<(i32, i32)> = c2.into_iter().map(vector_to_point).collect();
-
-    // Drawing the two polygonal chains.
-    let colorc1 = ShapeStyle {
-        color: RED_300.mix(0.6),
-        filled: true,
-        stroke_width: 1,
-    };
-    let colorc2 = ShapeStyle {
-        color: GREEN_400.mix(0.6),
-        filled: true,
-        stroke_width: 1,
+    let height = 20*fsd1.m as i32;
+    let to_pixel = |axis: usize, x: f64, y: f64| {
+        if axis == 0 { ((20.*x) as i32, height - (20.*y) as i32) }
+        else         { ((20.*y) as i32, height - (20.*x) as i32) }
     };
 
-    for (p1, p2) in zip(&seg1, &seg1[1..]) {
-        drawing_area.draw(&Polygon::new(vec![*p1, *p2], colorc1))?;
-    }
-
-    for (p1, p2) in zip(&seg2, &seg2[1..]) {
-        drawing_area.draw(&Polygon::new(vec![*p1, *p2], colorc2))?;
+    let mut changed = 0;
+    for axis in 0..2 {
+        let (w, h) = fsd1.dims[axis];
+        for x in 0..w {
+            for y in 0..h {
+                let curr = (axis, x, y);
+                let i1 = fsd1.segs[curr].map(|LineBoundary { a, b }| (a, b));
+                let i2 = fsd2.segs[curr].map(|LineBoundary { a, b }| (a, b));
+                if i1 != i2 { changed += 1; }
+                let both = match (i1, i2) {
+                    (Some((a1, b1)), Some((a2, b2))) if a1.max(a2) < b1.min(b2) => vec![(a1.max(a2), b1.min(b2))],
+                    _ => vec![]
+                };
+                for (intervals, style) in [(both, unchanged), (interval_difference(i1, i2), removed), (interval_difference(i2, i1), added)] {
+                    for (a, b) in intervals {
+                        let seg = vec![to_pixel(axis, x as f64, y as f64 + a), to_pixel(axis, x as f64, y as f64 + b)];
+                        drawing_area.draw(&PathElement::new(seg, style))?;
+                    }
+                }
+            }
+        }
     }
 
-
-    Ok(())
+    Ok(changed)
 }
 
-
 // =====================
 // === Testing logic ===
 // =====================
 
-/// Check the presence/absence of fully covered line segments with free/unfree line segments.
-fn check_corner_consistency(fsd: &FSD) -> Result<(), String> {
-    let (n,m) = (fsd.n, fsd.m);
-    for j in 0..m {
-        for i in 0..n {
-            for axis in 0..2 {
-                let (w,h) = fsd.dims[axis];
-                let (x,y) = [(i,j), (j,i)][axis];
-                let opt_curr = if y < h { Some((axis, x, y)) } else { None };
-                let opt_prev = if y > 0 { Some((axis, x, y-1)) } else { None };
-                let has_corner = fsd.corners[(i, j)];
-
-                if !has_corner {
-                    if let Some(curr) = opt_curr {
-                        if let Some(LineBoundary { a, b }) = fsd.segs[curr] {
-                            if a == 0.0 {
-                                return Err(format!("Start of boundary exists at {curr:?} while no corner at (({i},{j})) "));
-                            }
-                        }
-                    }
-                    if let Some(prev) = opt_prev {
-                        if let Some(LineBoundary { a, b }) = fsd.segs[prev] {
-                            if b == 1.0 {
-                                return Err(format!("End of boundary exists at {prev:?} while no corner at (({i},{j})) "));
-                            }
-                        }
-                    }
-                } else { // if has_corner {
-                    if let Some(curr) = opt_curr {
-                        if let Some(LineBoundary { a, b }) = fsd.segs[curr] {
-                            if a >= EPS {
-                                return Err(format!("Start of boundary does not exist at {curr:?} while corner at (({i},{j})) "));
-                            }
-                        } else {
-                            return Err(format!("Boundary does not exist at {curr:?} while corner at (({i},{j})) "));
-                        }
-                    }
-                    if let Some(prev) = opt_prev {
-                        if let Some(LineBoundary { a, b }) = fsd.segs[prev] {
-                            if b < 1.0 - EPS {
-                                return Err(format!("End of boundary does not exist at {prev:?} while corner at (({i},{j})) "));
-                            }
-                        } else {
-                            return Err(format!("Boundary does not exist at {prev:?} while corner at (({i},{j})) "));
-                        }
-                    }
-                } 
-            }
-        }
-    }
-    
-    Ok(())
-}
-
-type Steps = Vec<(f64, f64)>;
-
-/// Check steps result is within distance.
-fn check_steps(c1: Curve, c2: Curve, steps: Vec<(f64, f64)>, eps: f64) -> Result<(), String> {
-
-    // Check monotonic.
-    for ((_i1, _j1), (_i2, _j2)) in zip(&steps, &steps[1..]) {
-        if (_i1 > _i2) || (_j1 > _j2) {
-             return Err(format!("Decreasing from step ({_i1}, {_j1}) to ({_i2}, {_j2})."));
-        }
-    }
 
-    // Check distance while walking is within threshold.
-    for (_i, _j) in steps {
-        let i = _i.floor();
-        let i_off = _i - i;
-        let j = _j.floor();
-        let j_off = _j - j;
-        
-        let p = if i_off == 0. { // Interpolate.
-            c1[i as usize] 
-        } else { (1. - i_off) * c1[i as usize] + i_off * c1[i as usize + 1] };
-
-        let q = if j_off == 0. { // Interpolate.
-            c2[j as usize] 
-        } else { (1. - j_off) * c2[j as usize] + j_off * c2[j as usize + 1] };
-
-        let d = p.distance(q);
-        if !(d < eps + EPS) {
-             return Err(format!("Distance {d} at step ({i}+{i_off}, {j}+{j_off}) should be below threshold {eps}+{EPS}."));
-        }
-    }
-    Ok(())
-}
-
-/// Test validity of running a state.
-// fn run_test(state: State) -> Result<(), Box<dyn std::error::Error>> {
-fn run_test(state: State, testnumber: usize) -> Result<(), String> {
+/// Test validity of running a state, rendering the configured artifacts.
+fn run_test(state: State, testnumber: usize, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
     let State { ps, qs, eps } = state.clone();
+    let render = config.render;
+    let curves_path = config.artifact(&format!("curve_{testnumber}.png"));
 
-    draw_curves(ps.clone(), qs.clone(), format!("curve_{testnumber}").as_str());
+    // Drawn up front (so also for cases failing below), and again with leashes once steps are found.
+    if render.curves {
+        draw_curves(ps.clone(), qs.clone(), &curves_path, None).map_err(|e| e.to_string())?;
+    }
 
     let fsd = FSD::new(ps.clone(), qs.clone(), eps);
-    check_corner_consistency(&fsd)?;
-    draw_fsd(&fsd, format!("fsd_{testnumber}").as_str(), None);
+    fsd.validate()?;
+    if render.fsd {
+        draw_fsd(&fsd, config.artifact(&format!("fsd_{testnumber}.png")), None).map_err(|e| e.to_string())?;
+    }
 
     let rsd = fsd.to_rsd();
-    draw_fsd(&rsd, format!("rsd_{testnumber}").as_str(), None);
-    let opt_steps = rsd.pcm_steps()?;
-    draw_fsd(&rsd, format!("path_{testnumber}").as_str(), opt_steps.clone());
+    rsd.validate_reachability(&fsd)?;
+    if render.rsd {
+        draw_fsd(&rsd, config.artifact(&format!("rsd_{testnumber}.png")), None).map_err(|e| e.to_string())?;
+    }
+    let opt_steps = rsd.pcm_steps();
+    if render.curves && opt_steps.is_some() {
+        draw_curves(ps.clone(), qs.clone(), &curves_path, opt_steps.clone()).map_err(|e| e.to_string())?;
+    }
+    if render.path {
+        draw_fsd(&rsd, config.artifact(&format!("path_{testnumber}.png")), opt_steps.clone()).map_err(|e| e.to_string())?;
+    }
+    if let (Some(frames), Some(steps)) = (config.animate, &opt_steps) {
+        animate_walk(&ps, &qs, &rsd, steps, config.artifact(&format!("walk_{testnumber}.gif")), frames, &DrawOptions::default().with_legend_eps(eps)).map_err(|e| e.to_string())?;
+    }
 
     let partial = rsd.check_pcm();
     println!("Is there a partial curve match?: {partial:?}.");
     if partial {
         if opt_steps.is_none() {
-            return Err(format!("Should find steps if partial curve match is true."));
+            return Err("Should find steps if partial curve match is true.".into());
         }
-        check_steps(ps, qs, opt_steps.unwrap(), eps)?;
+        validate_steps(&ps, &qs, &opt_steps.unwrap(), eps)?;
     }
     
     Ok(())
 }
 
 
-// ========================
-// === IO functionality ===
-// ========================
+// ==========================
+// === Backend comparison ===
+// ==========================
 
-/// Testing state for storage/retrieval.
-#[derive(Serialize, Deserialize, Clone)]
-struct State {
-    ps: Curve,
-    qs: Curve,
-    eps: f64
+/// Matching implementation to compare against the reference (the first backend in [`BACKENDS`]).
+struct Backend {
+    name: &'static str,
+    /// Compute steps of a partial curve match (if any).
+    run: fn(&State) -> Result<Option<Steps>, PcmError>,
+    /// Whether the matched interval should equal the reference (otherwise only the decision is compared).
+    same_interval: bool,
 }
 
-/// Listing files in subfolder.
-fn list_files_in_subfolder<P: AsRef<Path>>(path: P) -> std::io::Result<Vec<String>> {
-    let mut files = Vec::new();
-
-    for entry in fs::read_dir(path)? {
-        let entry = entry?;
-        let path = entry.path();
+/// Backends to run on every case, starting with the reference.
+const BACKENDS: [Backend; 3] = [
+    Backend { name: "rsd", run: |s| Ok(FSD::try_new(s.ps.clone(), s.qs.clone(), s.eps)?.to_rsd().pcm_steps()), same_interval: true },
+    Backend { name: "max_coverage", run: |s| max_coverage_steps(s.ps.clone(), s.qs.clone(), s.eps), same_interval: false },
+    // Discretized, thus it may miss matches which require passing through narrow free space.
+    Backend { name: "lexicographic", run: |s| lexicographic_steps(&s.ps, &s.qs, s.eps, 16), same_interval: false },
+];
+
+/// Run every backend on the case, draw their paths along the reference RSD and return discrepancies.
+fn compare_backends(state: &State, testnumber: usize, config: &Config) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let rsd = FSD::try_new(state.ps.clone(), state.qs.clone(), state.eps)?.to_rsd();
+    let mut results = vec![];
+    for backend in &BACKENDS {
+        let opt_steps = (backend.run)(state)?;
+        draw_fsd(&rsd, config.artifact(&format!("backend_{testnumber}_{}.png", backend.name)), opt_steps.clone()).map_err(|e| e.to_string())?;
+        results.push(opt_steps.map(|steps| (steps[0].1, steps.last().unwrap().1)));
+    }
 
-        if path.is_file() {
-            if let Some(path_str) = path.to_str() {
-                files.push(path_str.to_string());
-            }
+    let mut discrepancies = vec![];
+    let reference = results[0];
+    for (backend, result) in zip(&BACKENDS, &results).skip(1) {
+        match (reference, result) {
+            (Some(_), None) | (None, Some(_)) => discrepancies.push(format!(
+                "case {testnumber}: decision {} = {}, {} = {}", BACKENDS[0].name, reference.is_some(), backend.name, result.is_some())),
+            (Some((a1, b1)), Some((a2, b2))) if backend.same_interval && ((a1 - a2).abs() > EPS || (b1 - b2).abs() > EPS) => discrepancies.push(format!(
+                "case {testnumber}: interval {} = ({a1}, {b1}), {} = ({a2}, {b2})", BACKENDS[0].name, backend.name)),
+            _ => {}
         }
     }
+    Ok(discrepancies)
+}
+
 
-    Ok(files)
+// ===================
+// === Experiments ===
+// ===================
+
+/// Minimal matching threshold of a base curve against increasingly perturbed copies of it, as (noise, eps) pairs.
+/// Points move uniformly over the disk of radius level (see [`perturb_curve`]) with the same seed at every level,
+/// so the offsets grow linearly with the level and keep their directions.
+fn robustness_sweep(base: &Curve, levels: &[f64], seed: u64) -> Result<Vec<(f64, f64)>, PcmError> {
+    levels.iter().map(|&level| {
+        let qs = perturb_curve(base, level, &mut seeded(seed));
+        Ok((level, min_partial_eps(base, &qs, 1e-6)?))
+    }).collect()
 }
 
-/// Write state to testdata folder as a new testcase to debug.
-fn write_new_testcase(state: State) -> Result<(), Box<dyn std::error::Error>> {
-    let bin = bincode::serialize(&state)?;
-    fs::create_dir("testdata"); // Folder probably already exists, then will throw error.
-    let files = list_files_in_subfolder("testdata")?;
-    let n = files.len();
-    let file_path = Path::new("testdata").join(format!("case_{n}.bin"));
-    let mut file = File::create(file_path)?;
-    file.write_all(&bin)?;
+/// Plot minimal matching threshold against perturbation magnitude.
+fn draw_sweep(results: &[(f64, f64)], filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let filename = format!("{filename}.png");
+    let drawing_area = BitMapBackend::new(&filename, (640, 480)).into_drawing_area();
+    drawing_area.fill(&WHITE)?;
+
+    let max_noise = results.iter().map(|r| r.0).fold(0., f64::max).max(EPS);
+    let max_eps = results.iter().map(|r| r.1).fold(0., f64::max).max(EPS);
+    let mut chart = ChartBuilder::on(&drawing_area)
+        .margin(20)
+        .x_label_area_size(30)
+        .y_label_area_size(40)
+        .build_cartesian_2d(0.0..max_noise, 0.0..1.1 * max_eps)?;
+    chart.configure_mesh().x_desc("noise").y_desc("minimal eps").draw()?;
+    chart.draw_series(LineSeries::new(results.iter().copied(), &RED))?;
+    chart.draw_series(results.iter().map(|&p| Circle::new(p, 3, RED.filled())))?;
+
+    drawing_area.present()?;
     Ok(())
 }
 
-/// Read states from disk, should represent testcases previously crashed (thus to debug).
-fn read_cases() -> Result<Vec<State>, Box<dyn std::error::Error>> {
-    let files = list_files_in_subfolder("testdata")?;
-    let mut result = vec![];
-    for file in files {
-        let mut file = File::open(file)?;
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer);
-        let state = bincode::deserialize(&buffer)?;
-        result.push(state);
-    }
+/// Render matrix (rows of ps curves, columns of qs curves) as heatmap, from blue (lowest value) to red (highest value).
+fn draw_heatmap(matrix: &[Vec<f64>], filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let rows = matrix.len();
+    let cols = matrix.first().map_or(0, |row| row.len());
+    let filename = format!("{filename}.png");
+    let drawing_area = BitMapBackend::new(&filename, (640, 640)).into_drawing_area();
+    drawing_area.fill(&WHITE)?;
 
-    Ok(result)
+    let lo = matrix.iter().flatten().copied().fold(f64::INFINITY, f64::min);
+    let hi = matrix.iter().flatten().copied().fold(f64::NEG_INFINITY, f64::max);
+    let mut chart = ChartBuilder::on(&drawing_area)
+        .margin(20)
+        .x_label_area_size(30)
+        .y_label_area_size(40)
+        .build_cartesian_2d(0..cols, 0..rows)?;
+    chart.configure_mesh().disable_mesh().x_desc("qs").y_desc("ps").draw()?;
+    chart.draw_series(matrix.iter().enumerate().flat_map(|(i, row)| row.iter().enumerate().map(move |(j, &v)| {
+        let t = if hi > lo { (v - lo) / (hi - lo) } else { 0. };
+        Rectangle::new([(j, i), (j + 1, i + 1)], HSLColor(2. / 3. * (1. - t), 0.8, 0.5).filled())
+    })))?;
+
+    drawing_area.present()?;
+    Ok(())
 }
 
 
@@ -371,41 +263,155 @@ fn read_cases() -> Result<Vec<State>, Box<dyn std::error::Error>> {
 // === Executable ===
 // ==================
 
-const DISCOVER: bool = true;
-const RUN_COUNT: usize = 10;
+#[cfg(feature = "viewer")]
+fn view(cases: Vec<(String, State)>) -> Result<(), Box<dyn std::error::Error>> {
+    viewer::run(cases)
+}
+
+#[cfg(not(feature = "viewer"))]
+fn view(_: Vec<(String, State)>) -> Result<(), Box<dyn std::error::Error>> {
+    Err("The interactive viewer is not available, build with the viewer feature.".into())
+}
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
 
-    let cases = 
-    if DISCOVER {
-        (0..RUN_COUNT).map(|_| {
-            let ps = random_curve(5, 2.);
-            // let c2 = translate_curve(ps, Vector{ x: 3. , y: 1. });
-            // let qs = perturb_curve(ps.clone(), 1.);
-            // let qs = random_curve(3, 2.);
-            let qs = ps.clone();
-            State { ps, qs, eps: 1. }
-        }).collect()
+    let config = Config::from_args();
+    fs::create_dir_all(&config.output)?;
+    let artifact = |name: &str| config.artifact(name).display().to_string();
+    // Random cases are reproducible by passing the printed seed.
+    let seed = config.seed.unwrap_or_else(|| if config.mode == Mode::Discover { rand::thread_rng().gen() } else { 42 });
+
+    if config.mode == Mode::Sweep {
+        let base = random_curve(10, 10., &mut seeded(seed));
+        let levels: Vec<f64> = (0..=config.levels).map(|k| k as f64 * 0.1).collect();
+        let results = robustness_sweep(&base, &levels, seed)?;
+        for (noise, eps) in &results {
+            println!("noise {noise:.2}: minimal eps {eps:.6}");
+        }
+        draw_sweep(&results, &artifact("sweep"))?;
+        return Ok(());
+    }
+
+    if config.mode == Mode::Heatmap {
+        let mut rng = seeded(seed);
+        let family: Vec<Curve> = (0..config.size).map(|_| random_curve(6, 10., &mut rng)).collect();
+        let mut others: Vec<Curve> = family.iter().map(|c| perturb_curve(c, 0.5, &mut rng)).collect();
+        others.extend((0..config.size).map(|_| random_curve(8, 10., &mut rng)));
+        let matrix = min_eps_matrix(&family, &others, 1e-4)?;
+        fs::write(artifact("heatmap.csv"), matrix_to_csv(&matrix, &ExportOptions::default().with_coordinate_decimals(4)))?;
+        draw_heatmap(&matrix, &artifact("heatmap"))?;
+        return Ok(());
+    }
+
+    if config.mode == Mode::Calibrate {
+        let mut rng = seeded(seed);
+        let pairs: Vec<(Curve, Curve, bool)> = (0..config.pairs).map(|k| {
+            let ps = random_curve(6, 10., &mut rng);
+            let is_match = k % 2 == 0;
+            let qs = if is_match { perturb_curve(&ps, 0.5, &mut rng) } else { random_curve(8, 10., &mut rng) };
+            (ps, qs, is_match)
+        }).collect();
+        let calibration = calibrate(&pairs, 1e-4)?;
+        for point in &calibration.points {
+            println!("eps {:.4}: precision {:.3}, recall {:.3}, f1 {:.3}", point.eps, point.precision, point.recall, point.f1());
+        }
+        if let Some(point) = calibration.recommendation() {
+            println!("Recommended eps {:.4} (precision {:.3}, recall {:.3}).", point.eps, point.precision, point.recall);
+        }
+        return Ok(());
+    }
+
+    let discover = config.mode == Mode::Discover || (config.mode == Mode::View && read_cases(&config.input)?.is_empty());
+    let cases: Vec<(String, State)> = 
+    if discover {
+        println!("Discovering {} cases with seed {seed}.", config.runs);
+        let mut rng = seeded(seed);
+        if let Some(dir) = &config.tracks {
+            let tracks = read_tracks(dir, config.length)?;
+            println!("Read {} tracks with at least {} points.", tracks.len(), config.length);
+            (0..config.runs).map(|i| {
+                let qs = tracks[rng.gen_range(0..tracks.len())].clone();
+                let start = rng.gen_range(0..=qs.len() - config.length);
+                let ps = qs[start..start + config.length].to_vec();
+                (format!("run_{i}"), State { ps, qs, eps: config.eps })
+            }).collect()
+        } else {
+            (0..config.runs).map(|i| {
+                let ps = random_curve(config.length, config.field, &mut rng);
+                // let c2 = translate_curve(&ps, Vector{ x: 3. , y: 1. });
+                // let qs = perturb_curve(&ps, 1., &mut rng);
+                // let qs = random_curve(3, 2., &mut rng);
+                let qs = ps.clone();
+                (format!("run_{i}"), State { ps, qs, eps: config.eps })
+            }).collect()
+        }
     } else {
-        let mut r = read_cases()?;
-        r.truncate(RUN_COUNT);
+        let migrated = migrate(&config.input)?;
+        if migrated > 0 {
+            println!("Renamed {migrated} test cases to their content hash.");
+        }
+        // Every state is paired with its filename (which acts as job identifier for checkpointing).
+        let mut r: Vec<(String, State)> = read_cases(&config.input)?.into_iter().map(|(path, state)| (path.display().to_string(), state)).collect();
+        r.truncate(config.runs);
         r
     };
 
-    for (i, case) in cases.into_iter().enumerate() {
-        let res_test = run_test(case.clone(), i);
+    if config.mode == Mode::View {
+        return view(cases);
+    }
+
+    // Checkpointing only makes sense when replaying stored cases (discovered cases are random).
+    let mut opt_checkpoint = if discover { None } else { Some(Checkpoint::open(&config.checkpoint, config.resume)?.with_interval(10)) };
+
+    let mut opt_audit_log = match &config.audit_log { Some(path) => Some(AuditLog::open(path)?), None => None };
+
+    let mut discrepancies = vec![];
+
+    for (i, (id, case)) in cases.into_iter().enumerate() {
+        if opt_checkpoint.as_ref().is_some_and(|checkpoint| checkpoint.is_done(&id)) {
+            println!("Skipping test case {id} (completed in previous run).");
+            continue;
+        }
+        if let Some(audit_log) = &mut opt_audit_log {
+            audited_partial_curve(audit_log, case.ps.clone(), case.qs.clone(), case.eps, true)?;
+        }
+        if config.precision_check {
+            let report = precision_check(&case.ps, &case.qs, case.eps)?;
+            if !report.is_consistent(1e-3) || report.is_fragile() {
+                println!("Test case {i} is numerically fragile: {report:?}");
+            }
+        }
+        if let Some(eps) = config.diff_eps {
+            let fsd1 = FSD::new(case.ps.clone(), case.qs.clone(), case.eps);
+            let fsd2 = FSD::new(case.ps.clone(), case.qs.clone(), eps);
+            let changed = draw_fsd_diff(&fsd1, &fsd2, &artifact(&format!("diff_{i}")))?;
+            println!("Test case {i}: {changed} boundaries changed at eps {eps}.");
+        }
+        if config.backend_report.is_some() {
+            discrepancies.extend(compare_backends(&case, i, &config)?);
+        }
+        let res_test = run_test(case.clone(), i, &config);
+        // Failing cases are run again on resume.
+        if let (Some(checkpoint), Ok(_)) = (&mut opt_checkpoint, &res_test) {
+            checkpoint.mark_done(&id)?;
+        }
         if res_test.is_err() {
             // Print we got an error.
-            println!("Test case {} failed. Error message:", i);
+            println!("Test case {i} failed. Error message:");
             println!("{:?}", res_test.unwrap_err());
             // Only write new tast case in disovery mode, 
             //   otherwise we are duplicating testcases 
             //   (writing new case we just read).
-            if DISCOVER { 
-                write_new_testcase(case);
+            if discover { 
+                write_case(&config.input, &case)?;
             }
         }
     }
 
+    if let Some(path) = &config.backend_report {
+        println!("Found {} backend discrepancies.", discrepancies.len());
+        fs::write(path, discrepancies.join("\n"))?;
+    }
+
     Ok(())
 }
\ No newline at end of file
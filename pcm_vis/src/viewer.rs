@@ -0,0 +1,214 @@
+//! Interactive viewer (using eframe): pan and zoom the diagram, hover a cell to inspect its boundaries and segments, and scrub eps.
+use eframe::egui::{self, Align2, Color32, FontId, Pos2, Rect, Sense, Stroke, Vec2};
+use pcm::{min_partial_eps, LineBoundary, OptLineBoundary, Vector, FSD};
+use pcm_testdata::State;
+use pcm_vis::{leashes, Palette, Steps};
+use plotters::style::RGBAColor;
+
+
+/// Cases to step through, with the diagram of the current case at the current threshold.
+struct Viewer {
+    cases: Vec<(String, State)>,
+    current: usize,
+    eps: f64,
+    fsd: FSD,
+    rsd: FSD,
+    opt_steps: Option<Steps>,
+    /// Threshold and case the diagrams are computed for.
+    computed: (f64, usize),
+    /// Pixels per cell (before zooming).
+    cell: f32,
+    zoom: f32,
+    pan: Vec2,
+    /// Cell (i, j) under the pointer.
+    hovered: Option<(usize, usize)>,
+    palette: Palette,
+}
+
+fn color(c: RGBAColor) -> Color32 {
+    Color32::from_rgba_unmultiplied(c.0, c.1, c.2, (c.3 * 255.) as u8)
+}
+
+/// Interval of a cell boundary as text.
+fn interval(boundary: OptLineBoundary) -> String {
+    boundary.map_or("empty".to_string(), |LineBoundary { a, b }| format!("[{a:.4}, {b:.4}]"))
+}
+
+impl Viewer {
+
+    fn new(cases: Vec<(String, State)>) -> Viewer {
+        let State { ps, qs, eps } = cases[0].1.clone();
+        let fsd = FSD::new(ps, qs, eps);
+        let rsd = fsd.to_rsd();
+        let opt_steps = rsd.pcm_steps();
+        Viewer { cases, current: 0, eps, fsd, rsd, opt_steps, computed: (eps, 0), cell: 40., zoom: 1., pan: Vec2::ZERO, hovered: None, palette: Palette::default() }
+    }
+
+    fn state(&self) -> &State {
+        &self.cases[self.current].1
+    }
+
+    /// Recompute the diagrams if the threshold or case changed.
+    fn update_diagrams(&mut self) {
+        if self.computed == (self.eps, self.current) {
+            return;
+        }
+        let State { ps, qs, .. } = self.state().clone();
+        self.fsd = FSD::new(ps, qs, self.eps);
+        self.rsd = self.fsd.to_rsd();
+        self.opt_steps = self.rsd.pcm_steps();
+        self.computed = (self.eps, self.current);
+    }
+
+    /// Switch to case k, at its own threshold.
+    fn select(&mut self, k: usize) {
+        self.current = k;
+        self.eps = self.state().eps;
+        self.hovered = None;
+    }
+
+    fn controls(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Case");
+        ui.label(self.cases[self.current].0.as_str());
+        ui.horizontal(|ui| {
+            if ui.add_enabled(self.current > 0, egui::Button::new("Previous")).clicked() {
+                self.select(self.current - 1);
+            }
+            ui.label(format!("{} / {}", self.current + 1, self.cases.len()));
+            if ui.add_enabled(self.current + 1 < self.cases.len(), egui::Button::new("Next")).clicked() {
+                self.select(self.current + 1);
+            }
+        });
+        let State { ps, qs, eps } = self.state().clone();
+        ui.label(format!("{} points on ps, {} points on qs, stored eps {eps}", ps.len(), qs.len()));
+
+        ui.separator();
+        ui.heading("Threshold");
+        // Up to the largest distance between any two points, beyond which everything matches.
+        let diameter = ps.iter().flat_map(|p| qs.iter().map(move |q| p.distance(*q))).fold(0., f64::max);
+        ui.add(egui::Slider::new(&mut self.eps, 1e-3..=diameter.max(2. * eps)).logarithmic(true).text("eps"));
+        if ui.button("Minimal eps").clicked() {
+            if let Ok(eps) = min_partial_eps(&ps, &qs, 1e-6) {
+                self.eps = eps + 1e-6;
+            }
+        }
+        self.update_diagrams();
+        ui.label(if self.opt_steps.is_some() { "Partial curve match." } else { "No partial curve match." });
+
+        ui.separator();
+        ui.heading("View");
+        ui.label("Drag to pan, scroll to zoom.");
+        if ui.button("Reset view").clicked() {
+            (self.zoom, self.pan) = (1., Vec2::ZERO);
+        }
+
+        ui.separator();
+        ui.heading("Cell");
+        match self.hovered {
+            Some((i, j)) => {
+                ui.label(format!("Cell ({i}, {j}): segment {i} of ps against segment {j} of qs."));
+                ui.label(format!("Left boundary: free {}, reachable {}", interval(self.fsd.segs[(0, i, j)]), interval(self.rsd.segs[(0, i, j)])));
+                ui.label(format!("Bottom boundary: free {}, reachable {}", interval(self.fsd.segs[(1, j, i)]), interval(self.rsd.segs[(1, j, i)])));
+                ui.label(format!("Right boundary: free {}, reachable {}", interval(self.fsd.segs[(0, i + 1, j)]), interval(self.rsd.segs[(0, i + 1, j)])));
+                ui.label(format!("Top boundary: free {}, reachable {}", interval(self.fsd.segs[(1, j + 1, i)]), interval(self.rsd.segs[(1, j + 1, i)])));
+            },
+            None => { ui.label("Hover a cell of the diagram."); },
+        }
+    }
+
+    /// Draw the diagram (reachable parts on top of the free parts), pan/zoom it and track the hovered cell.
+    fn diagram(&mut self, ui: &mut egui::Ui) {
+        let (response, painter) = ui.allocate_painter(ui.available_size(), Sense::click_and_drag());
+        if response.dragged() {
+            self.pan += response.drag_delta();
+        }
+        if response.hovered() {
+            let scroll = ui.input(|input| input.smooth_scroll_delta.y);
+            self.zoom = (self.zoom * (1. + scroll / 200.)).clamp(0.1, 20.);
+        }
+        let (n, m) = (self.fsd.n, self.fsd.m);
+        let cell = self.cell * self.zoom;
+        let origin = response.rect.left_bottom() + Vec2::new(20., -20.) + self.pan;
+        let to_screen = |(i, j): (f64, f64)| Pos2::new(origin.x + cell * i as f32, origin.y - cell * j as f32);
+        let width = 2. * self.zoom.sqrt();
+
+        // Boundaries: blocked, free but unreachable (faded) and reachable.
+        let (blocked, free, reachable) = (color(self.palette.blocked), color(self.palette.free).gamma_multiply(0.4), color(self.palette.free));
+        for axis in 0..2 {
+            let (w, h) = self.fsd.dims[axis];
+            for x in 0..w {
+                for y in 0..h {
+                    let at = |off: f64| to_screen(if axis == 0 { (x as f64, y as f64 + off) } else { (y as f64 + off, x as f64) });
+                    painter.line_segment([at(0.), at(1.)], Stroke::new(width, blocked));
+                    for (boundary, color) in [(self.fsd.segs[(axis, x, y)], free), (self.rsd.segs[(axis, x, y)], reachable)] {
+                        if let Some(LineBoundary { a, b }) = boundary {
+                            painter.line_segment([at(a), at(b)], Stroke::new(width, color));
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(steps) = &self.opt_steps {
+            for pair in steps.windows(2) {
+                painter.line_segment([to_screen(pair[0]), to_screen(pair[1])], Stroke::new(width, color(self.palette.path)));
+            }
+        }
+
+        // Hovered cell.
+        self.hovered = response.hover_pos().and_then(|p| {
+            let (i, j) = ((p.x - origin.x) / cell, (origin.y - p.y) / cell);
+            (i >= 0. && j >= 0. && (i as usize) < n - 1 && (j as usize) < m - 1).then_some((i as usize, j as usize))
+        });
+        if let Some((i, j)) = self.hovered {
+            let rect = Rect::from_two_pos(to_screen((i as f64, j as f64)), to_screen((i as f64 + 1., j as f64 + 1.)));
+            painter.rect_stroke(rect, 0., Stroke::new(1., Color32::BLACK));
+        }
+        painter.text(response.rect.left_top(), Align2::LEFT_TOP, format!("eps = {:.6}", self.eps), FontId::proportional(14.), Color32::BLACK);
+    }
+
+    /// Draw both curves fitted to the available space, with leashes along the steps and the hovered segments highlighted.
+    fn curves(&self, ui: &mut egui::Ui) {
+        let (response, painter) = ui.allocate_painter(ui.available_size(), Sense::hover());
+        let State { ps, qs, .. } = self.state();
+        let pmin = ps.iter().chain(qs).fold(ps[0], |acc, p| acc.min(p));
+        let pmax = ps.iter().chain(qs).fold(ps[0], |acc, p| acc.max(p));
+        let rect = response.rect.shrink(20.);
+        let scale = (rect.width() as f64 / (pmax.x - pmin.x).max(1e-9)).min(rect.height() as f64 / (pmax.y - pmin.y).max(1e-9));
+        let to_screen = |p: Vector| Pos2::new(rect.left() + (scale * (p.x - pmin.x)) as f32, rect.bottom() - (scale * (p.y - pmin.y)) as f32);
+
+        if let Some(steps) = &self.opt_steps {
+            for (p, q) in leashes(ps, qs, steps, 40) {
+                painter.line_segment([to_screen(p), to_screen(q)], Stroke::new(1., Color32::GRAY));
+            }
+        }
+        let hovered = self.hovered;
+        for (curve, c, highlight) in [(ps, color(self.palette.curve1), hovered.map(|(i, _)| i)), (qs, color(self.palette.curve2), hovered.map(|(_, j)| j))] {
+            for (k, pair) in curve.windows(2).enumerate() {
+                let width = if highlight == Some(k) { 4. } else { 1.5 };
+                painter.line_segment([to_screen(pair[0]), to_screen(pair[1])], Stroke::new(width, c));
+            }
+        }
+    }
+}
+
+impl eframe::App for Viewer {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::SidePanel::left("controls").min_width(260.).show(ctx, |ui| self.controls(ui));
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.columns(2, |columns| {
+                self.diagram(&mut columns[0]);
+                self.curves(&mut columns[1]);
+            });
+        });
+    }
+}
+
+/// Open the viewer on the cases (blocks until its window is closed).
+pub fn run(cases: Vec<(String, State)>) -> Result<(), Box<dyn std::error::Error>> {
+    if cases.is_empty() {
+        return Err("No cases to view.".into());
+    }
+    let viewer = Viewer::new(cases);
+    eframe::run_native("pcm_vis", eframe::NativeOptions::default(), Box::new(|_| Ok(Box::new(viewer) as Box<dyn eframe::App>)))?;
+    Ok(())
+}
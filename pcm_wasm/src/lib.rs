@@ -0,0 +1,62 @@
+//! JavaScript bindings for partial curve matching (build with `build_wasm_module.sh`).
+//!
+//! Curves are passed as flat `Float64Array`s of interleaved coordinates `[x0, y0, x1, y1, ...]`.
+use pcm::{Curve, PcmError, Vector, FSD};
+use wasm_bindgen::prelude::*;
+
+
+/// Curve constructed from interleaved coordinates.
+#[wasm_bindgen(js_name = Curve)]
+pub struct WasmCurve(Curve);
+
+#[wasm_bindgen(js_class = Curve)]
+impl WasmCurve {
+
+    /// Construct a curve out of interleaved coordinates `[x0, y0, x1, y1, ...]`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(coords: &[f64]) -> Result<WasmCurve, JsError> {
+        to_curve(coords).map(WasmCurve).map_err(|e| JsError::new(&e.to_string()))
+    }
+
+    /// Number of points.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Interleaved coordinates `[x0, y0, x1, y1, ...]`.
+    pub fn coords(&self) -> Vec<f64> {
+        self.0.iter().flat_map(|p| [p.x, p.y]).collect()
+    }
+}
+
+/// Check whether ps matches some subcurve of qs within distance threshold eps.
+#[wasm_bindgen(js_name = checkPcm)]
+pub fn check_pcm(ps: &WasmCurve, qs: &WasmCurve, eps: f64) -> Result<bool, JsError> {
+    let fsd = FSD::try_new(ps.0.clone(), qs.0.clone(), eps).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(fsd.check_pcm())
+}
+
+/// Steps of a partial curve matching as interleaved parameters `[i0, j0, i1, j1, ...]` (i on ps, j on qs), undefined without a match.
+#[wasm_bindgen(js_name = pcmSteps)]
+pub fn pcm_steps(ps: &WasmCurve, qs: &WasmCurve, eps: f64) -> Result<Option<Vec<f64>>, JsError> {
+    let fsd = FSD::try_new(ps.0.clone(), qs.0.clone(), eps).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(fsd.to_rsd().pcm_steps().map(|steps| steps.into_iter().flat_map(|(i, j)| [i, j]).collect()))
+}
+
+/// Interval `[start, end]` of the subcurve of qs matched by ps, undefined without a match.
+#[wasm_bindgen(js_name = partialCurve)]
+pub fn partial_curve(ps: &WasmCurve, qs: &WasmCurve, eps: f64) -> Result<Option<Vec<f64>>, JsError> {
+    let opt = pcm::try_partial_curve(ps.0.clone(), qs.0.clone(), eps).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(opt.map(|(start, end)| vec![start, end]))
+}
+
+/// Interpret interleaved coordinates as a curve.
+fn to_curve(coords: &[f64]) -> Result<Curve, PcmError> {
+    if coords.len() % 2 != 0 {
+        return Err(PcmError::InvalidArgument(format!("Expected interleaved (x, y) coordinates, got an odd number ({}) of values.", coords.len())));
+    }
+    let curve: Curve = coords.chunks_exact(2).map(|c| Vector::new(c[0], c[1])).collect();
+    pcm::curve::validate_curve(&curve)?;
+    Ok(curve)
+}
@@ -0,0 +1,23 @@
+use proptest::{arbitrary::{any, Arbitrary}, collection::{vec, SizeRange}, strategy::{BoxedStrategy, Strategy}};
+
+use crate::{curve::Curve, vector::Vector};
+
+
+/// Coordinates are drawn from `[-FIELD, FIELD)`, shrinking towards the origin.
+pub const FIELD: f64 = 10.;
+
+impl Arbitrary for Vector {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Vector>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (-FIELD..FIELD, -FIELD..FIELD).prop_map(|(x, y)| Vector::new(x, y)).boxed()
+    }
+}
+
+/// Curves with a number of points within len, shrinking by dropping points and moving the remaining ones towards the origin.
+///
+/// Prefer this over `any::<Curve>()`, which may generate curves of fewer than two points.
+pub fn curve(len: impl Into<SizeRange>) -> impl Strategy<Value = Curve> {
+    vec(any::<Vector>(), len)
+}
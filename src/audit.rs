@@ -0,0 +1,113 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use serde_derive::Serialize;
+
+use crate::{curve::{arc_length, content_hash, Curve}, error::PcmError, export::ExportOptions, partial_curve::{min_partial_eps, try_partial_curve}};
+
+
+/// Precision the minimal threshold is computed up to (when requested).
+const MIN_EPS_TOLERANCE: f64 = 1e-6;
+
+/// Content hash of a curve as hexadecimal string (see [`content_hash`]).
+pub fn curve_hash(c: &Curve) -> String {
+    format!("{:016x}", content_hash(c))
+}
+
+/// A single matching decision, written as one JSON object per line.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AuditRecord {
+    /// Seconds since the unix epoch at which the call finished.
+    pub timestamp: f64,
+    pub ps_hash: String,
+    pub qs_hash: String,
+    pub ps_len: usize,
+    pub qs_len: usize,
+    /// Distance threshold the decision was made at.
+    pub eps: f64,
+    /// Whether the minimal threshold was computed as part of the call.
+    pub with_min_eps: bool,
+    /// Whether ps partially matches a subcurve of qs.
+    pub decision: bool,
+    /// Matched subcurve of qs (as curve parameters).
+    pub interval: Option<(f64, f64)>,
+    /// Smallest threshold at which a partial match exists.
+    pub min_eps: Option<f64>,
+    /// Arc length of the matched subcurve of qs.
+    pub matched_length: Option<f64>,
+    /// Wall-clock duration of the call.
+    pub elapsed_ms: f64,
+    /// Error message if the input was rejected.
+    pub error: Option<String>,
+}
+
+/// Append-only JSON-lines log of matching decisions.
+pub struct AuditLog {
+    writer: BufWriter<File>,
+    options: ExportOptions,
+}
+impl AuditLog {
+
+    /// Open (or create) the log file, records are appended to existing content.
+    pub fn open<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let file = OpenOptions::new().append(true).create(true).open(path)?;
+        Ok(AuditLog { writer: BufWriter::new(file), options: ExportOptions::default() })
+    }
+
+    /// Round written values: the interval to step precision, thresholds and matched length to coordinate precision.
+    pub fn with_options(mut self, options: ExportOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Write a record as a single line, flushed immediately so the trail survives crashes.
+    pub fn write(&mut self, record: &AuditRecord) -> std::io::Result<()> {
+        let o = &self.options;
+        let record = AuditRecord {
+            eps: o.coordinate(record.eps),
+            interval: record.interval.map(|(a, b)| (o.step(a), o.step(b))),
+            min_eps: record.min_eps.map(|v| o.coordinate(v)),
+            matched_length: record.matched_length.map(|v| o.coordinate(v)),
+            ..record.clone()
+        };
+        serde_json::to_writer(&mut self.writer, &record)?;
+        writeln!(self.writer)?;
+        self.writer.flush()
+    }
+}
+
+/// Like [`try_partial_curve`], additionally writing the decision to the audit log.
+///
+/// Computing the minimal threshold requires a bisection over the decision, so it is only done if `with_min_eps` is set.
+pub fn audited_partial_curve(log: &mut AuditLog, ps: Curve, qs: Curve, eps: f64, with_min_eps: bool) -> Result<Option<(f64, f64)>, PcmError> {
+    let start = Instant::now();
+    let ps_hash = curve_hash(&ps);
+    let qs_hash = curve_hash(&qs);
+    let (ps_len, qs_len) = (ps.len(), qs.len());
+
+    let result = try_partial_curve(ps.clone(), qs.clone(), eps).and_then(|interval| {
+        let min_eps = if with_min_eps { Some(min_partial_eps(&ps, &qs, MIN_EPS_TOLERANCE)?) } else { None };
+        Ok((interval, min_eps))
+    });
+    let elapsed_ms = 1000. * start.elapsed().as_secs_f64();
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0., |d| d.as_secs_f64());
+
+    let (interval, min_eps, error) = match &result {
+        Ok((interval, min_eps)) => (*interval, *min_eps, None),
+        Err(e) => (None, None, Some(e.to_string()))
+    };
+    let record = AuditRecord {
+        timestamp, ps_hash, qs_hash, ps_len, qs_len, eps, with_min_eps,
+        decision: interval.is_some(),
+        interval,
+        min_eps,
+        matched_length: interval.map(|(a, b)| arc_length(&qs, b) - arc_length(&qs, a)),
+        elapsed_ms,
+        error,
+    };
+    log.write(&record)?;
+
+    result.map(|(interval, _)| interval)
+}
@@ -0,0 +1,72 @@
+use crate::{bvh::Aabb, curve::{point_on, validate_curve, validate_eps, Curve, CurveExt}, error::PcmError, fsd::FSD, parallel};
+
+
+/// Outcome of matching the query against a single candidate, see [`match_many`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidateMatch {
+    /// Largest distance between matched points along the steps (at most the threshold, up to rounding), infinite without a match.
+    pub eps: f64,
+    /// Positions (parameter on the query, parameter on the candidate) walked along, empty without a match.
+    pub steps: Vec<(f64, f64)>,
+    /// Whether the query matched traversed backwards (steps are then parameters on the reversed query), see [`match_many_bidirectional`].
+    pub reversed: bool,
+}
+impl CandidateMatch {
+
+    const NONE: CandidateMatch = CandidateMatch { eps: f64::INFINITY, steps: vec![], reversed: false };
+
+    pub fn is_match(&self) -> bool {
+        !self.steps.is_empty()
+    }
+
+    /// Parameter interval on the candidate covered by the matching (if any).
+    pub fn interval(&self) -> Option<(f64, f64)> {
+        Some((self.steps.first()?.1, self.steps.last()?.1))
+    }
+}
+
+/// Bounding box of a curve.
+fn bounding_box(c: &Curve) -> Aabb {
+    c.windows(2).map(|w| Aabb::of_segment(w[0], w[1])).reduce(|a, b| a.union(&b)).unwrap()
+}
+
+/// Partially match the query against a subcurve of every candidate, in parallel (if enabled).
+///
+/// Results are in the order of the candidates. The query is validated and copied only once,
+/// candidates whose bounding box lies beyond eps of either query endpoint are rejected without computing their diagram.
+pub fn match_many(query: &Curve, candidates: &[Curve], eps: f64) -> Result<Vec<CandidateMatch>, PcmError> {
+    validate_curve(query)?;
+    validate_eps(eps)?;
+    parallel::map(candidates.iter().enumerate().collect(), |(k, qs)| {
+        validate_curve(qs).map_err(|e| PcmError::Candidate { index: k, source: Box::new(e) })?;
+        Ok(match_candidate(query, qs, eps))
+    }).into_iter().collect()
+}
+
+/// Like [`match_many`], but also matching the query traversed backwards (e.g. traces running opposite to the reference geometry of a two-way road).
+///
+/// Per candidate the orientation with the smallest largest distance is reported (forwards on ties).
+pub fn match_many_bidirectional(query: &Curve, candidates: &[Curve], eps: f64) -> Result<Vec<CandidateMatch>, PcmError> {
+    validate_curve(query)?;
+    validate_eps(eps)?;
+    let reversed = query.reversed();
+    parallel::map(candidates.iter().enumerate().collect(), |(k, qs)| {
+        validate_curve(qs).map_err(|e| PcmError::Candidate { index: k, source: Box::new(e) })?;
+        let forwards = match_candidate(query, qs, eps);
+        let backwards = CandidateMatch { reversed: true, ..match_candidate(&reversed, qs, eps) };
+        Ok(if backwards.eps < forwards.eps { backwards } else { forwards })
+    }).into_iter().collect()
+}
+
+/// Partially match the query against a subcurve of a single (validated) candidate.
+fn match_candidate(query: &Curve, qs: &Curve, eps: f64) -> CandidateMatch {
+    let (first, last) = (query[0], query[query.len() - 1]);
+    let bounds = bounding_box(qs);
+    if bounds.distance(first) > eps || bounds.distance(last) > eps {
+        return CandidateMatch::NONE;
+    }
+    let Some(steps) = FSD::from_slices(query, qs, eps).to_rsd().pcm_steps() else { return CandidateMatch::NONE };
+    // Within a cell the distance is convex along a step, so it peaks at the steps themselves.
+    let eps = steps.iter().map(|&(i, j)| point_on(query, i).distance(point_on(qs, j))).fold(0., f64::max);
+    CandidateMatch { eps, steps, reversed: false }
+}
@@ -0,0 +1,226 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{curve::{distance_to_segment, Curve}, vector::Vector};
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+
+
+/// Axis-aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Vector,
+    pub max: Vector,
+}
+impl Aabb {
+
+    /// Bounding box of the line segment (p0, p1).
+    pub fn of_segment(p0: Vector, p1: Vector) -> Self {
+        Aabb { min: p0.min(&p1), max: p0.max(&p1) }
+    }
+
+    /// Smallest box containing both boxes.
+    pub fn union(&self, other: &Self) -> Self {
+        Aabb { min: self.min.min(&other.min), max: self.max.max(&other.max) }
+    }
+
+    pub fn center(&self) -> Vector {
+        0.5 * (self.min + self.max)
+    }
+
+    /// Check both boxes overlap (touching counts as overlap).
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.x <= other.max.x && other.min.x <= self.max.x &&
+        self.min.y <= other.max.y && other.min.y <= self.max.y
+    }
+
+    /// Smallest distance of point p to the box (zero inside).
+    pub fn distance(&self, p: Vector) -> f64 {
+        let dx = (self.min.x - p.x).max(p.x - self.max.x).max(0.);
+        let dy = (self.min.y - p.y).max(p.y - self.max.y).max(0.);
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    /// Parameter range [t0, t1] (clipped to [tmin, tmax]) along the line origin + t * dir within the box (slab test).
+    fn clip(&self, origin: Vector, dir: Vector, tmin: f64, tmax: f64) -> Option<(f64, f64)> {
+        let (mut t0, mut t1) = (tmin, tmax);
+        for (o, d, lo, hi) in [(origin.x, dir.x, self.min.x, self.max.x), (origin.y, dir.y, self.min.y, self.max.y)] {
+            if d == 0. {
+                if o < lo || o > hi { return None; }
+            } else {
+                let (a, b) = ((lo - o) / d, (hi - o) / d);
+                t0 = t0.max(a.min(b));
+                t1 = t1.min(a.max(b));
+            }
+        }
+        if t0 <= t1 { Some((t0, t1)) } else { None }
+    }
+}
+
+/// First parameter t >= 0 at which the ray origin + t * dir hits the line segment (q0, q1).
+fn ray_segment(origin: Vector, dir: Vector, q0: Vector, q1: Vector) -> Option<f64> {
+    let cross = |a: Vector, b: Vector| a.x * b.y - a.y * b.x;
+    let e = q1 - q0;
+    let w = q0 - origin;
+    let denom = cross(dir, e);
+    if denom != 0. {
+        let t = cross(w, e) / denom;
+        let s = cross(w, dir) / denom;
+        return if t >= 0. && (0. ..=1.).contains(&s) { Some(t) } else { None };
+    }
+    // Parallel: only a hit if collinear, then at the nearest segment point ahead (or the origin itself if on the segment).
+    if cross(w, dir) != 0. || dir.dot(dir) == 0. { return None; }
+    let (t0, t1) = (w.dot(dir) / dir.dot(dir), (q1 - origin).dot(dir) / dir.dot(dir));
+    let (lo, hi) = (t0.min(t1), t0.max(t1));
+    if hi < 0. { None } else { Some(lo.max(0.)) }
+}
+
+/// Node of the hierarchy, children are indices into the node list.
+#[derive(Debug, Clone)]
+enum Node {
+    Leaf { bbox: Aabb, segment: usize },
+    Inner { bbox: Aabb, left: usize, right: usize },
+}
+impl Node {
+    fn bbox(&self) -> &Aabb {
+        match self {
+            Node::Leaf { bbox, .. } | Node::Inner { bbox, .. } => bbox
+        }
+    }
+}
+
+/// Bounding volume hierarchy over the segments of a curve (segment i runs from point i to point i+1).
+#[derive(Debug, Clone)]
+pub struct SegmentBvh {
+    segments: Vec<(Vector, Vector)>,
+    nodes: Vec<Node>,
+    root: Option<usize>,
+}
+impl SegmentBvh {
+
+    /// Build hierarchy by recursively splitting segments at the median along the longest axis of their centers.
+    pub fn new(c: &Curve) -> Self {
+        let segments: Vec<(Vector, Vector)> = c.windows(2).map(|w| (w[0], w[1])).collect();
+        let mut nodes = vec![];
+        let mut indices: Vec<usize> = (0..segments.len()).collect();
+        let root = if indices.is_empty() { None } else { Some(SegmentBvh::build(&segments, &mut nodes, &mut indices)) };
+        SegmentBvh { segments, nodes, root }
+    }
+
+    /// Build subtree over the segments at indices, returns index of its root node.
+    fn build(segments: &[(Vector, Vector)], nodes: &mut Vec<Node>, indices: &mut [usize]) -> usize {
+        let bbox_of = |i: usize| Aabb::of_segment(segments[i].0, segments[i].1);
+        if indices.len() == 1 {
+            nodes.push(Node::Leaf { bbox: bbox_of(indices[0]), segment: indices[0] });
+            return nodes.len() - 1;
+        }
+        let bbox = indices.iter().map(|&i| bbox_of(i)).reduce(|a, b| a.union(&b)).unwrap();
+        let centers = indices.iter().map(|&i| bbox_of(i).center()).fold(Aabb { min: bbox.max, max: bbox.min }, |acc, p| acc.union(&Aabb { min: p, max: p }));
+        let split_x = centers.max.x - centers.min.x >= centers.max.y - centers.min.y;
+        let key = |i: usize| { let p = bbox_of(i).center(); if split_x { p.x } else { p.y } };
+        let mid = indices.len() / 2;
+        indices.select_nth_unstable_by(mid, |&a, &b| key(a).total_cmp(&key(b)));
+        let (lower, upper) = indices.split_at_mut(mid);
+        let left = SegmentBvh::build(segments, nodes, lower);
+        let right = SegmentBvh::build(segments, nodes, upper);
+        nodes.push(Node::Inner { bbox, left, right });
+        nodes.len() - 1
+    }
+
+    /// Number of segments.
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Visit every leaf whose path of boxes passes the filter.
+    fn visit(&self, filter: impl Fn(&Aabb) -> bool, mut leaf: impl FnMut(usize)) {
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(k) = stack.pop() {
+            if !filter(self.nodes[k].bbox()) { continue; }
+            match self.nodes[k] {
+                Node::Leaf { segment, .. } => leaf(segment),
+                Node::Inner { left, right, .. } => { stack.push(left); stack.push(right); }
+            }
+        }
+    }
+
+    /// Segments (sorted by index) intersecting the box.
+    pub fn query_box(&self, b: &Aabb) -> Vec<usize> {
+        let mut result = vec![];
+        self.visit(|bbox| bbox.intersects(b), |i| {
+            let (p0, p1) = self.segments[i];
+            if b.clip(p0, p1 - p0, 0., 1.).is_some() { result.push(i); }
+        });
+        result.sort();
+        result
+    }
+
+    /// Segments (sorted by index) within distance radius of center.
+    pub fn query_disk(&self, center: Vector, radius: f64) -> Vec<usize> {
+        let mut result = vec![];
+        self.visit(|bbox| bbox.distance(center) <= radius, |i| {
+            let (p0, p1) = self.segments[i];
+            if distance_to_segment(center, p0, p1) <= radius { result.push(i); }
+        });
+        result.sort();
+        result
+    }
+
+    /// Segments hit by the ray origin + t * dir (t >= 0), as (segment, t) sorted by t.
+    pub fn query_ray(&self, origin: Vector, dir: Vector) -> Vec<(usize, f64)> {
+        let mut result = vec![];
+        self.visit(|bbox| bbox.clip(origin, dir, 0., f64::INFINITY).is_some(), |i| {
+            let (p0, p1) = self.segments[i];
+            if let Some(t) = ray_segment(origin, dir, p0, p1) { result.push((i, t)); }
+        });
+        result.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        result
+    }
+
+    /// Check whether any segment lies within distance eps of point p (stopping at the first one found).
+    pub fn any_within(&self, p: Vector, eps: f64) -> bool {
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(k) = stack.pop() {
+            if self.nodes[k].bbox().distance(p) > eps { continue; }
+            match self.nodes[k] {
+                Node::Leaf { segment, .. } => {
+                    let (p0, p1) = self.segments[segment];
+                    if distance_to_segment(p, p0, p1) <= eps { return true; }
+                },
+                Node::Inner { left, right, .. } => { stack.push(left); stack.push(right); }
+            }
+        }
+        false
+    }
+
+    /// Early-reject test for partially matching ps against a subcurve of the indexed curve at threshold eps.
+    ///
+    /// Every vertex of ps has to be matched, so if some vertex lies beyond eps of every segment no match exists (the converse does not hold).
+    pub fn may_match(&self, ps: &Curve, eps: f64) -> bool {
+        ps.iter().all(|&p| self.any_within(p, eps))
+    }
+
+    /// Smallest distance of point p to any segment (branch and bound on box distances), infinite without segments.
+    pub fn nearest_distance(&self, p: Vector) -> f64 {
+        let mut best = f64::INFINITY;
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(k) = stack.pop() {
+            if self.nodes[k].bbox().distance(p) >= best { continue; }
+            match self.nodes[k] {
+                Node::Leaf { segment, .. } => {
+                    let (p0, p1) = self.segments[segment];
+                    best = best.min(distance_to_segment(p, p0, p1));
+                },
+                Node::Inner { left, right, .. } => {
+                    // Descend into the nearest child first (it is popped first).
+                    let (dl, dr) = (self.nodes[left].bbox().distance(p), self.nodes[right].bbox().distance(p));
+                    if dl < dr { stack.push(right); stack.push(left); } else { stack.push(left); stack.push(right); }
+                }
+            }
+        }
+        best
+    }
+}
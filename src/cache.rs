@@ -0,0 +1,110 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use crate::{curve::{content_hash, Curve}, error::PcmError, partial_curve::try_partial_curve};
+
+
+/// Cache key: content hashes of both curves and the bit pattern of eps.
+type Key = (u64, u64, u64);
+
+/// Cached result, with the curves themselves to rule out hash collisions and the time of last use.
+struct Entry {
+    ps: Curve,
+    qs: Curve,
+    result: Option<(f64, f64)>,
+    last_used: u64,
+}
+
+/// Least-recently-used bookkeeping, guarded as a whole by the mutex of the cache.
+#[derive(Default)]
+struct Lru {
+    entries: HashMap<Key, Entry>,
+    /// Keys ordered by time of last use.
+    order: BTreeMap<u64, Key>,
+    /// Logical clock, incremented on every use.
+    clock: u64,
+    hits: usize,
+    misses: usize,
+}
+impl Lru {
+
+    /// Mark the entry as most recently used.
+    fn touch(&mut self, key: Key) {
+        self.clock += 1;
+        let entry = self.entries.get_mut(&key).unwrap();
+        self.order.remove(&entry.last_used);
+        entry.last_used = self.clock;
+        self.order.insert(self.clock, key);
+    }
+}
+
+/// Thread-safe cache of partial curve matching results, evicting the least recently used entry once full.
+///
+/// Services which repeatedly match the same probe against overlapping candidate sets thereby skip identical work.
+pub struct MatchCache {
+    capacity: usize,
+    lru: Mutex<Lru>,
+}
+impl MatchCache {
+
+    /// Construct cache holding at most capacity results (at least one).
+    pub fn new(capacity: usize) -> Self {
+        MatchCache { capacity: capacity.max(1), lru: Mutex::new(Lru::default()) }
+    }
+
+    /// Like [`try_partial_curve`], but reusing the result of a previous call on identical input.
+    ///
+    /// The computation itself is done without holding the lock, so concurrent misses on the same input may both compute it.
+    /// Malformed input is not cached.
+    pub fn partial_curve(&self, ps: &Curve, qs: &Curve, eps: f64) -> Result<Option<(f64, f64)>, PcmError> {
+        let key = (content_hash(ps), content_hash(qs), eps.to_bits());
+        {
+            let mut lru = self.lru.lock().unwrap();
+            if let Some(entry) = lru.entries.get(&key) && entry.ps == *ps && entry.qs == *qs {
+                let result = entry.result;
+                lru.hits += 1;
+                lru.touch(key);
+                return Ok(result);
+            }
+            lru.misses += 1;
+        }
+
+        let result = try_partial_curve(ps.clone(), qs.clone(), eps)?;
+
+        let mut lru = self.lru.lock().unwrap();
+        if let Some(entry) = lru.entries.remove(&key) {
+            lru.order.remove(&entry.last_used);
+        }
+        while lru.entries.len() >= self.capacity {
+            let (_, oldest) = lru.order.pop_first().unwrap();
+            lru.entries.remove(&oldest);
+        }
+        lru.clock += 1;
+        let last_used = lru.clock;
+        lru.entries.insert(key, Entry { ps: ps.clone(), qs: qs.clone(), result, last_used });
+        lru.order.insert(last_used, key);
+        Ok(result)
+    }
+
+    /// Number of cached results.
+    pub fn len(&self) -> usize {
+        self.lru.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of calls answered from the cache and number of calls which had to compute, as (hits, misses).
+    pub fn stats(&self) -> (usize, usize) {
+        let lru = self.lru.lock().unwrap();
+        (lru.hits, lru.misses)
+    }
+
+    /// Drop all cached results.
+    pub fn clear(&self) {
+        let mut lru = self.lru.lock().unwrap();
+        lru.entries.clear();
+        lru.order.clear();
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(feature = "python")]
+use pyo3::pyfunction;
+
+use crate::{curve::Curve, error::PcmError, parallel, partial_curve::min_partial_eps};
+
+
+/// Threshold with its precision and recall.
+#[cfg(feature = "python")]
+type Sweep = (f64, f64, f64);
+
+/// Classification quality of a single threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalibrationPoint {
+    pub eps: f64,
+    /// Fraction of pairs matching at eps which are labeled as matching (one if no pair matches).
+    pub precision: f64,
+    /// Fraction of pairs labeled as matching which match at eps (one if no pair is labeled as matching).
+    pub recall: f64,
+}
+impl CalibrationPoint {
+
+    /// Harmonic mean of precision and recall.
+    pub fn f1(&self) -> f64 {
+        if self.precision + self.recall == 0. { 0. } else { 2. * self.precision * self.recall / (self.precision + self.recall) }
+    }
+}
+
+/// Precision and recall over a sweep of thresholds, see [`calibrate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibration {
+    /// Thresholds in increasing order.
+    pub points: Vec<CalibrationPoint>,
+    /// Index of the recommended operating point into `points` (if any).
+    pub recommended: Option<usize>,
+}
+impl Calibration {
+
+    /// Recommended operating point (if any).
+    pub fn recommendation(&self) -> Option<CalibrationPoint> {
+        self.recommended.map(|k| self.points[k])
+    }
+}
+
+/// Sweep the threshold over a labeled set of (ps, qs, is_match) pairs, computing precision and recall of the partial matching decision at every threshold.
+///
+/// The minimal matching threshold of every pair is computed (up to tolerance, see [`min_partial_eps`]) in parallel (if enabled),
+/// since a pair matches at every threshold beyond it, each of these minimal thresholds is a sweep point.
+/// Recommends the threshold with the highest F1 score (the smallest one on ties).
+pub fn calibrate(pairs: &[(Curve, Curve, bool)], tolerance: f64) -> Result<Calibration, PcmError> {
+    let min_eps = parallel::map(pairs.iter().collect(), |(ps, qs, _)| min_partial_eps(ps, qs, tolerance)).into_iter().collect::<Result<Vec<f64>, PcmError>>()?;
+    let mut thresholds = min_eps.clone();
+    thresholds.sort_by(f64::total_cmp);
+    thresholds.dedup();
+
+    let positives = pairs.iter().filter(|(_, _, is_match)| *is_match).count();
+    let points: Vec<CalibrationPoint> = thresholds.into_iter().map(|eps| {
+        let predicted = min_eps.iter().filter(|&&e| e <= eps).count();
+        let correct = min_eps.iter().zip(pairs).filter(|&(&e, (_, _, is_match))| e <= eps && *is_match).count();
+        let precision = if predicted == 0 { 1. } else { correct as f64 / predicted as f64 };
+        let recall = if positives == 0 { 1. } else { correct as f64 / positives as f64 };
+        CalibrationPoint { eps, precision, recall }
+    }).collect();
+
+    let mut recommended: Option<usize> = None;
+    for (k, point) in points.iter().enumerate() {
+        if recommended.is_none_or(|r| point.f1() > points[r].f1()) {
+            recommended = Some(k);
+        }
+    }
+    Ok(Calibration { points, recommended })
+}
+
+/// Sweep the threshold over labeled (ps, qs, is_match) pairs, returns the recommended threshold (if any) and (eps, precision, recall) per threshold.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn calibrate_eps(pairs: Vec<(Curve, Curve, bool)>, tolerance: f64) -> Result<(Option<f64>, Vec<Sweep>), PcmError> {
+    let calibration = calibrate(&pairs, tolerance)?;
+    let points = calibration.points.iter().map(|p| (p.eps, p.precision, p.recall)).collect();
+    Ok((calibration.recommendation().map(|p| p.eps), points))
+}
@@ -0,0 +1,104 @@
+use std::collections::BTreeMap as Map;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+
+/// Checkpoint of completed job identifiers for long-running batch jobs.
+///
+/// Identifiers are appended to a text file (one per line, optionally followed by a tab and the job's record) every `interval` completed jobs,
+/// so an interrupted batch can be resumed by skipping the jobs already done (and reusing their records).
+/// Only mark jobs done that should not run again on resume (e.g. not those that failed), identifiers should not contain tabs or newlines.
+pub struct Checkpoint {
+    /// File the completed job identifiers are appended to.
+    path: PathBuf,
+    /// Completed job identifiers with their records (both written and pending).
+    done: Map<String, String>,
+    /// Completed job identifiers with their records not yet written to disk.
+    pending: Vec<(String, String)>,
+    /// Number of completed jobs to buffer before writing.
+    interval: usize,
+}
+impl Checkpoint {
+
+    /// Open checkpoint file, continuing from its completed jobs if resume is set and starting afresh (truncating the file) otherwise.
+    pub fn open<P: AsRef<Path>>(path: P, resume: bool) -> std::io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut done = Map::new();
+        if resume && path.exists() {
+            for line in BufReader::new(File::open(&path)?).lines() {
+                let line = line?;
+                if !line.is_empty() {
+                    let (id, record) = line.split_once('\t').unwrap_or((&line, ""));
+                    done.insert(id.to_string(), record.to_string());
+                }
+            }
+        } else {
+            File::create(&path)?;
+        }
+        Ok(Checkpoint { path, done, pending: vec![], interval: 100 })
+    }
+
+    /// Set the number of completed jobs to buffer before writing the checkpoint.
+    pub fn with_interval(mut self, interval: usize) -> Self {
+        self.interval = interval.max(1);
+        self
+    }
+
+    /// Check whether a job has already been completed.
+    pub fn is_done(&self, id: &str) -> bool {
+        self.done.contains_key(id)
+    }
+
+    /// Record stored with a completed job (empty if it was marked done without one).
+    pub fn record(&self, id: &str) -> Option<&str> {
+        self.done.get(id).map(String::as_str)
+    }
+
+    /// Number of completed jobs.
+    pub fn len(&self) -> usize {
+        self.done.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.done.is_empty()
+    }
+
+    /// Mark a job as completed, writes the checkpoint once enough jobs are pending.
+    pub fn mark_done(&mut self, id: &str) -> std::io::Result<()> {
+        self.mark_done_with(id, "")
+    }
+
+    /// Mark a job as completed with a record to reuse on resume (a single line, e.g. its result), see [`Checkpoint::mark_done`].
+    pub fn mark_done_with(&mut self, id: &str, record: &str) -> std::io::Result<()> {
+        if id.contains(['\t', '\n']) || record.contains('\n') {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("Job {id:?} cannot be checkpointed on a single line.")));
+        }
+        if self.done.insert(id.to_string(), record.to_string()).is_none() {
+            self.pending.push((id.to_string(), record.to_string()));
+        }
+        if self.pending.len() >= self.interval {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Append pending completed jobs to the checkpoint file.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        if self.pending.is_empty() { return Ok(()); }
+        let mut file = OpenOptions::new().append(true).create(true).open(&self.path)?;
+        for (id, record) in self.pending.drain(..) {
+            if record.is_empty() {
+                writeln!(file, "{id}")?;
+            } else {
+                writeln!(file, "{id}\t{record}")?;
+            }
+        }
+        file.sync_data()
+    }
+}
+impl Drop for Checkpoint {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
@@ -0,0 +1,134 @@
+use core::cell::RefCell;
+
+use crate::{curve::{validate_curve, validate_eps, Curve}, error::PcmError, fsd::{free_boundary, reachable_boundary, Boundaries, MatchMode}, lineboundary::OptLineBoundary, LineBoundary};
+
+
+/// Occurrence of ps along qs found by [`chunked_matchings`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkedOccurrence {
+    /// Maximal parameter interval on qs matchings of ps may end in (like [`crate::Occurrence::exit`]).
+    pub exit: (f64, f64),
+    /// Steps of a matching ending at the start of the exit interval, as (parameter on ps, parameter on qs).
+    pub steps: Vec<(f64, f64)>,
+}
+impl ChunkedOccurrence {
+
+    /// Parameter interval on qs the matching covers, None if the steps are empty.
+    pub fn covered_interval(&self) -> Option<(f64, f64)> {
+        Some((self.steps.first()?.1, self.steps.last()?.1))
+    }
+}
+
+/// Enumerate every occurrence of ps along a (very long) qs below threshold epsilon, like [`crate::all_matchings`] without storing the n×m diagram.
+///
+/// Sweeps the diagram row by row along qs keeping only the reachable intervals of the last row, plus a checkpoint of the row every chunk of about √m rows.
+/// Matchings are walked back through the chunks they pass, each replayed from its checkpoint (at most two are kept at a time).
+/// Memory is thereby O(n √m) rather than O(n m), for about twice the time of the sweep alone.
+pub fn chunked_matchings(ps: &Curve, qs: &Curve, eps: f64) -> Result<Vec<ChunkedOccurrence>, PcmError> {
+    validate_curve(ps)?;
+    validate_curve(qs)?;
+    validate_eps(eps)?;
+    let m = qs.len();
+    let chunk = (m as f64).sqrt().ceil() as usize;
+    let (n, mut below) = (ps.len(), Row::empty(ps.len()));
+    let mut checkpoints = vec![];
+    // Lowest row, offset and end parameter of every maximal exit interval (intervals connecting at a cornerpoint are merged).
+    let mut exits: Vec<(usize, f64, f64)> = vec![];
+    for j in 0..m {
+        if j % chunk == 0 {
+            checkpoints.push(below.clone());
+        }
+        below = Row::sweep(ps, qs, eps, j, &below);
+        if let Some(&Some(LineBoundary { a, b })) = below.verticals.last() {
+            match exits.last_mut() {
+                Some((_, _, end)) if *end == j as f64 && a == 0. => *end = j as f64 + b,
+                _ => exits.push((j, a, j as f64 + b)),
+            }
+        }
+    }
+
+    let rows = Rows { ps, qs, eps, chunk, checkpoints, cache: RefCell::new(vec![]) };
+    Ok(exits.into_iter().map(|(y, a, end)| ChunkedOccurrence { exit: (y as f64 + a, end), steps: rows.steps_to((0, n-1, y, a)) }).collect())
+}
+
+/// Reachable intervals of a row of the diagram: `horizontals` at a point of qs (along the segments of ps),
+/// `verticals` along the next segment of qs (at the points of ps, none on the last row).
+#[derive(Debug, Clone)]
+struct Row {
+    horizontals: Vec<OptLineBoundary>,
+    verticals: Vec<OptLineBoundary>,
+}
+impl Row {
+
+    /// Row below the diagram, nothing is reachable.
+    fn empty(n: usize) -> Row {
+        Row { horizontals: vec![None; n-1], verticals: vec![None; n] }
+    }
+
+    /// Row j, propagated from the row below it with the rules of [`crate::FSD::to_rsd`].
+    fn sweep(ps: &Curve, qs: &Curve, eps: f64, j: usize, below: &Row) -> Row {
+        let (n, m, q) = (ps.len(), qs.len(), qs[j]);
+        let mut row = Row { horizontals: Vec::with_capacity(n-1), verticals: Vec::with_capacity(n) };
+        for i in 0..n {
+            if i < n - 1 {
+                let free = free_boundary(q, ps[i], ps[i+1], eps);
+                // First horizontal boundary of every row starting at a free left cornerpoint.
+                let seed = if i == 0 && let Some(LineBoundary { a: 0., b: _ }) = free { free } else { None };
+                let prev = if i > 0 { row.horizontals[i-1] } else { None };
+                row.horizontals.push(reachable_boundary(seed, free, below.verticals[i], below.horizontals[i], prev));
+            }
+            if j < m - 1 {
+                let free = free_boundary(ps[i], q, qs[j+1], eps);
+                // Left border.
+                let seed = if i == 0 { free } else { None };
+                let (orth, para) = if i > 0 { (row.horizontals[i-1], row.verticals[i-1]) } else { (None, None) };
+                row.verticals.push(reachable_boundary(seed, free, orth, para, below.verticals[i]));
+            }
+        }
+        row
+    }
+}
+
+/// Reachable space diagram replaying chunks of rows from their checkpoints on access, for walking back matchings.
+#[derive(Debug)]
+struct Rows<'a> {
+    ps: &'a Curve,
+    qs: &'a Curve,
+    eps: f64,
+    /// Number of rows per chunk.
+    chunk: usize,
+    /// Row below the first row of every chunk.
+    checkpoints: Vec<Row>,
+    /// Most recently replayed chunks (walking back alternates between a row and the one below it).
+    cache: RefCell<Vec<(usize, Vec<Row>)>>,
+}
+impl Boundaries for Rows<'_> {
+
+    fn size(&self) -> (usize, usize) {
+        (self.ps.len(), self.qs.len())
+    }
+
+    fn mode(&self) -> MatchMode {
+        MatchMode::SubcurveOfQ
+    }
+
+    fn boundary(&self, (axis, x, y): (usize, usize, usize)) -> OptLineBoundary {
+        let (j, i) = if axis == 0 { (y, x) } else { (x, y) };
+        let k = j / self.chunk;
+        let mut cache = self.cache.borrow_mut();
+        if !cache.iter().any(|(k_, _)| *k_ == k) {
+            let mut rows: Vec<Row> = vec![];
+            for j in k * self.chunk..((k + 1) * self.chunk).min(self.qs.len()) {
+                let row = Row::sweep(self.ps, self.qs, self.eps, j, rows.last().unwrap_or(&self.checkpoints[k]));
+                rows.push(row);
+            }
+            if cache.len() == 2 {
+                cache.remove(0);
+            }
+            cache.push((k, rows));
+        }
+        let (_, rows) = cache.iter().find(|(k_, _)| *k_ == k).unwrap();
+        let row = &rows[j - k * self.chunk];
+        if axis == 0 { row.verticals[i] } else { row.horizontals[i] }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{curve::{validate_curve, validate_eps, Curve}, error::PcmError, grid::{Grid, PathRules, Sample}, vector::Vector};
+
+/// Single move of a matching between two positions (parameter on ps, parameter on qs), with the points matched at both ends.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Move {
+    pub from: (f64, f64),
+    pub to: (f64, f64),
+    /// Points on ps and qs at the start of the move.
+    pub start: (Vector, Vector),
+    /// Points on ps and qs at the end of the move.
+    pub end: (Vector, Vector),
+}
+impl Move {
+
+    /// Length walked along ps.
+    pub fn length_p(&self) -> f64 {
+        self.start.0.distance(self.end.0)
+    }
+
+    /// Length walked along qs.
+    pub fn length_q(&self) -> f64 {
+        self.start.1.distance(self.end.1)
+    }
+
+    /// Average leash length at both ends.
+    pub fn leash(&self) -> f64 {
+        0.5 * (self.start.0.distance(self.start.1) + self.end.0.distance(self.end.1))
+    }
+}
+
+/// Cost model of a matching path, the path extracted by [`min_cost_steps`] minimizes the summed cost of its moves.
+pub trait StepCost {
+    fn cost(&self, m: &Move) -> f64;
+}
+
+/// Closures act as cost model.
+impl<F: Fn(&Move) -> f64> StepCost for F {
+    fn cost(&self, m: &Move) -> f64 {
+        self(m)
+    }
+}
+
+/// Every move costs the same (fewest moves, thus preferring diagonal moves).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Uniform;
+impl StepCost for Uniform {
+    fn cost(&self, _: &Move) -> f64 {
+        1.
+    }
+}
+
+/// Leash length integrated over the length walked along both curves (keeping the curves close along the way).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DistanceWeighted;
+impl StepCost for DistanceWeighted {
+    fn cost(&self, m: &Move) -> f64 {
+        m.leash() * (m.length_p() + m.length_q())
+    }
+}
+
+/// Length walked along both curves, with the difference between both lengths penalized by weight (keeping both curves at the same speed).
+#[derive(Debug, Clone, Copy)]
+pub struct SlopePenalty {
+    pub weight: f64,
+}
+impl StepCost for SlopePenalty {
+    fn cost(&self, m: &Move) -> f64 {
+        let (lp, lq) = (m.length_p(), m.length_q());
+        lp + lq + self.weight * (lp - lq).abs()
+    }
+}
+
+/// Compute steps of a partial curve match of ps against a subcurve of qs with minimal total cost under the cost model.
+///
+/// Only walks through free space (leash length at most eps), starting anywhere on the left boundary and ending anywhere on the right boundary.
+/// The parameter space is discretized with `resolution` samples per FSD cell (monotone moves right, up, or diagonal),
+/// and only the leash at sampled positions is checked, not the continuous free space between them,
+/// so the result is an approximation of the continuous matching.
+pub fn min_cost_steps(ps: &Curve, qs: &Curve, eps: f64, cost: &impl StepCost, resolution: usize) -> Result<Option<Vec<(f64, f64)>>, PcmError> {
+    validate_curve(ps)?;
+    validate_curve(qs)?;
+    validate_eps(eps)?;
+    let grid = Grid::new(ps, qs, resolution)?;
+    let rules = CostRules { free: grid.free(eps), grid: &grid, cost };
+    Ok(grid.best_path(&rules).map(|(_, path)| path.into_iter().map(|s| grid.position(s)).collect()))
+}
+
+/// Paths through free samples summing the cost of their moves.
+struct CostRules<'a, C> {
+    grid: &'a Grid,
+    free: Vec<Vec<bool>>,
+    cost: &'a C,
+}
+impl<C: StepCost> PathRules for CostRules<'_, C> {
+    type Cost = f64;
+
+    fn start(&self, (x, y): Sample) -> Option<f64> {
+        // Partial match: We may start anywhere on the left boundary.
+        (x == 0 && self.free[x][y]).then_some(0.)
+    }
+
+    fn step(&self, total: &f64, (px, py): Sample, (x, y): Sample) -> Option<f64> {
+        if x == 0 || !self.free[x][y] { return None; }
+        let m = Move { from: self.grid.position((px, py)), to: self.grid.position((x, y)), start: (self.grid.p[px], self.grid.q[py]), end: (self.grid.p[x], self.grid.q[y]) };
+        Some(total + self.cost.cost(&m))
+    }
+
+    fn better(&self, c1: &f64, c2: &f64) -> bool {
+        c1 < c2
+    }
+
+    fn end(&self, (x, _): Sample) -> bool {
+        x == self.grid.size().0 - 1
+    }
+}
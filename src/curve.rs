@@ -1,4 +1,202 @@
-use crate::vector::Vector;
+use alloc::{format, vec, vec::Vec};
 
-/// A polygonal chain.
-pub type Curve = Vec<Vector>;
+use crate::{error::PcmError, vector::{Point, Vector}};
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+
+/// A polygonal chain (of planar points by default).
+pub type Curve<P = Vector> = Vec<P>;
+
+/// Curve utilities as methods (a curve is a plain vector of points, hence an extension trait).
+pub trait CurveExt {
+    /// Insert interpolated points so no segment exceeds max_segment_length (segments are split evenly).
+    ///
+    /// Non-positive or non-finite lengths leave the curve unchanged.
+    fn densify(&self, max_segment_length: f64) -> Curve;
+
+    /// Total arc length.
+    fn length(&self) -> f64;
+
+    /// Point at parameter t, as returned in steps (integer part is the segment index, fractional part the offset on that segment), None if the curve is empty.
+    fn point_at(&self, t: f64) -> Option<Vector>;
+
+    /// Points at every multiple of spacing in arc length from the start, plus the last point.
+    ///
+    /// Non-positive or non-finite spacings leave the curve unchanged.
+    fn resample_by_arclength(&self, spacing: f64) -> Curve;
+
+    /// Same curve traversed backwards.
+    fn reversed(&self) -> Curve;
+
+    /// Check the curve is well-formed: at least two points, all coordinates finite (see [`validate_curve`]) and no consecutive duplicate points.
+    ///
+    /// Stricter than [`validate_curve`], which accepts zero-length segments (they are handled by the diagram, but carry no information).
+    fn validate(&self) -> Result<(), PcmError>;
+
+    /// Same curve without non-finite (or too large) points and consecutive duplicate points, which [`CurveExt::validate`] accepts.
+    ///
+    /// Fails if fewer than two points remain.
+    fn normalize(&self) -> Result<Curve, PcmError>;
+}
+impl CurveExt for Curve {
+    fn densify(&self, max_segment_length: f64) -> Curve {
+        if !(max_segment_length.is_finite() && max_segment_length > 0.) || self.is_empty() {
+            return self.clone();
+        }
+        let mut result = vec![self[0]];
+        for w in self.windows(2) {
+            let k = (w[0].distance(w[1]) / max_segment_length).ceil().max(1.) as usize;
+            for i in 1..k {
+                let t = i as f64 / k as f64;
+                result.push((1. - t) * w[0] + t * w[1]);
+            }
+            result.push(w[1]);
+        }
+        result
+    }
+
+    fn length(&self) -> f64 {
+        self.windows(2).map(|w| w[0].distance(w[1])).sum()
+    }
+
+    fn point_at(&self, t: f64) -> Option<Vector> {
+        interpolate(self, t)
+    }
+
+    fn resample_by_arclength(&self, spacing: f64) -> Curve {
+        if !(spacing.is_finite() && spacing > 0.) || self.is_empty() {
+            return self.clone();
+        }
+        let mut result = vec![self[0]];
+        // Arc length at the start of the current segment, and index of the next sample (multiplied rather than accumulated against drift).
+        let (mut start, mut k) = (0., 1);
+        for w in self.windows(2) {
+            let length = w[0].distance(w[1]);
+            while (k as f64) * spacing < start + length {
+                let t = ((k as f64) * spacing - start) / length;
+                result.push((1. - t) * w[0] + t * w[1]);
+                k += 1;
+            }
+            start += length;
+        }
+        if self.len() > 1 {
+            result.push(self[self.len() - 1]);
+        }
+        result
+    }
+
+    fn reversed(&self) -> Curve {
+        self.iter().rev().copied().collect()
+    }
+
+    fn validate(&self) -> Result<(), PcmError> {
+        validate_curve(self)?;
+        if let Some(k) = self.windows(2).position(|w| w[0] == w[1]) {
+            return Err(PcmError::DuplicatePoint { index: k + 1 });
+        }
+        Ok(())
+    }
+
+    fn normalize(&self) -> Result<Curve, PcmError> {
+        let mut result: Curve = vec![];
+        for &p in self.iter().filter(|p| p.is_bounded(MAX_COORDINATE)) {
+            if result.last() != Some(&p) {
+                result.push(p);
+            }
+        }
+        validate_curve(&result)?;
+        Ok(result)
+    }
+}
+
+/// Largest coordinate magnitude for which squared distances do not overflow.
+pub const MAX_COORDINATE: f64 = 1e150;
+
+/// Check a curve is usable for matching: at least two points and all coordinates finite (and below [`MAX_COORDINATE`]).
+pub fn validate_curve<P: Point>(c: &Curve<P>) -> Result<(), PcmError> {
+    if c.len() < 2 {
+        return Err(PcmError::EmptyCurve { len: c.len() });
+    }
+    if let Some(i) = c.iter().position(|p| !p.is_bounded(MAX_COORDINATE)) {
+        return Err(PcmError::InvalidPoint { index: i, point: format!("{:?}", c[i]) });
+    }
+    Ok(())
+}
+
+/// Check a distance threshold is usable for matching: positive and finite.
+pub fn validate_eps(eps: f64) -> Result<(), PcmError> {
+    if !(eps.is_finite() && eps > 0.) {
+        return Err(PcmError::InvalidThreshold(eps));
+    }
+    Ok(())
+}
+
+/// Point on curve c at parameter t (integer part is the segment index, fractional part the offset on that segment), None if c is empty.
+pub fn interpolate<P: Point>(c: &Curve<P>, t: f64) -> Option<P> {
+    (!c.is_empty()).then(|| point_on(c, t))
+}
+
+/// Like [`interpolate`] on a curve known to be non-empty (e.g. validated).
+pub(crate) fn point_on<P: Point>(c: &Curve<P>, t: f64) -> P {
+    let i = (t.floor() as usize).min(c.len() - 1);
+    let off = t - i as f64;
+    if off == 0. || i == c.len() - 1 {
+        c[i]
+    } else {
+        c[i].scale(1. - off) + c[i + 1].scale(off)
+    }
+}
+
+/// Subcurve of c between parameters t0 and t1 (with interpolated endpoints), empty if c is.
+pub fn subcurve<P: Point>(c: &Curve<P>, t0: f64, t1: f64) -> Curve<P> {
+    if c.is_empty() {
+        return vec![];
+    }
+    let mut points = vec![point_on(c, t0)];
+    for (i, &p) in c.iter().enumerate() {
+        let t = i as f64;
+        if t > t0 && t < t1 {
+            points.push(p);
+        }
+    }
+    points.push(point_on(c, t1));
+    points
+}
+
+/// Arc length of curve c from its start up to parameter t (zero if c is empty).
+pub fn arc_length<P: Point>(c: &Curve<P>, t: f64) -> f64 {
+    if c.is_empty() {
+        return 0.;
+    }
+    let mut length = 0.;
+    let k = (t.floor() as usize).min(c.len() - 1);
+    for i in 0..k {
+        length += c[i].distance(c[i+1]);
+    }
+    length + c[k].distance(point_on(c, t))
+}
+
+/// Smallest distance of point p to any point on curve c.
+pub fn distance_to_curve(p: Vector, c: &Curve) -> f64 {
+    c.windows(2).map(|w| distance_to_segment(p, w[0], w[1])).fold(f64::INFINITY, f64::min)
+}
+
+/// Smallest distance of point p to any point on the line segment (q0, q1).
+pub fn distance_to_segment<P: Point>(p: P, q0: P, q1: P) -> f64 {
+    let v = q1 - q0;
+    let vv = v.dot(v);
+    let t = if vv > 0. { ((p - q0).dot(v) / vv).clamp(0., 1.) } else { 0. };
+    p.distance(q0 + v.scale(t))
+}
+
+/// Content hash of a curve (FNV-1a over the coordinate bit patterns), stable across runs and platforms.
+pub fn content_hash(c: &Curve) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for p in c {
+        for byte in p.x.to_bits().to_le_bytes().into_iter().chain(p.y.to_bits().to_le_bytes()) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
@@ -0,0 +1,51 @@
+use crate::{curve::{validate_curve, Curve}, error::PcmError, partial_curve::{partial_curve_matching, Matching}};
+
+
+/// Closed loop qs (closing it if its last point differs from its first) traversed twice, with the number of segments of a single lap.
+///
+/// Every subcurve of the loop of at most one lap, wrapping around its end or not, is a subcurve of the unrolled curve.
+pub fn unroll_loop(qs: &Curve) -> (Curve, usize) {
+    let mut closed = qs.clone();
+    if closed.first() != closed.last() {
+        closed.push(qs[0]);
+    }
+    let period = closed.len() - 1;
+    let unrolled = closed.iter().chain(&closed[1..]).copied().collect();
+    (unrolled, period)
+}
+
+/// Partial match of ps against a subcurve of a closed loop, see [`cyclic_matching`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CyclicMatching {
+    /// Matching against the unrolled loop (see [`unroll_loop`]).
+    pub matching: Matching,
+    /// Number of segments of a single lap, parameters on the unrolled loop correspond to the loop modulo period.
+    pub period: usize,
+}
+impl CyclicMatching {
+
+    /// Parameter interval [t_start, t_end] on the loop covered by the matching, with t_end < t_start if it wraps around the end of the loop.
+    pub fn interval(&self) -> (f64, f64) {
+        let (t0, t1) = self.matching.covered_interval();
+        let period = self.period as f64;
+        let shift = (t0 / period).floor() * period;
+        let (t0, t1) = (t0 - shift, t1 - shift);
+        (t0, if t1 > period { t1 - period } else { t1 })
+    }
+
+    /// Whether the matching wraps around the end of the loop back to its start.
+    pub fn wraps(&self) -> bool {
+        let (t0, t1) = self.matching.covered_interval();
+        let period = self.period as f64;
+        (t1 / period).ceil() - (t0 / period).floor() > 1.
+    }
+}
+
+/// Compute a partial curve match (if any) of ps against a subcurve of the closed loop qs below threshold epsilon, which may wrap around the end of the loop.
+///
+/// Matches against the loop traversed twice, so the matched subcurve is at most a single lap long.
+pub fn cyclic_matching(ps: Curve, qs: Curve, eps: f64) -> Result<Option<CyclicMatching>, PcmError> {
+    validate_curve(&qs)?;
+    let (unrolled, period) = unroll_loop(&qs);
+    Ok(partial_curve_matching(ps, unrolled, eps)?.map(|matching| CyclicMatching { matching, period }))
+}
@@ -0,0 +1,62 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{curve::{validate_curve, validate_eps, Curve}, error::PcmError, fsd::{free_boundary, reachable_boundary}, lineboundary::OptLineBoundary, vector::Point, LineBoundary};
+
+
+/// Decide whether ps matches a subcurve of qs (like [`crate::FSD::check_pcm`]) without storing the diagram.
+///
+/// Sweeps the diagram line by line along the longer curve, keeping only the reachable intervals of the last line,
+/// so memory is linear in the length of the shorter curve. No matching is computed, use [`crate::FSD::pcm_steps`] for that.
+pub fn pcm_decide<P: Point>(ps: &Curve<P>, qs: &Curve<P>, eps: f64) -> Result<bool, PcmError> {
+    validate_curve(ps)?;
+    validate_curve(qs)?;
+    validate_eps(eps)?;
+    Ok(if ps.len() <= qs.len() { sweep(ps, qs, eps, false) } else { sweep(qs, ps, eps, true) })
+}
+
+/// Sweep the diagram of inner curve a against outer curve b, one point of b at a time.
+///
+/// Not transposed, a is ps (lines are rows of the diagram), otherwise a is qs (lines are columns of the diagram).
+/// Per line, `across` are the boundaries at the point of b (along the segments of a) and `along` the boundaries along the next segment of b (at the points of a).
+/// The propagation rules are symmetric in both axes, only the seeded left border and the right border to end at depend on the orientation.
+fn sweep<P: Point>(a: &[P], b: &[P], eps: f64, transposed: bool) -> bool {
+    let k = a.len();
+    let mut across_prev: Vec<OptLineBoundary> = vec![None; k-1];
+    let mut along_prev: Vec<OptLineBoundary> = vec![None; k];
+    for (j, &p) in b.iter().enumerate() {
+        let mut across: Vec<OptLineBoundary> = Vec::with_capacity(k-1);
+        let mut along: Vec<OptLineBoundary> = Vec::with_capacity(k);
+        for i in 0..k {
+            if i < k - 1 {
+                let free = free_boundary(p, a[i], a[i+1], eps);
+                // Left border (transposed), or first horizontal boundary of every row starting at a free left cornerpoint.
+                let seed = if transposed && j == 0 { free } else if !transposed && i == 0 && let Some(LineBoundary { a: 0., b: _ }) = free { free } else { None };
+                let orth = along_prev[i];
+                let para = across_prev[i];
+                let prev = if i > 0 { across[i-1] } else { None };
+                across.push(reachable_boundary(seed, free, orth, para, prev));
+            }
+            if j < b.len() - 1 {
+                let free = free_boundary(a[i], p, b[j+1], eps);
+                // Left border, or first horizontal boundary of every row starting at a free left cornerpoint (transposed).
+                let seed = if !transposed && i == 0 { free } else if transposed && j == 0 && let Some(LineBoundary { a: 0., b: _ }) = free { free } else { None };
+                let orth = if i > 0 { across[i-1] } else { None };
+                let para = if i > 0 { along[i-1] } else { None };
+                let prev = along_prev[i];
+                along.push(reachable_boundary(seed, free, orth, para, prev));
+            }
+        }
+        if !transposed && along.last().is_some_and(|boundary| boundary.is_some()) {
+            return true;
+        }
+        if transposed && j == b.len() - 1 {
+            return across.iter().any(|boundary| boundary.is_some());
+        }
+        // Columns are only seeded at the left border, once nothing is reachable nothing will be.
+        if transposed && across.iter().chain(&along).all(|boundary| boundary.is_none()) {
+            return false;
+        }
+        (across_prev, along_prev) = (across, along);
+    }
+    false
+}
@@ -0,0 +1,42 @@
+use crate::{curve::Curve, error::PcmError, parallel, partial_curve::try_same_route};
+
+
+/// Fraction of a curve (in arc length) a duplicate has to cover, used by [`dedupe`].
+pub const DEFAULT_MIN_COVERAGE: f64 = 0.95;
+
+/// Group of near-identical curves, identified by their index in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    /// First curve of the group, every member is a duplicate of it.
+    pub representative: usize,
+    /// All curves of the group (including the representative), in input order.
+    pub members: Vec<usize>,
+}
+
+/// Group near-identical curves (see [`dedupe_with_coverage`]) with the default coverage.
+pub fn dedupe(curves: &[Curve], eps: f64) -> Result<Vec<DuplicateGroup>, PcmError> {
+    dedupe_with_coverage(curves, eps, DEFAULT_MIN_COVERAGE)
+}
+
+/// Group near-identical curves: curves which follow the same route at threshold eps (see [`try_same_route`]).
+///
+/// Curves are assigned greedily in input order to the first representative they duplicate, or otherwise become a representative themselves.
+/// Comparisons against the representatives are evaluated in parallel (if enabled).
+pub fn dedupe_with_coverage(curves: &[Curve], eps: f64, min_coverage: f64) -> Result<Vec<DuplicateGroup>, PcmError> {
+    let mut groups: Vec<DuplicateGroup> = vec![];
+    for (i, c) in curves.iter().enumerate() {
+        let representatives: Vec<usize> = groups.iter().map(|g| g.representative).collect();
+        let duplicates = parallel::map(representatives, |r| try_same_route(&curves[r], c, eps, min_coverage));
+        let mut opt_group = None;
+        for (k, duplicate) in duplicates.into_iter().enumerate() {
+            if duplicate? && opt_group.is_none() {
+                opt_group = Some(k);
+            }
+        }
+        match opt_group {
+            Some(k) => groups[k].members.push(i),
+            None => groups.push(DuplicateGroup { representative: i, members: vec![i] })
+        }
+    }
+    Ok(groups)
+}
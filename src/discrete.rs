@@ -0,0 +1,77 @@
+use ndarray::Array2;
+
+use crate::{curve::{validate_curve, validate_eps, Curve}, error::PcmError, vector::Point};
+
+
+/// Discrete Free-Space Diagram: only couplings of vertex pairs (i on ps, j on qs) are considered.
+///
+/// A much simpler and faster approximation of the (continuous) [`crate::FSD`] for densely sampled curves,
+/// a discrete match is a continuous match as well, but not necessarily the other way around.
+#[derive(Debug, Clone)]
+pub struct DiscreteFSD {
+    /// Width (number of points on ps).
+    pub n: usize,
+    /// Height (number of points on qs).
+    pub m: usize,
+    /// Vertex pairs strictly within distance eps (for a FSD), as free space is, or reachable by a partial matching (for a RSD).
+    pub cells: Array2<bool>,
+    /// FSD and RSD are the same struct, use this boolean to ensure being in the correct space.
+    pub is_rsd: bool,
+}
+impl DiscreteFSD {
+
+    /// Compute the discrete free-space diagram between curve P (points ps) and curve Q (points qs).
+    pub fn new<P: Point>(ps: Curve<P>, qs: Curve<P>, eps: f64) -> DiscreteFSD {
+        let (n, m) = (ps.len(), qs.len());
+        let cells = Array2::from_shape_fn((n, m), |(i, j)| ps[i].distance(qs[j]) < eps);
+        DiscreteFSD { n, m, cells, is_rsd: false }
+    }
+
+    /// Like [`DiscreteFSD::new`], but reject malformed input with an error instead of panicking.
+    pub fn try_new<P: Point>(ps: Curve<P>, qs: Curve<P>, eps: f64) -> Result<DiscreteFSD, PcmError> {
+        validate_curve(&ps)?;
+        validate_curve(&qs)?;
+        validate_eps(eps)?;
+        Ok(DiscreteFSD::new(ps, qs, eps))
+    }
+
+    /// Compute reachable space diagram: starting anywhere on the left boundary, stepping to the next vertex on ps, qs, or both.
+    pub fn to_rsd(&self) -> Self {
+        assert!(!self.is_rsd); // Sanity check: Check it is already an RSD.
+        let mut rsd = DiscreteFSD { n: self.n, m: self.m, cells: Array2::from_elem((self.n, self.m), false), is_rsd: true };
+        for i in 0..self.n {
+            for j in 0..self.m {
+                rsd.cells[(i, j)] = self.cells[(i, j)] && (i == 0 || rsd.predecessors(i, j).next().is_some());
+            }
+        }
+        rsd
+    }
+
+    /// Reachable vertex pairs a step into (i, j) may come from, diagonal first.
+    fn predecessors(&self, i: usize, j: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let candidates = [(i > 0 && j > 0).then(|| (i - 1, j - 1)), (i > 0).then(|| (i - 1, j)), (j > 0).then(|| (i, j - 1))];
+        candidates.into_iter().flatten().filter(|&c| self.cells[c])
+    }
+
+    /// Check for a discrete partial curve match.
+    pub fn check_pcm(&self) -> bool {
+        let rsd = if self.is_rsd { self } else { &self.to_rsd() };
+        (0..rsd.m).any(|j| rsd.cells[(rsd.n - 1, j)])
+    }
+
+    /// Compute steps (vertex index pairs) to walk along curves for a discrete partial matching solution.
+    pub fn pcm_steps(&self) -> Option<Vec<(f64, f64)>> {
+        let rsd = if self.is_rsd { self } else { &self.to_rsd() };
+
+        // End at the lowest reachable vertex pair on the right boundary, walk back to the left boundary.
+        let end = (0..rsd.m).find(|&j| rsd.cells[(rsd.n - 1, j)])?;
+        let mut curr = (rsd.n - 1, end);
+        let mut steps = vec![curr];
+        while curr.0 > 0 {
+            curr = rsd.predecessors(curr.0, curr.1).next().expect("Reachable vertex pair should have a reachable predecessor.");
+            steps.push(curr);
+        }
+        steps.reverse();
+        Some(steps.into_iter().map(|(i, j)| (i as f64, j as f64)).collect())
+    }
+}
@@ -0,0 +1,93 @@
+use ndarray::Array2;
+
+use crate::{curve::{validate_curve, Curve}, error::PcmError, fsd::MatchMode, vector::Point};
+
+
+/// Dynamic time warping between curve P (points ps) and curve Q (points qs): the coupling of vertex pairs minimizing the sum of their distances.
+///
+/// An alternative to the (bottleneck) Fréchet distance, which tolerates short outliers but accumulates noise along the whole matching.
+/// Couplings start and end at the boundaries the match mode allows (by default a subcurve of qs, i.e. subsequence DTW).
+#[derive(Debug, Clone)]
+pub struct DTW {
+    /// Width (number of points on ps).
+    pub n: usize,
+    /// Height (number of points on qs).
+    pub m: usize,
+    /// Smallest accumulated cost of a coupling (allowed by the match mode) ending at vertex pair (i, j).
+    pub costs: Array2<f64>,
+    /// Boundaries couplings may start and end at.
+    pub mode: MatchMode,
+}
+impl DTW {
+
+    /// Compute the subsequence DTW between curve P (points ps) and curve Q (points qs), all of ps against a subcurve of qs.
+    pub fn new<P: Point>(ps: Curve<P>, qs: Curve<P>) -> DTW {
+        DTW::new_with(ps, qs, MatchMode::SubcurveOfQ)
+    }
+
+    /// Like [`DTW::new`], starting and ending couplings at the boundaries of the match mode.
+    pub fn new_with<P: Point>(ps: Curve<P>, qs: Curve<P>, mode: MatchMode) -> DTW {
+        let (n, m) = (ps.len(), qs.len());
+        let mut dtw = DTW { n, m, costs: Array2::from_elem((n, m), f64::INFINITY), mode };
+        for (i, p) in ps.iter().enumerate() {
+            for (j, q) in qs.iter().enumerate() {
+                let cost = p.distance(*q);
+                let prev = if dtw.is_start(i, j) { 0. } else { dtw.predecessors(i, j).map(|c| dtw.costs[c]).fold(f64::INFINITY, f64::min) };
+                dtw.costs[(i, j)] = prev + cost;
+            }
+        }
+        dtw
+    }
+
+    /// Like [`DTW::new_with`], but reject malformed input with an error instead of panicking.
+    pub fn try_new_with<P: Point>(ps: Curve<P>, qs: Curve<P>, mode: MatchMode) -> Result<DTW, PcmError> {
+        validate_curve(&ps)?;
+        validate_curve(&qs)?;
+        Ok(DTW::new_with(ps, qs, mode))
+    }
+
+    /// Whether a coupling may start at vertex pair (i, j).
+    fn is_start(&self, i: usize, j: usize) -> bool {
+        match self.mode {
+            MatchMode::Full | MatchMode::PrefixOfQ => i == 0 && j == 0,
+            MatchMode::SuffixOfQ | MatchMode::SubcurveOfQ => i == 0,
+            MatchMode::SubcurveOfBoth => i == 0 || j == 0,
+        }
+    }
+
+    /// Vertex pairs a coupling may end at, lowest on the right boundary first.
+    fn ends(&self) -> Vec<(usize, usize)> {
+        let (n, m) = (self.n, self.m);
+        match self.mode {
+            MatchMode::Full | MatchMode::SuffixOfQ => vec![(n - 1, m - 1)],
+            MatchMode::PrefixOfQ | MatchMode::SubcurveOfQ => (0..m).map(|j| (n - 1, j)).collect(),
+            MatchMode::SubcurveOfBoth => (0..m).map(|j| (n - 1, j)).chain((0..n - 1).map(|i| (i, m - 1))).collect(),
+        }
+    }
+
+    /// Vertex pairs a step into (i, j) may come from, diagonal first.
+    fn predecessors(&self, i: usize, j: usize) -> impl Iterator<Item = (usize, usize)> {
+        [(i > 0 && j > 0).then(|| (i - 1, j - 1)), (i > 0).then(|| (i - 1, j)), (j > 0).then(|| (i, j - 1))].into_iter().flatten()
+    }
+
+    /// Smallest total cost (sum of the distances of the coupled vertex pairs) over all couplings.
+    pub fn cost(&self) -> f64 {
+        self.ends().into_iter().map(|c| self.costs[c]).fold(f64::INFINITY, f64::min)
+    }
+
+    /// Compute steps (vertex index pairs) of a coupling with the smallest total cost.
+    pub fn steps(&self) -> Vec<(f64, f64)> {
+        // End at the cheapest end (lowest first on ties), walk back along the cheapest predecessors (diagonal first on ties).
+        let cheapest = |candidates: Vec<(usize, usize)>| candidates.into_iter().fold(None, |best: Option<(usize, usize)>, c| {
+            if best.is_some_and(|b| self.costs[b] <= self.costs[c]) { best } else { Some(c) }
+        });
+        let mut curr = cheapest(self.ends()).expect("Curves should not be empty.");
+        let mut steps = vec![curr];
+        while !self.is_start(curr.0, curr.1) {
+            curr = cheapest(self.predecessors(curr.0, curr.1).collect()).expect("Vertex pair which is not a start should have a predecessor.");
+            steps.push(curr);
+        }
+        steps.reverse();
+        steps.into_iter().map(|(i, j)| (i as f64, j as f64)).collect()
+    }
+}
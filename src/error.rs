@@ -0,0 +1,98 @@
+use alloc::{boxed::Box, string::String};
+use core::{error::Error, fmt};
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(feature = "python")]
+use pyo3::{exceptions::{PyIOError, PyValueError}, PyErr};
+
+
+/// Cell boundary (axis, x, y) of a diagram, see [`crate::FSD`].
+pub type Cell = (usize, usize, usize);
+
+/// Ways matching, validation and (de)serialization may fail.
+#[derive(Debug)]
+pub enum PcmError {
+    /// Curve with fewer than two points.
+    EmptyCurve { len: usize },
+    /// Curve point with a non-finite or too large coordinate (formatted, since points are generic).
+    InvalidPoint { index: usize, point: String },
+    /// Curve point equal to its predecessor (a zero-length segment).
+    DuplicatePoint { index: usize },
+    /// Timestamp which is not finite or decreases from its predecessor, see [`crate::TimedCurve`].
+    InvalidTimestamp { index: usize, time: f64 },
+    /// Distance threshold which is not positive and finite.
+    InvalidThreshold(f64),
+    /// Any other argument out of range (e.g. tolerance, resolution or penalties).
+    InvalidArgument(String),
+    /// Steps to validate are empty.
+    EmptySteps,
+    /// Step beyond the parameter range of the curves.
+    StepOutOfRange { step: (f64, f64) },
+    /// Step decreasing along either curve.
+    NonMonotoneSteps { from: (f64, f64), to: (f64, f64) },
+    /// Step coupling points further apart than the threshold.
+    StepExceedsThreshold { step: (f64, f64), distance: f64, eps: f64 },
+    /// Cell boundary contradicting the diagram (or the diagram it is derived from).
+    InconsistentBoundary { cell: Cell, detail: String },
+    /// Diagrams (or results derived from them) contradicting each other as a whole.
+    InconsistentDiagram(String),
+    /// Malformed input of a curve format.
+    Parse(String),
+    /// Failure of a single candidate (by index) of a batch.
+    Candidate { index: usize, source: Box<PcmError> },
+    /// Reading or writing a file failed.
+    #[cfg(feature = "std")]
+    Io(io::Error),
+}
+
+impl fmt::Display for PcmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PcmError::EmptyCurve { len } => write!(f, "Curve should consist of at least two points, got {len}."),
+            PcmError::InvalidPoint { index, point } => write!(f, "Curve point {index} has a non-finite or too large coordinate ({point})."),
+            PcmError::DuplicatePoint { index } => write!(f, "Curve point {index} equals its predecessor (a zero-length segment)."),
+            PcmError::InvalidTimestamp { index, time } => write!(f, "Timestamp {index} is not finite or decreases from its predecessor, got {time}."),
+            PcmError::InvalidThreshold(eps) => write!(f, "Distance threshold should be positive and finite, got {eps}."),
+            PcmError::InvalidArgument(detail) => write!(f, "{detail}"),
+            PcmError::EmptySteps => write!(f, "Steps should not be empty."),
+            PcmError::StepOutOfRange { step: (i, j) } => write!(f, "Step ({i}, {j}) lies outside of the curves."),
+            PcmError::NonMonotoneSteps { from: (i1, j1), to: (i2, j2) } => write!(f, "Decreasing from step ({i1}, {j1}) to ({i2}, {j2})."),
+            PcmError::StepExceedsThreshold { step: (i, j), distance, eps } => write!(f, "Distance {distance} at step ({i}, {j}) should be below threshold {eps}+{}.", crate::EPS),
+            PcmError::InconsistentBoundary { cell, detail } => write!(f, "Boundary at {cell:?}: {detail}"),
+            PcmError::InconsistentDiagram(detail) => write!(f, "{detail}"),
+            PcmError::Parse(detail) => write!(f, "{detail}"),
+            PcmError::Candidate { index, source } => write!(f, "Candidate {index}: {source}"),
+            #[cfg(feature = "std")]
+            PcmError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for PcmError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PcmError::Candidate { source, .. } => Some(source.as_ref()),
+            #[cfg(feature = "std")]
+            PcmError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for PcmError {
+    fn from(e: io::Error) -> Self {
+        PcmError::Io(e)
+    }
+}
+
+#[cfg(feature = "python")]
+impl From<PcmError> for PyErr {
+    fn from(e: PcmError) -> Self {
+        match e {
+            PcmError::Io(e) => PyIOError::new_err(e.to_string()),
+            e => PyValueError::new_err(e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+/// Part of a matching that pairs (part of) a single segment of ps with (part of) a single segment of qs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentMatch {
+    /// Segment index on ps (between points `p_segment` and `p_segment + 1`).
+    pub p_segment: usize,
+    /// Matched sub-interval of the ps segment (as offsets on the unit interval).
+    pub p_interval: (f64, f64),
+    /// Segment index on qs (between points `q_segment` and `q_segment + 1`).
+    pub q_segment: usize,
+    /// Matched sub-interval of the qs segment (as offsets on the unit interval).
+    pub q_interval: (f64, f64),
+}
+
+/// Map the steps of a matching between curves with n and m points back to segment indices.
+///
+/// Every step is split at the FSD cells it walks through, consecutive pieces within the same cell are merged.
+/// Results are ordered along the matching, so all segments of qs a segment of ps is matched against are adjacent.
+pub fn segment_matches(steps: &[(f64, f64)], n: usize, m: usize) -> Vec<SegmentMatch> {
+    let mut result: Vec<SegmentMatch> = vec![];
+    if n < 2 || m < 2 { return result; }
+
+    for w in steps.windows(2) {
+        let ((i0, j0), (i1, j1)) = (w[0], w[1]);
+        if w[0] == w[1] { continue; }
+
+        // Split the step at every integer crossing of either parameter.
+        let mut ts = vec![0., 1.];
+        for (a, b) in [(i0, i1), (j0, j1)] {
+            if b > a {
+                let mut k = a.floor() + 1.;
+                while k < b {
+                    ts.push((k - a) / (b - a));
+                    k += 1.;
+                }
+            }
+        }
+        ts.sort_by(f64::total_cmp);
+        ts.dedup();
+
+        for t in ts.windows(2) {
+            let at = |t: f64| (i0 + t * (i1 - i0), j0 + t * (j1 - j0));
+            let ((pi0, pj0), (pi1, pj1)) = (at(t[0]), at(t[1]));
+            // Cell containing the piece (its midpoint), pieces on the last vertex belong to the last segment.
+            let (mi, mj) = at(0.5 * (t[0] + t[1]));
+            let p_segment = (mi.floor() as usize).min(n - 2);
+            let q_segment = (mj.floor() as usize).min(m - 2);
+            let (ko, lo) = (p_segment as f64, q_segment as f64);
+
+            if let Some(last) = result.last_mut() && last.p_segment == p_segment && last.q_segment == q_segment {
+                last.p_interval.1 = pi1 - ko;
+                last.q_interval.1 = pj1 - lo;
+            } else {
+                result.push(SegmentMatch { p_segment, p_interval: (pi0 - ko, pi1 - ko), q_segment, q_interval: (pj0 - lo, pj1 - lo) });
+            }
+        }
+    }
+
+    result
+}
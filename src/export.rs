@@ -0,0 +1,101 @@
+use serde_json::json;
+
+use crate::curve::Curve;
+
+
+/// Output precision of exporters, unset fields keep full precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExportOptions {
+    /// Decimals of coordinates (and other distances).
+    pub coordinate_decimals: Option<u32>,
+    /// Decimals of step (curve parameter) values.
+    pub step_decimals: Option<u32>,
+}
+impl ExportOptions {
+
+    pub fn with_coordinate_decimals(mut self, decimals: u32) -> Self {
+        self.coordinate_decimals = Some(decimals);
+        self
+    }
+
+    pub fn with_step_decimals(mut self, decimals: u32) -> Self {
+        self.step_decimals = Some(decimals);
+        self
+    }
+
+    /// Round a coordinate (or distance) to the configured precision.
+    pub fn coordinate(&self, v: f64) -> f64 {
+        round_to(v, self.coordinate_decimals)
+    }
+
+    /// Round a step value to the configured precision.
+    pub fn step(&self, v: f64) -> f64 {
+        round_to(v, self.step_decimals)
+    }
+}
+
+/// Round v to the number of decimals (if any).
+///
+/// Values are rounded numerically, so printing them (shortest representation) drops trailing digits.
+pub fn round_to(v: f64, decimals: Option<u32>) -> f64 {
+    match decimals {
+        Some(d) if d < 16 && v.is_finite() => {
+            let scale = 10f64.powi(d as i32);
+            let rounded = (v * scale).round() / scale;
+            if rounded.is_finite() { rounded } else { v }
+        },
+        _ => v
+    }
+}
+
+/// Curve as GeoJSON LineString feature (x as longitude/easting, y as latitude/northing).
+pub fn curve_to_geojson(c: &Curve, options: &ExportOptions) -> String {
+    let coordinates: Vec<[f64; 2]> = c.iter().map(|p| [options.coordinate(p.x), options.coordinate(p.y)]).collect();
+    json!({
+        "type": "Feature",
+        "geometry": { "type": "LineString", "coordinates": coordinates },
+        "properties": {}
+    }).to_string()
+}
+
+/// Curve as CSV with header `x,y`.
+pub fn curve_to_csv(c: &Curve, options: &ExportOptions) -> String {
+    let mut result = "x,y\n".to_string();
+    for p in c {
+        result += &format!("{},{}\n", options.coordinate(p.x), options.coordinate(p.y));
+    }
+    result
+}
+
+/// Steps as CSV with header `i,j` (parameter on ps, parameter on qs).
+pub fn steps_to_csv(steps: &[(f64, f64)], options: &ExportOptions) -> String {
+    let mut result = "i,j\n".to_string();
+    for &(i, j) in steps {
+        result += &format!("{},{}\n", options.step(i), options.step(j));
+    }
+    result
+}
+
+/// Steps as JSON array of `[i, j]` pairs.
+pub fn steps_to_json(steps: &[(f64, f64)], options: &ExportOptions) -> String {
+    let steps: Vec<[f64; 2]> = steps.iter().map(|&(i, j)| [options.step(i), options.step(j)]).collect();
+    json!(steps).to_string()
+}
+
+/// Matrix as CSV, the header names the columns `q0,q1,...` and every row starts with its name `p{i}`.
+pub fn matrix_to_csv(matrix: &[Vec<f64>], options: &ExportOptions) -> String {
+    let columns = matrix.first().map_or(0, |row| row.len());
+    let mut result = String::new();
+    for j in 0..columns {
+        result += &format!(",q{j}");
+    }
+    result += "\n";
+    for (i, row) in matrix.iter().enumerate() {
+        result += &format!("p{i}");
+        for v in row {
+            result += &format!(",{}", options.coordinate(*v));
+        }
+        result += "\n";
+    }
+    result
+}
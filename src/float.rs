@@ -0,0 +1,70 @@
+use core::{fmt::{Debug, Display}, ops::{Add, Div, Mul, Neg, Sub}};
+
+
+/// Float function of `std`, or of the nightly `core::{f32, f64}::math` without it (`math!(f64, sqrt(x))`).
+#[cfg(feature = "std")]
+macro_rules! math {
+    ($t:ident, $f:ident($($x:expr),*)) => { $t::$f($($x),*) };
+}
+#[cfg(not(feature = "std"))]
+macro_rules! math {
+    ($t:ident, $f:ident($($x:expr),*)) => { core::$t::math::$f($($x),*) };
+}
+
+
+/// Floating point scalar points and diagrams are stored in, `f64` by default and `f32` to halve memory (bandwidth).
+///
+/// Thresholds, steps and error bounds remain `f64`, scalars are converted at the boundaries of the computations.
+pub trait Float: Copy + Debug + Display + Default + PartialEq + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Neg<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+    /// Difference between 1 and the next larger representable number.
+    const EPSILON: Self;
+    /// Smallest positive normal number.
+    const MIN_POSITIVE: Self;
+
+    /// Round a double to this scalar (to nearest).
+    fn from_f64(x: f64) -> Self;
+    /// Widen this scalar to a double.
+    fn to_f64(self) -> f64;
+    fn sqrt(self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    /// Fused multiply-add, `self * a + b` rounded once.
+    fn mul_add(self, a: Self, b: Self) -> Self;
+    fn abs(self) -> Self;
+    fn min(self, rhs: Self) -> Self;
+    fn max(self, rhs: Self) -> Self;
+}
+impl Float for f64 {
+    const ZERO: Self = 0.;
+    const ONE: Self = 1.;
+    const EPSILON: Self = f64::EPSILON;
+    const MIN_POSITIVE: Self = f64::MIN_POSITIVE;
+
+    fn from_f64(x: f64) -> Self { x }
+    fn to_f64(self) -> f64 { self }
+    fn sqrt(self) -> Self { math!(f64, sqrt(self)) }
+    fn floor(self) -> Self { math!(f64, floor(self)) }
+    fn ceil(self) -> Self { math!(f64, ceil(self)) }
+    fn mul_add(self, a: Self, b: Self) -> Self { math!(f64, mul_add(self, a, b)) }
+    fn abs(self) -> Self { f64::abs(self) }
+    fn min(self, rhs: Self) -> Self { f64::min(self, rhs) }
+    fn max(self, rhs: Self) -> Self { f64::max(self, rhs) }
+}
+impl Float for f32 {
+    const ZERO: Self = 0.;
+    const ONE: Self = 1.;
+    const EPSILON: Self = f32::EPSILON;
+    const MIN_POSITIVE: Self = f32::MIN_POSITIVE;
+
+    fn from_f64(x: f64) -> Self { x as f32 }
+    fn to_f64(self) -> f64 { self as f64 }
+    fn sqrt(self) -> Self { math!(f32, sqrt(self)) }
+    fn floor(self) -> Self { math!(f32, floor(self)) }
+    fn ceil(self) -> Self { math!(f32, ceil(self)) }
+    fn mul_add(self, a: Self, b: Self) -> Self { math!(f32, mul_add(self, a, b)) }
+    fn abs(self) -> Self { f32::abs(self) }
+    fn min(self, rhs: Self) -> Self { f32::min(self, rhs) }
+    fn max(self, rhs: Self) -> Self { f32::max(self, rhs) }
+}
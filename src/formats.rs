@@ -0,0 +1,126 @@
+use crate::{curve::{validate_curve, Curve}, error::PcmError, export::ExportOptions, vector::Vector};
+
+
+/// Read a curve from CSV with a point per line.
+///
+/// With a header, the coordinates are taken from the `x`/`y` (or `lon`/`lat`) columns, otherwise from the first two columns.
+/// Empty lines are skipped.
+pub fn curve_from_csv(input: &str) -> Result<Curve, PcmError> {
+    let mut lines = input.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()).peekable();
+    let mut columns = (0, 1);
+    if let Some((_, header)) = lines.peek() && header.split(',').next().is_some_and(|v| v.trim().parse::<f64>().is_err()) {
+        let names: Vec<String> = header.split(',').map(|name| name.trim().to_lowercase()).collect();
+        let find = |candidates: &[&str]| names.iter().position(|name| candidates.contains(&name.as_str()));
+        columns = match (find(&["x", "lon", "lng", "longitude"]), find(&["y", "lat", "latitude"])) {
+            (Some(x), Some(y)) => (x, y),
+            _ => (0, 1),
+        };
+        lines.next();
+    }
+    let curve = lines.map(|(k, line)| {
+        let values: Vec<&str> = line.split(',').map(|v| v.trim()).collect();
+        let parse = |c: usize| values.get(c).and_then(|v| v.parse::<f64>().ok());
+        match (parse(columns.0), parse(columns.1)) {
+            (Some(x), Some(y)) => Ok(Vector::new(x, y)),
+            _ => Err(PcmError::Parse(format!("Invalid CSV point at line {}: {line}", k + 1))),
+        }
+    }).collect::<Result<Curve, PcmError>>()?;
+    validate_curve(&curve)?;
+    Ok(curve)
+}
+
+/// Read all curves out of a WKT `LINESTRING` or `MULTILINESTRING` (coordinates beyond x and y, e.g. Z or M, are ignored).
+pub fn curves_from_wkt(input: &str) -> Result<Vec<Curve>, PcmError> {
+    let input = input.trim();
+    let open = input.find('(').ok_or_else(|| PcmError::Parse(format!("Expected LINESTRING or MULTILINESTRING, got {input}.")))?;
+    let tag = input[..open].split_whitespace().next().unwrap_or("").to_uppercase();
+    let body = input[open..].strip_suffix(')').ok_or(PcmError::Parse("Unbalanced parentheses in WKT.".to_string()))?;
+    let parts: Vec<&str> = match tag.as_str() {
+        "LINESTRING" => vec![&body[1..]],
+        "MULTILINESTRING" => body[1..].split(')').map(|part| part.trim_start_matches([',', ' ', '('])).filter(|part| !part.trim().is_empty()).collect(),
+        _ => return Err(PcmError::Parse(format!("Expected LINESTRING or MULTILINESTRING, got {tag}."))),
+    };
+    parts.into_iter().map(|part| {
+        let curve = part.split(',').map(|position| {
+            let values: Vec<Option<f64>> = position.split_whitespace().map(|v| v.parse::<f64>().ok()).collect();
+            match values.as_slice() {
+                [Some(x), Some(y), rest @ ..] if rest.iter().all(|v| v.is_some()) => Ok(Vector::new(*x, *y)),
+                _ => Err(PcmError::Parse(format!("Invalid WKT position \"{}\".", position.trim()))),
+            }
+        }).collect::<Result<Curve, PcmError>>()?;
+        validate_curve(&curve)?;
+        Ok(curve)
+    }).collect()
+}
+
+/// Read the single curve out of a WKT `LINESTRING`, see [`curves_from_wkt`].
+pub fn curve_from_wkt(input: &str) -> Result<Curve, PcmError> {
+    let mut curves = curves_from_wkt(input)?;
+    if curves.len() != 1 {
+        return Err(PcmError::Parse(format!("Expected a single curve in WKT, found {}.", curves.len())));
+    }
+    Ok(curves.pop().unwrap())
+}
+
+/// Curve as WKT `LINESTRING`.
+pub fn curve_to_wkt(c: &Curve, options: &ExportOptions) -> String {
+    let positions: Vec<String> = c.iter().map(|p| format!("{} {}", options.coordinate(p.x), options.coordinate(p.y))).collect();
+    format!("LINESTRING ({})", positions.join(", "))
+}
+
+/// Decode a Google encoded polyline into a curve (x as longitude, y as latitude).
+///
+/// Precision is the number of decimals encoded, 5 for Google, 6 for e.g. OSRM and Valhalla.
+pub fn curve_from_polyline(input: &str, precision: u32) -> Result<Curve, PcmError> {
+    let scale = 10f64.powi(precision as i32);
+    let mut bytes = input.bytes();
+    let mut next = || -> Result<Option<i64>, PcmError> {
+        let (mut result, mut shift) = (0i64, 0);
+        loop {
+            let Some(byte) = bytes.next() else {
+                return if shift == 0 { Ok(None) } else { Err(PcmError::Parse("Truncated encoded polyline.".to_string())) };
+            };
+            if !(63..127).contains(&byte) || shift > 60 {
+                return Err(PcmError::Parse(format!("Invalid character {:?} in encoded polyline.", byte as char)));
+            }
+            let chunk = (byte - 63) as i64;
+            result |= (chunk & 0x1f) << shift;
+            shift += 5;
+            if chunk < 0x20 {
+                return Ok(Some(if result & 1 == 1 { !(result >> 1) } else { result >> 1 }));
+            }
+        }
+    };
+    let (mut lat, mut lon) = (0i64, 0i64);
+    let mut curve = vec![];
+    while let Some(dlat) = next()? {
+        let dlon = next()?.ok_or(PcmError::Parse("Encoded polyline ends halfway a point.".to_string()))?;
+        lat += dlat;
+        lon += dlon;
+        curve.push(Vector::new(lon as f64 / scale, lat as f64 / scale));
+    }
+    validate_curve(&curve)?;
+    Ok(curve)
+}
+
+/// Encode a curve (x as longitude, y as latitude) as Google encoded polyline with the number of decimals of precision.
+pub fn curve_to_polyline(c: &Curve, precision: u32) -> String {
+    let scale = 10f64.powi(precision as i32);
+    let mut result = String::new();
+    let mut encode = |delta: i64| {
+        let mut value = if delta < 0 { !(delta << 1) } else { delta << 1 };
+        while value >= 0x20 {
+            result.push((((value & 0x1f) | 0x20) as u8 + 63) as char);
+            value >>= 5;
+        }
+        result.push((value as u8 + 63) as char);
+    };
+    let (mut lat, mut lon) = (0i64, 0i64);
+    for p in c {
+        let (next_lat, next_lon) = ((p.y * scale).round() as i64, (p.x * scale).round() as i64);
+        encode(next_lat - lat);
+        encode(next_lon - lon);
+        (lat, lon) = (next_lat, next_lon);
+    }
+    result
+}
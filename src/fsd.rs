@@ -1,10 +1,16 @@
+use alloc::{collections::VecDeque, format, string::{String, ToString}, vec, vec::Vec};
+
 use ndarray::{s, Array2, Array3, ArrayBase, Dim, OwnedRepr};
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "ndarray")]
+use ndarray::ArrayView2;
 
-use crate::{curve::Curve, lineboundary::OptLineBoundary, LineBoundary};
+use crate::{curve::{validate_curve, validate_eps, Curve}, error::PcmError, float::Float, lineboundary::OptLineBoundary, parallel, predicates::{exact_boundary, inside, Predicates}, vector::Point, LineBoundary};
 
 
 /// Position on the FSD considering axis.
-type FSDPosition = (usize, usize, usize, f64);
+pub(crate) type FSDPosition = (usize, usize, usize, f64);
 
 /// Convert a FSD position into curve positions.
 fn position_to_ij((axis, x, y, off): FSDPosition) -> (f64, f64) {
@@ -17,15 +23,42 @@ fn position_to_seg((axis, x, y, _): FSDPosition) -> (usize, usize, usize) {
 }
 
 /// Check whether position is on the left boundary of the FSD.
-fn position_on_left_boundary((axis, x, y, _): (usize, usize, usize, f64)) -> bool {
+fn position_on_left_boundary((axis, x, y, off): (usize, usize, usize, f64)) -> bool {
     (axis == 0 && x == 0) ||
-    (axis == 1 && y == 0)
+    (axis == 1 && y == 0 && off == 0.)
+}
+
+
+/// Check whether position is on the bottom boundary of the FSD.
+fn position_on_bottom_boundary((axis, x, y, off): (usize, usize, usize, f64)) -> bool {
+    (axis == 1 && x == 0) ||
+    (axis == 0 && y == 0 && off == 0.)
+}
+
+
+/// Which parts of both curves have to be matched, determining which FSD boundaries a matching may start and end at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MatchMode {
+    /// All of ps against all of qs (start at the bottom-left corner, end at the top-right corner).
+    Full,
+    /// All of ps against a prefix of qs (start at the bottom-left corner, end anywhere on the right boundary).
+    PrefixOfQ,
+    /// All of ps against a suffix of qs (start anywhere on the left boundary, end at the top-right corner).
+    SuffixOfQ,
+    /// All of ps against a subcurve of qs (start anywhere on the left boundary, end anywhere on the right boundary).
+    #[default]
+    SubcurveOfQ,
+    /// Either curve against a subcurve of the other, or a suffix of one against a prefix of the other
+    /// (start anywhere on the left or bottom boundary, end anywhere on the right or top boundary).
+    SubcurveOfBoth,
 }
 
 
-/// Free-Space Diagram.
+/// Free-Space Diagram, with its cell boundaries stored in scalar F (`f64` by default, `f32` for half the memory).
 #[derive(Debug, Clone)]
-pub struct FSD {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FSD<F: Float = f64> {
     /// Width (number of points on ps).
     pub n: usize,
     /// Height (number of points on qs).
@@ -33,30 +66,267 @@ pub struct FSD {
     /// Axis-specific dimensions (basically `[(n,m-1), (m,n-1)]`).
     pub dims: [(usize, usize); 2], 
     /// Cell boundaries for both axii. Format is (axis, x, y).
-    pub segs : ArrayBase<OwnedRepr<OptLineBoundary>, Dim<[usize; 3]>>,
+    pub segs : ArrayBase<OwnedRepr<OptLineBoundary<F>>, Dim<[usize; 3]>>,
     /// Cornerpoints either true or not. (Used for debugging purposes, the consistency in segment computations).
     pub corners: ArrayBase<OwnedRepr<bool>, Dim<[usize; 2]>>,
     /// FSD and RSD are the same struct, use this boolean to ensure being in the correct space.
-    pub is_rsd: bool
+    pub is_rsd: bool,
+    /// Boundary conditions the RSD is computed with.
+    pub mode: MatchMode,
+    /// Whether the RSD drops the monotonicity of the matching (weak Fréchet), see [`FSD::to_weak_rsd_with`].
+    pub weak: bool,
+    /// Floating point error bound on the endpoints of every cell boundary (same layout as `segs`).
+    pub errs: ArrayBase<OwnedRepr<F>, Dim<[usize; 3]>>,
+    /// Smallest margin (minus error bound) of the interval comparisons made while propagating reachability.
+    /// A negative value means the decision may flip under rounding.
+    pub slack: f64
+}
+
+/// Free interval on the cell boundary of point p against segment (q0, q1).
+pub(crate) fn free_boundary<P: Point>(p: P, q0: P, q1: P, eps: f64) -> OptLineBoundary {
+    let LineBoundary { a, b } = LineBoundary::compute(p, q0, q1, eps)?;
+    snap_boundary(a, b, p.distance(q0) < eps, p.distance(q1) < eps)
+}
+
+/// Snap boundary endpoints onto cornerpoints within eps distance, and off cornerpoints which are not.
+/// 
+/// Rounding near tangency (or on zero-length segments) otherwise breaks the relation between corners and boundaries checked by [`FSD::validate`],
+/// so the decision of the (strict) corner test is leading. Boundaries left without interior are empty.
+pub(crate) fn snap_boundary(a: f64, b: f64, start: bool, end: bool) -> OptLineBoundary {
+    let a = if start { 0. } else { a.max(f64::MIN_POSITIVE) };
+    let b = if end { 1. } else { b.min(1. - f64::EPSILON / 2.) };
+    LineBoundary::new(a, b)
+}
+
+/// Whether cornerpoint (p, q) is free, decided with the given predicates.
+fn corner<P: Point>(p: P, q: P, eps: f64, predicates: Predicates) -> bool {
+    match predicates {
+        Predicates::Float => p.distance(q) < eps,
+        Predicates::Exact => inside(p, q, eps),
+    }
+}
+
+/// Reachable part of a free cell boundary, given its initiated interval (if any) and the reachable intervals on its orthogonal, parallel and previous boundaries.
+/// 
+/// Same rules as [`FSD::propagate_at`], for diagrams which do not store their boundaries in a dense array.
+pub(crate) fn reachable_boundary(seed: OptLineBoundary, free: OptLineBoundary, orth: OptLineBoundary, para: OptLineBoundary, prev: OptLineBoundary) -> OptLineBoundary {
+    let Some(LineBoundary { a, b }) = free else { return seed };
+    let mut reachable = if orth.is_some() { free } else { seed };
+    if let Some(LineBoundary { a: a_, b: _ }) = para {
+        reachable = LineBoundary::union(reachable, LineBoundary::new(a.max(a_), b));
+    }
+    if let Some(LineBoundary { a: _, b: 1. }) = prev && a == 0. {
+        reachable = LineBoundary::union(reachable, LineBoundary::new(0., b));
+    }
+    reachable
+}
+
+/// Read access to the cell boundaries of a diagram, shared by the dense [`FSD`] and the sparse [`crate::SparseFSD`].
+pub(crate) trait Boundaries: core::fmt::Debug {
+
+    /// Number of points on ps and qs.
+    fn size(&self) -> (usize, usize);
+
+    /// Boundary conditions the (reachable space) diagram is computed with.
+    fn mode(&self) -> MatchMode;
+
+    /// Free (or reachable) interval on the cell boundary (axis, x, y).
+    fn boundary(&self, seg: (usize, usize, usize)) -> OptLineBoundary;
+
+    /// Reachable position the match mode of the RSD may end at (if any).
+    /// 
+    /// Prefers the lowest point on the right boundary, then the leftmost point on the top boundary.
+    fn end_position(&self) -> Option<FSDPosition> {
+        let rsd = self;
+        let (n, m) = rsd.size();
+        let corner = || {
+            if let Some(LineBoundary { a: _, b: 1. }) = rsd.boundary((0, n-1, m-2)) { Some((0, n-1, m-2, 1.)) }
+            else if let Some(LineBoundary { a: _, b: 1. }) = rsd.boundary((1, m-1, n-2)) { Some((1, m-1, n-2, 1.)) }
+            else { None }
+        };
+        let right = || (0..m-1).find_map(|y| rsd.boundary((0, n-1, y)).map(|LineBoundary { a, b: _ }| (0, n-1, y, a)));
+        let top = || (0..n-1).find_map(|x| rsd.boundary((1, m-1, x)).map(|LineBoundary { a, b: _ }| (1, m-1, x, a)));
+        match rsd.mode() {
+            MatchMode::Full | MatchMode::SuffixOfQ => corner(),
+            MatchMode::PrefixOfQ | MatchMode::SubcurveOfQ => right(),
+            MatchMode::SubcurveOfBoth => right().or_else(top),
+        }
+    }
+
+    /// Walk backwards from the (reachable) position curr to the left boundary of the RSD (or bottom boundary, if the match mode may start there).
+    fn steps_to(&self, mut curr: FSDPosition) -> Vec<(f64,f64)> {
+        let rsd = self;
+        let mut steps = vec![];
+
+        // Final step is found.
+        steps.push(position_to_ij(curr));
+
+        // Walk backwards. (Walk greedily, it should not matter).
+        let on_start = |curr| position_on_left_boundary(curr) || (rsd.mode() == MatchMode::SubcurveOfBoth && position_on_bottom_boundary(curr));
+        while !on_start(curr) { // Walk while we're not at the start position of P.
+
+            // println!("curr: {curr:?}");
+            if rsd.boundary(position_to_seg(curr)).is_none() {
+                // Sanity check:
+                panic!("Current segment while walking ({curr:?}) should not be empty.");
+            }
+
+            // Try to walk backwards.
+            let (axis, x, y, off) = curr;
+            let mut states = vec![curr];
+            if off == 0. { // In this case we can decide to walk both directions.
+                states.push((1-axis, y, x, off))
+            }
+
+            let mut next = None;
+            for (axis, x, y, off) in states {
+                if next.is_none() {
+                    let opt_prev = if off == 0. && y > 0 { 
+                        Some((axis, x, y-1)) 
+                    } else { None }; // previous.
+                    let opt_para = if x > 0 { 
+                        Some((axis, x-1, y)) 
+                    } else { None }; // parallel. (At off 0 only along the cell edge, which is free if both its endpoints are.)
+                    let opt_orth = if x > 0 { 
+                        Some((1-axis, y, x-1))
+                    } else { None }; // orthogonal.
+
+                    // Attempt to walk to previous.
+                    if let Some(prev) = opt_prev {
+                        if let Some(LineBoundary { a: a_, b: b_ }) = rsd.boundary(prev) {
+                            if off == 0. && b_ == 1. {
+                                // println!("prev");
+                                let (axis, x, y) = prev;
+                                let off = a_;
+                                next = Some((axis, x, y, off));
+                            }
+                        }
+                    }
+
+                    // Attempt to walk to orthogonal.
+                    if next.is_none() && let Some(orth) = opt_orth {
+                        if let Some(LineBoundary { a: a_, b: b_ }) = rsd.boundary(orth) {
+                            if (off == 0. && b_ == 1.) || off > 0. {
+                                // println!("orth");
+                                let (axis, x, y) = orth;
+                                let off = a_;
+                                next = Some((axis, x, y, off));
+                            } 
+                        }
+                    } 
+
+                    // Attempt to walk to parallel.
+                    if next.is_none() && let Some(para) = opt_para {
+                        if let Some(LineBoundary { a: a_, b: _ }) = rsd.boundary(para) {
+                            if off >= a_ {
+                                // println!("para");
+                                let (axis, x, y) = para;
+                                let off = a_;
+                                next = Some((axis, x, y, off));
+                            }
+                        }
+                    }
+
+                    // Attempt to walk along the boundary itself back to its (reachable) start.
+                    // (Required for positions which are only reachable along the boundary, such as on the bottom row.)
+                    if next.is_none() && off > 0. && let Some(LineBoundary { a: 0., b: _ }) = rsd.boundary((axis, x, y)) {
+                        next = Some((axis, x, y, 0.));
+                    }
+                }
+            }
+
+            if next.is_none() {
+                // Sanity check:
+                panic!("Should find next step in backwards walk at {curr:?}.\n{rsd:?}");
+            }
+            // println!("next: {next:?}");
+            curr = next.unwrap();
+            steps.push(position_to_ij(curr));
+
+        }
+
+        steps.reverse();
+        steps
+    }
+}
+impl<F: Float> Boundaries for FSD<F> {
+
+    fn size(&self) -> (usize, usize) {
+        (self.n, self.m)
+    }
+
+    fn mode(&self) -> MatchMode {
+        self.mode
+    }
+
+    fn boundary(&self, seg: (usize, usize, usize)) -> OptLineBoundary {
+        self.segs[seg].map(LineBoundary::to_f64)
+    }
 }
+
 impl FSD {
-    
+
+    /// Compute the free-space diagram between curve P (points ps) and curve Q (points qs).
+    /// Placing P point indices on the horizontal axis and Q on the vertical axis.
+    pub fn new<P: Point>(ps: Curve<P>, qs: Curve<P>, eps: f64) -> FSD {
+        FSD::from_slices(&ps, &qs, eps)
+    }
+
+    /// Like [`FSD::new`], but borrowing the curves (so matching a curve against many others needs no copies of it).
+    pub fn from_slices<P: Point>(ps: &[P], qs: &[P], eps: f64) -> FSD {
+        FSD::from_slices_with(ps, qs, eps, Predicates::Float)
+    }
+
+    /// Like [`FSD::new`], deciding free space with the given predicates (use [`Predicates::Exact`] for degenerate input,
+    /// such as collinear points, coincident vertices or boundaries tangent to the disc).
+    pub fn new_with<P: Point>(ps: Curve<P>, qs: Curve<P>, eps: f64, predicates: Predicates) -> FSD {
+        FSD::from_slices_with(&ps, &qs, eps, predicates)
+    }
+
+    /// Like [`FSD::new_with`], but borrowing the curves.
+    pub fn from_slices_with<P: Point>(ps: &[P], qs: &[P], eps: f64, predicates: Predicates) -> FSD {
+        FSD::compute(ps, qs, eps, predicates)
+    }
+
+    /// Like [`FSD::new`], but reject malformed input (too short curves, non-finite coordinates, non-positive or non-finite eps) with an error instead of panicking.
+    ///
+    /// Zero-length segments are accepted (their boundaries agree with the corners), [`crate::CurveExt::normalize`] drops them.
+    pub fn try_new<P: Point>(ps: Curve<P>, qs: Curve<P>, eps: f64) -> Result<FSD, PcmError> {
+        validate_curve(&ps)?;
+        validate_curve(&qs)?;
+        validate_eps(eps)?;
+        Ok(FSD::new(ps, qs, eps))
+    }
+
+    /// Like [`FSD::new`], computing the cell boundaries and corners of different lines in parallel (with the `parallel` feature).
+    ///
+    /// Results are identical to [`FSD::new`], use it for single pairs of very long curves (tens of thousands of points).
+    pub fn new_parallel<P: Point + Sync>(ps: Curve<P>, qs: Curve<P>, eps: f64) -> FSD {
+        FSD::compute_parallel(&ps, &qs, eps, Predicates::Float)
+    }
+
+}
+
+impl<F: Float> FSD<F> {
 
     /// Construct empty FSD.
-    fn empty(n: usize, m: usize) -> FSD {
+    fn empty(n: usize, m: usize) -> FSD<F> {
         let dims = [(n,m-1), (m,n-1)];
         let segs = Array3::from_shape_simple_fn((2,n.max(m),m.max(n)), || None);
         // let verticals = Array2::from_shape_simple_fn([n,m-1], || None); // Contains n cols, with m-1 intervals.
         // let horizontals = Array2::from_shape_simple_fn([m,n-1], || None);  // Contains m rows, with n-1 intervals.
         let corners = Array2::from_shape_simple_fn([n,m], || false);
         let is_rsd = false;
-        FSD { n, m, dims, segs, corners, is_rsd }
+        let errs = Array3::from_shape_simple_fn((2,n.max(m),m.max(n)), || F::ZERO);
+        let slack = f64::INFINITY;
+        FSD { n, m, dims, segs, corners, is_rsd, mode: MatchMode::SubcurveOfQ, weak: false, errs, slack }
     }
 
 
-    /// Compute the free-space diagram between curve P (points ps) and curve Q (points qs).
-    /// Placing P point indices on the horizontal axis and Q on the vertical axis.
-    pub fn new(ps: Curve, qs: Curve, eps: f64) -> FSD {
+    /// Like [`FSD::from_slices_with`], storing the diagram in scalar F (e.g. `FSD::<f32>::compute` for half the memory).
+    ///
+    /// Boundaries are computed in `f64` and rounded (keeping them consistent with the corners), the error bounds include the rounding.
+    pub fn compute<P: Point>(ps: &[P], qs: &[P], eps: f64, predicates: Predicates) -> FSD<F> {
 
         let n = ps.len();
         let m = qs.len();
@@ -64,105 +334,237 @@ impl FSD {
 
         // Constructing cell boundaries.
         for axis in 0..2 {
-            let dims = fsd.dims[axis];
-            let (c1, c2) = [(&ps, &qs), (&qs, &ps)][axis];
-            for x in 0..dims.0 {
-                for y in 0..dims.1 {
-                    fsd.segs[(axis,x,y)] = LineBoundary::compute(c1[x], c2[y], c2[y+1], eps);
-                    // Sanity check by the relation on the existence of a cornerpoint in the FSD and the curve points being within eps distance.
-                    if let Some(LineBoundary { a, b }) = fsd.segs[(axis,x,y)] {
-                    //     println!("a {:?}", a); 
-                    //     println!("b {:?}", b); 
-                    //     println!("x {:?}", x); 
-                    //     println!("y {:?}", y); 
-                    //     println!("c1[x] {:?}", c1[x]); 
-                    //     println!("c2[y] {:?}", c2[y]); 
-                    //     println!("c2[y+1] {:?}", c2[y+1]); 
-                    //     println!("eps {:?}", eps); 
-                    //     println!("c1[x].distance(c2[y]) {:?}", c1[x].distance(c2[y])); 
-                    //     println!("c1[x].distance(c2[y+1]) {:?}", c1[x].distance(c2[y+1])); 
-                        if a == 0. {
-                            assert!(c1[x].distance(c2[y]) <= eps);
-                        }
-                        if c1[x].distance(c2[y]) < eps {
-                            assert!(a == 0.);
-                        }
-                        if b == 1. {
-                            assert!(c1[x].distance(c2[y+1]) <= eps);
-                        }
-                        if c1[x].distance(c2[y+1]) <= eps {
-                            assert!(b == 1.);
-                        }
-                    } else {
-                    //     println!("c1[x].distance(c2[y]) {:?}", c1[x].distance(c2[y])); 
-                        assert!(c1[x].distance(c2[y])   >= eps - 5.*0.0001);
-                    //     println!("c1[x].distance(c2[y+1]) {:?}", c1[x].distance(c2[y+1])); 
-                        assert!(c1[x].distance(c2[y+1]) >= eps - 5.*0.0001);
-                    }
-                }
+            for x in 0..fsd.dims[axis].0 {
+                fsd.assign_line(axis, x, FSD::compute_line(ps, qs, axis, x, eps, predicates));
             }
         }
 
         // Constructing corners.
         for i in 0..n {
             for j in 0..m {
-                fsd.corners[(i,j)] = ps[i].distance(qs[j]) < eps;
+                fsd.corners[(i,j)] = corner(ps[i], qs[j], eps, predicates);
             }
         }
 
         fsd
     }
 
+    /// Cell boundaries (with their error bounds) on line x of axis, see [`FSD::compute`].
+    fn compute_line<P: Point>(ps: &[P], qs: &[P], axis: usize, x: usize, eps: f64, predicates: Predicates) -> Vec<(OptLineBoundary<F>, F)> {
+        let (c1, c2) = [(ps, qs), (qs, ps)][axis];
+        // Rounding only adds to the error bound for scalars narrower than f64.
+        let rounding = if F::EPSILON.to_f64() > f64::EPSILON { F::EPSILON.to_f64() } else { 0. };
+        // A whole column at once (vectorized with the `simd` feature).
+        let boundaries = match predicates {
+            Predicates::Float => c1[x].free_boundaries(c2, eps),
+            Predicates::Exact => (0..c2.len() - 1).map(|y| exact_boundary(c1[x], c2[y], c2[y+1], eps)).collect(),
+        };
+        boundaries.into_iter().enumerate().map(|(y, boundary)| {
+            (boundary.map(LineBoundary::from_f64), F::from_f64(LineBoundary::compute_error(c1[x], c2[y], c2[y+1], eps) + rounding))
+        }).collect()
+    }
+
+    /// Store the cell boundaries of line x of axis.
+    fn assign_line(&mut self, axis: usize, x: usize, line: Vec<(OptLineBoundary<F>, F)>) {
+        for (y, (boundary, err)) in line.into_iter().enumerate() {
+            self.segs[(axis,x,y)] = boundary;
+            self.errs[(axis,x,y)] = err;
+        }
+    }
+
     /// Compute reachable space diagram out of a free space diagram.
     pub fn to_rsd(&self) -> Self {
+        self.to_rsd_with(MatchMode::SubcurveOfQ)
+    }
+
+    /// Compute reachable space diagram out of a free space diagram, seeding the boundaries the match mode may start at.
+    pub fn to_rsd_with(&self, mode: MatchMode) -> Self {
+        self.propagate(self.seed(mode))
+    }
+
+    /// Compute reachable space diagram for weak Fréchet matching, where the path through the free space may walk back and forth along either curve.
+    ///
+    /// Free space within a cell is convex, so reachability becomes connectivity: a free interval is reachable entirely once any boundary of a cell it borders is.
+    pub fn to_weak_rsd_with(&self, mode: MatchMode) -> Self {
+        let fsd = self;
+        let mut rsd = fsd.seed(mode);
+        rsd.weak = true;
+        rsd.errs.assign(&fsd.errs);
+
+        // Breadth-first search over the free intervals, starting from the initiated ones.
+        let mut queue: VecDeque<_> = rsd.segs.indexed_iter().filter(|(_, b)| b.is_some()).map(|(seg, _)| seg).collect();
+        for &seg in &queue {
+            rsd.segs[seg] = fsd.segs[seg];
+        }
+        while let Some(seg) = queue.pop_front() {
+            for next in fsd.weak_neighbours(seg) {
+                if rsd.segs[next].is_none() {
+                    rsd.segs[next] = fsd.segs[next];
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        rsd
+    }
+
+    /// Boundaries connected to the boundary seg in this diagram, either through a cell they both border or through a cornerpoint they share along their line.
+    fn weak_neighbours(&self, seg: (usize, usize, usize)) -> Vec<(usize, usize, usize)> {
+        let (axis, x, y) = seg;
+        let (w, h) = self.dims[axis];
+        let Some(LineBoundary { a, b }) = self.segs[seg] else { return vec![] };
+        let mut result = vec![];
+        // Cells on either side of the boundary.
+        for u in [x.checked_sub(1), Some(x)].into_iter().flatten().filter(|&u| u + 1 < w) {
+            result.extend([(axis, u, y), (axis, u + 1, y), (1 - axis, y, u), (1 - axis, y + 1, u)]);
+        }
+        // Previous and next boundary along the line.
+        if a == F::ZERO && y > 0 && matches!(self.segs[(axis, x, y - 1)], Some(LineBoundary { b: b_, .. }) if b_ == F::ONE) {
+            result.push((axis, x, y - 1));
+        }
+        if b == F::ONE && y + 1 < h && matches!(self.segs[(axis, x, y + 1)], Some(LineBoundary { a: a_, .. }) if a_ == F::ZERO) {
+            result.push((axis, x, y + 1));
+        }
+        result.retain(|&next| next != seg && self.segs[next].is_some());
+        result
+    }
+
+    /// Walk from a boundary the match mode may start at to the (reachable) position end, through the reachable boundaries of this weak RSD.
+    ///
+    /// Consecutive steps lie on a common boundary or in a common cell, so the straight line between them is free.
+    fn weak_steps_to(&self, end: FSDPosition) -> Vec<(f64, f64)> {
+        let rsd = self;
+        // Offset on the boundary seg a matching may start at (if any).
+        let start = |(axis, x, y): (usize, usize, usize)| {
+            let LineBoundary { a, .. } = rsd.boundary((axis, x, y))?;
+            match rsd.mode {
+                MatchMode::Full | MatchMode::PrefixOfQ => (x == 0 && y == 0 && a == 0.).then_some(0.),
+                MatchMode::SuffixOfQ | MatchMode::SubcurveOfQ => (axis == 0 && x == 0).then_some(a),
+                MatchMode::SubcurveOfBoth => (x == 0).then_some(a),
+            }
+        };
+
+        // Breadth-first search backwards, every reachable boundary is connected to a start.
+        let last = position_to_seg(end);
+        let mut next_of = Array3::from_elem(rsd.segs.dim(), None);
+        let mut queue = VecDeque::from([last]);
+        let mut first = None;
+        while let Some(seg) = queue.pop_front() {
+            if start(seg).is_some() {
+                first = Some(seg);
+                break;
+            }
+            for prev in rsd.weak_neighbours(seg) {
+                if next_of[prev].is_none() && prev != last {
+                    next_of[prev] = Some(seg);
+                    queue.push_back(prev);
+                }
+            }
+        }
+        let mut seg = first.expect("Reachable boundaries should connect to a start boundary.");
+
+        // Walk forwards, entering every boundary at the start of its interval (passing through the cornerpoint when moving along a line).
+        let (axis, x, y) = seg;
+        let mut positions = vec![(axis, x, y, start(seg).unwrap())];
+        while let Some(next) = next_of[seg] {
+            let (axis, x, y) = seg;
+            if next.0 == axis && next.1 == x {
+                positions.push((axis, x, y, if next.2 > y { 1. } else { 0. }));
+            }
+            positions.push((next.0, next.1, next.2, rsd.boundary(next).unwrap().a));
+            seg = next;
+        }
+        positions.push(end);
+
+        let mut steps: Vec<(f64, f64)> = positions.into_iter().map(position_to_ij).collect();
+        steps.dedup();
+        steps
+    }
+
+    /// Stream the reachable intervals column by column (along ps), propagating reachability lazily.
+    /// Collecting all frontiers yields the same intervals as [`FSD::to_rsd`], but iteration may be stopped early.
+    pub fn frontiers(&self) -> Frontiers<'_, F> {
+        self.frontiers_with(MatchMode::SubcurveOfQ)
+    }
+
+    /// Like [`FSD::frontiers`], seeding the boundaries the match mode may start at.
+    pub fn frontiers_with(&self, mode: MatchMode) -> Frontiers<'_, F> {
+        let mut rsd = self.seed(mode);
+        rsd.errs.assign(&self.errs);
+        Frontiers { fsd: self, rsd, column: 0 }
+    }
+
+    /// Empty RSD with the boundaries the match mode may start at initiated.
+    fn seed(&self, mode: MatchMode) -> FSD<F> {
         let fsd = self;
-        let n = fsd.n;
-        let m = fsd.m;
         assert!(!fsd.is_rsd); // Sanity check: Check it is already an RSD.
-        let mut rsd = FSD::empty(n, m);
+        let mut rsd = FSD::empty(fsd.n, fsd.m);
         rsd.is_rsd = true;
+        rsd.mode = mode;
 
-        // Initiate whole left FSD border (we seek partial curve).
-        rsd.segs.slice_mut(s![0, 0, ..]).assign(&fsd.segs.slice(s![0, 0, ..]));
+        if matches!(mode, MatchMode::Full | MatchMode::PrefixOfQ) {
+            // Initiate boundaries at the bottom-left cornerpoint only (propagation walks along the borders from there).
+            for axis in 0..2 {
+                if let Some(LineBoundary { a, b }) = fsd.segs[(axis, 0, 0)] && a == F::ZERO {
+                    rsd.segs[(axis, 0, 0)] = Some(LineBoundary { a, b });
+                }
+            }
+        } else {
+            // Initiate whole left FSD border (we seek partial curve).
+            rsd.segs.slice_mut(s![0, 0, ..]).assign(&fsd.segs.slice(s![0, 0, ..]));
+
+            // Initiate first horizontal RSD boundary of every row starting at a free left cornerpoint (since neither prev, para, nor orth may exist).
+            for j in 0..fsd.m {
+                if let Some(LineBoundary { a, b }) = fsd.segs[(1, j, 0)] {
+                    if a == F::ZERO {
+                        rsd.segs[(1, j, 0)] = Some(LineBoundary { a, b });
+                    }
+                }
+            }
+        }
 
-        // Initiate first lower-left horizontal RSD border (since neither prev, para, nor orth exists).
-        if let Some(LineBoundary { a, b }) = fsd.segs[(1, 0, 0)] {
-            if a == 0. {
-                rsd.segs[(1, 0, 0)] = Some(LineBoundary { a, b });
+        if mode == MatchMode::SubcurveOfBoth {
+            // Likewise initiate whole bottom FSD border and first vertical RSD boundary of every column starting at a free bottom cornerpoint.
+            rsd.segs.slice_mut(s![1, 0, ..]).assign(&fsd.segs.slice(s![1, 0, ..]));
+            for i in 0..fsd.n {
+                if let Some(LineBoundary { a, b }) = fsd.segs[(0, i, 0)] && a == F::ZERO {
+                    rsd.segs[(0, i, 0)] = Some(LineBoundary { a, b });
+                }
             }
         }
 
+        rsd
+    }
+
+    /// Compute reachable space diagram when only starting from the left boundary interval at y (and the intervals above it connects to).
+    pub fn to_rsd_from(&self, y: usize) -> Self {
+        let fsd = self;
+        assert!(!fsd.is_rsd); // Sanity check: Check it is already an RSD.
+        let mut rsd = FSD::empty(fsd.n, fsd.m);
+        rsd.is_rsd = true;
+
+        rsd.segs[(0, 0, y)] = fsd.segs[(0, 0, y)];
+        // First horizontal RSD boundary of row y is only reachable when starting at its left cornerpoint.
+        if let Some(LineBoundary { a: a0, b: _ }) = fsd.segs[(0, 0, y)] && let Some(LineBoundary { a, b }) = fsd.segs[(1, y, 0)] && a0 == F::ZERO && a == F::ZERO {
+            rsd.segs[(1, y, 0)] = Some(LineBoundary { a, b });
+        }
+
+        fsd.propagate(rsd)
+    }
+
+    /// Propagate reachability through the FSD, starting from the (initiated) left and bottom borders of the RSD.
+    fn propagate(&self, mut rsd: FSD<F>) -> FSD<F> {
+        let fsd = self;
+        let n = fsd.n;
+        let m = fsd.m;
+        rsd.errs.assign(&fsd.errs);
+
         // Walk all cells left to right, bottom to top.
         for j in 0..m {
             for i in 0..n {
                 for axis in 0..2 {
                     let (x,y) = [(i,j),(j,i)][axis];
-                    let curr = (axis, x, y); // current.
-                    let opt_prev = if y > 0 { Some((axis  , x  , y-1)) } else { None }; // previous.
-                    let opt_para = if x > 0 { Some((axis  , x-1, y  )) } else { None }; // parallel.
-                    let opt_orth = if x > 0 { Some((1-axis, y  , x-1)) } else { None }; // orthogonal.
-                    if let Some(orth) = opt_orth {
-                        if rsd.segs[orth].is_some() {
-                            rsd.segs[curr] = fsd.segs[curr];
-                        }
-                    } 
-                    if let Some(para) = opt_para {
-                        // Custom intersect.
-                        if let Some(LineBoundary { a: a_, b: _b }) = rsd.segs[para] {
-                            if let Some(LineBoundary { a, b }) = fsd.segs[curr] {
-                                rsd.segs[curr] = LineBoundary::union(rsd.segs[curr], LineBoundary::new(a.max(a_), b));
-                            }
-                        }
-                    } 
-                    if let Some(prev) = opt_prev { 
-                        if let Some(LineBoundary { a: _a, b: b_ }) = rsd.segs[prev] {
-                            if let Some(LineBoundary { a, b }) = fsd.segs[curr] {
-                                if b_ == 1. && a == 0. {
-                                    rsd.segs[curr] = LineBoundary::union(rsd.segs[curr], LineBoundary::new(0., b));
-                                }
-                            }
-                        }
-                    }
+                    fsd.propagate_at(&mut rsd, (axis, x, y));
                 }
             }
         }
@@ -170,12 +572,163 @@ impl FSD {
         rsd
     }
 
+    /// Propagate reachability onto a single cell boundary, its previous, parallel and orthogonal boundaries should be propagated already.
+    fn propagate_at(&self, rsd: &mut FSD<F>, curr: (usize, usize, usize)) {
+        let (seg, err, slack) = self.reachable_at(rsd, curr);
+        (rsd.segs[curr], rsd.errs[curr], rsd.slack) = (seg, err, rsd.slack.min(slack));
+    }
+
+    /// Reachable part of a single cell boundary with its error bound and slack (see [`FSD::propagate_at`]), leaving the RSD untouched.
+    fn reachable_at(&self, rsd: &FSD<F>, curr: (usize, usize, usize)) -> (OptLineBoundary<F>, F, f64) {
+        let fsd = self;
+        let (axis, x, y) = curr;
+        let (mut seg, mut err, mut slack) = (rsd.segs[curr], rsd.errs[curr], f64::INFINITY);
+        let opt_prev = if y > 0 { Some((axis  , x  , y-1)) } else { None }; // previous.
+        let opt_para = if x > 0 { Some((axis  , x-1, y  )) } else { None }; // parallel.
+        let opt_orth = if x > 0 { Some((1-axis, y  , x-1)) } else { None }; // orthogonal.
+        let Some(LineBoundary { a, b }) = fsd.segs[curr] else { return (seg, err, slack) };
+        if let Some(orth) = opt_orth {
+            if let Some(LineBoundary { a: a_, b: b_ }) = rsd.segs[orth] {
+                seg = fsd.segs[curr];
+                err = err.max(fsd.errs[curr]);
+                // The transition vanishes if the orthogonal interval may be empty.
+                slack = slack.min((b_ - a_).to_f64() - 2. * rsd.errs[orth].to_f64());
+            }
+        } 
+        if let Some(para) = opt_para {
+            // Custom intersect.
+            if let Some(LineBoundary { a: a_, b: _b }) = rsd.segs[para] {
+                seg = LineBoundary::union(seg, LineBoundary::new(a.max(a_), b));
+                // Track how close the intersection was to flipping.
+                let err_ = fsd.errs[curr].max(rsd.errs[para]);
+                slack = slack.min((b - a.max(a_)).abs().to_f64() - 2. * err_.to_f64());
+                if a_ > a { err = err.max(err_); }
+            }
+        } 
+        if let Some(prev) = opt_prev { 
+            if let Some(LineBoundary { a: a_, b: b_ }) = rsd.segs[prev] {
+                if b_ == F::ONE && a == F::ZERO {
+                    seg = LineBoundary::union(seg, LineBoundary::new(F::ZERO, b));
+                    err = err.max(fsd.errs[curr]);
+                    // Passing the cornerpoint needs both intervals to touch it, which they may not if either may be empty.
+                    let err_ = fsd.errs[curr].max(rsd.errs[prev]);
+                    slack = slack.min((b_ - a_).min(b).to_f64() - 2. * err_.to_f64());
+                }
+            }
+        }
+        (seg, err, slack)
+    }
+
     /// Check for a partial curve match. 
     /// 
     /// Note: Should be appied to a reachability-space diagram.
     pub fn check_pcm(&self) -> bool {
         let rsd = if self.is_rsd { self } else { &self.to_rsd() };
-        rsd.segs.slice(s![0,rsd.n-1,..]).iter().any(|b| b.is_some())
+        rsd.end_position().is_some()
+    }
+
+    /// Check for a weak partial curve match (see [`FSD::to_weak_rsd_with`]).
+    ///
+    /// Note: Should be applied to a free-space diagram (or a weak reachability-space diagram).
+    pub fn check_weak_pcm(&self) -> bool {
+        let rsd = if self.is_rsd && self.weak { self } else { &self.to_weak_rsd_with(MatchMode::SubcurveOfQ) };
+        rsd.end_position().is_some()
+    }
+
+    /// Check for a (full) Fréchet match, both curves matched end-to-end.
+    /// 
+    /// Note: Should be applied to a free-space diagram (or a reachability-space diagram in full match mode).
+    pub fn check_frechet(&self) -> bool {
+        let rsd = if self.is_rsd && !self.weak && self.mode == MatchMode::Full { self } else { &self.to_rsd_with(MatchMode::Full) };
+        rsd.end_position().is_some()
+    }
+
+    /// Check the diagram is well-formed: every boundary interval is non-empty and lies on the unit interval, only boundaries within `dims` are set,
+    /// and (for a FSD) a boundary starts/ends at a cornerpoint exactly when that cornerpoint is free.
+    pub fn validate(&self) -> Result<(), PcmError> {
+        for ((axis, x, y), opt_boundary) in self.segs.indexed_iter() {
+            let (w, h) = self.dims[axis];
+            if let Some(LineBoundary { a, b }) = *opt_boundary {
+                if x >= w || y >= h {
+                    return Err(PcmError::InconsistentBoundary { cell: (axis, x, y), detail: "exists outside of the diagram.".to_string() });
+                }
+                if !(F::ZERO <= a && a < b && b <= F::ONE) {
+                    return Err(PcmError::InconsistentBoundary { cell: (axis, x, y), detail: format!("invalid interval [{a}, {b}].") });
+                }
+            }
+        }
+
+        if self.is_rsd {
+            return if self.corners.iter().any(|&c| c) { Err(PcmError::InconsistentDiagram("Cornerpoints are not tracked on a RSD.".to_string())) } else { Ok(()) };
+        }
+
+        for j in 0..self.m {
+            for i in 0..self.n {
+                let has_corner = self.corners[(i, j)];
+                for axis in 0..2 {
+                    let (_, h) = self.dims[axis];
+                    let (x, y) = [(i, j), (j, i)][axis];
+                    // Boundary starting at the cornerpoint.
+                    if y < h {
+                        let curr = (axis, x, y);
+                        match self.segs[curr] {
+                            Some(LineBoundary { a, .. }) if !has_corner && a == F::ZERO => return Err(PcmError::InconsistentBoundary { cell: curr, detail: format!("start exists while no corner at ({i},{j}).") }),
+                            Some(LineBoundary { a, .. }) if has_corner && a != F::ZERO => return Err(PcmError::InconsistentBoundary { cell: curr, detail: format!("start does not exist while corner at ({i},{j}).") }),
+                            None if has_corner => return Err(PcmError::InconsistentBoundary { cell: curr, detail: format!("does not exist while corner at ({i},{j}).") }),
+                            _ => {}
+                        }
+                    }
+                    // Boundary ending at the cornerpoint.
+                    if y > 0 {
+                        let prev = (axis, x, y - 1);
+                        match self.segs[prev] {
+                            Some(LineBoundary { b, .. }) if !has_corner && b == F::ONE => return Err(PcmError::InconsistentBoundary { cell: prev, detail: format!("end exists while no corner at ({i},{j}).") }),
+                            Some(LineBoundary { b, .. }) if has_corner && b != F::ONE => return Err(PcmError::InconsistentBoundary { cell: prev, detail: format!("end does not exist while corner at ({i},{j}).") }),
+                            None if has_corner => return Err(PcmError::InconsistentBoundary { cell: prev, detail: format!("does not exist while corner at ({i},{j}).") }),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check this RSD is consistent with the FSD it is computed from (by [`FSD::to_rsd_with`]):
+    /// every reachable interval lies within its free interval, and propagating reachability anew yields the same intervals.
+    pub fn validate_reachability(&self, fsd: &FSD<F>) -> Result<(), PcmError> {
+        if !self.is_rsd || fsd.is_rsd {
+            return Err(PcmError::InconsistentDiagram("Expected a RSD and the FSD it is computed from.".to_string()));
+        }
+        if (self.n, self.m) != (fsd.n, fsd.m) {
+            return Err(PcmError::InconsistentDiagram(format!("RSD of {}x{} does not match FSD of {}x{}.", self.n, self.m, fsd.n, fsd.m)));
+        }
+        self.validate()?;
+
+        let interval = |opt_boundary: &OptLineBoundary<F>| opt_boundary.map(|LineBoundary { a, b }| (a, b));
+        let expected = if self.weak { fsd.to_weak_rsd_with(self.mode) } else { fsd.to_rsd_with(self.mode) };
+        for (curr, opt_boundary) in self.segs.indexed_iter() {
+            match (interval(opt_boundary), interval(&fsd.segs[curr])) {
+                (Some(_), None) => return Err(PcmError::InconsistentBoundary { cell: curr, detail: "reachable while not free.".to_string() }),
+                (Some((a, b)), Some((a_, b_))) if a < a_ || b > b_ => return Err(PcmError::InconsistentBoundary { cell: curr, detail: format!("reachable interval [{a}, {b}] exceeds free interval [{a_}, {b_}].") }),
+                _ => {}
+            }
+            if interval(opt_boundary) != interval(&expected.segs[curr]) {
+                return Err(PcmError::InconsistentBoundary { cell: curr, detail: format!("reachable interval {:?} while propagation yields {:?}.", interval(opt_boundary), interval(&expected.segs[curr])) });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Numerical robustness of the partial curve match decision.
+    /// 
+    /// Positive values are the margin by which every interval comparison exceeded its floating point error bound.
+    /// Note: Should be appied to a reachability-space diagram.
+    pub fn robustness(&self) -> f64 {
+        let rsd = if self.is_rsd { self } else { &self.to_rsd() };
+        rsd.slack
     }
 
     pub fn is_mostright_boundary_nonempty(&self) -> bool {
@@ -194,120 +747,193 @@ impl FSD {
         self.segs.slice(s![1, 0, ..]).iter().any(|b| b.is_some())
     }
 
-    pub fn bottom(&self) -> ArrayBase<ndarray::ViewRepr<&Option<LineBoundary>>, Dim<[usize; 1]>> {
+    pub fn bottom(&self) -> ArrayBase<ndarray::ViewRepr<&OptLineBoundary<F>>, Dim<[usize; 1]>> {
         self.segs.slice(s![1, 0, ..])
     }
 
+    /// Vertical cell boundaries, indexed by (x, y) with shape `(n, m-1)`.
+    #[cfg(feature = "ndarray")]
+    pub fn verticals(&self) -> ArrayView2<'_, OptLineBoundary<F>> {
+        let (w, h) = self.dims[0];
+        self.segs.slice(s![0, ..w, ..h])
+    }
+
+    /// Horizontal cell boundaries, indexed by (y, x) with shape `(m, n-1)`.
+    #[cfg(feature = "ndarray")]
+    pub fn horizontals(&self) -> ArrayView2<'_, OptLineBoundary<F>> {
+        let (w, h) = self.dims[1];
+        self.segs.slice(s![1, ..w, ..h])
+    }
+
+    /// Corner points, indexed by (i, j) with shape `(n, m)`.
+    #[cfg(feature = "ndarray")]
+    pub fn corners(&self) -> ArrayView2<'_, bool> {
+        self.corners.view()
+    }
+
     /// Compute steps to walk along curves for partial matching solution.
     /// 
+    /// On a weak RSD the steps may walk backwards along either curve (check them with [`crate::validate_weak_steps`]).
     /// Note: Should be appied to a reachability-space diagram.
     pub fn pcm_steps(&self) -> Option<Vec<(f64,f64)>> {
 
         let rsd = if self.is_rsd { self } else { &self.to_rsd() };
-        let n = rsd.n;
-        // let m = rsd.m;
 
-        let mut steps = vec![];
-        let mut curr = (0, 0, 0, 0.);
-
-        // Seek lowest non-empty boundary on right side of the RSD.
+        // Seek end position (lowest non-empty boundary on right side of the RSD for a partial curve match).
         // (Basically performs PCM existence check as well.)
-        let mut found = false;
-        for (_j, lb) in rsd.segs.slice(s![0,n-1,..]).iter().enumerate() {
-            if let Some(LineBoundary { a, b: _ }) = lb {
-                let x = n - 1;
-                let y = _j;
-                let off = *a;
-                curr = (0, x, y, off);
-                found = true;
-                break;
-            }
+        let curr = rsd.end_position()?;
+        if rsd.weak {
+            return Some(rsd.weak_steps_to(curr));
         }
-        if !found { return None }
+        let mut steps = rsd.steps_to(curr);
 
-        // Final step is found.
-        steps.push(position_to_ij(curr));
+        // The walk may arrive higher on the left boundary, continue down along it to the bottom-left cornerpoint.
+        if matches!(rsd.mode, MatchMode::Full | MatchMode::PrefixOfQ) && steps[0] != (0., 0.) {
+            steps.insert(0, (0., 0.));
+        }
 
-        // Walk backwards. (Walk greedily, it should not matter).
-        while !(position_on_left_boundary(curr)) { // Walk while we're not at the start position of P.
+        Some(steps)
+    }
 
-            // println!("curr: {curr:?}");
-            if rsd.segs[position_to_seg(curr)].is_none() {
-                // Sanity check:
-                panic!("Current segment while walking ({curr:?}) should not be empty.");
+    /// Render the diagram as a character grid (for debugging purposes).
+    /// 
+    /// Corners are drawn as `+` (free) or `.` (blocked), horizontal boundaries as `=` (fully free), `-` (partially free) or blank,
+    /// and vertical boundaries as `|` (fully free), `:` (partially free) or blank.
+    /// If steps are provided, the cells the path walks through are marked with `*`.
+    pub fn to_ascii(&self, opt_steps: Option<&[(f64, f64)]>) -> String {
+        let n = self.n;
+        let m = self.m;
+
+        // Mark cells the path walks through.
+        let mut visited = Array2::from_shape_simple_fn([n.max(2)-1, m.max(2)-1], || false);
+        if let Some(steps) = opt_steps {
+            for ((x1, y1), (x2, y2)) in steps.iter().zip(&steps[1..]) {
+                let i = ((0.5 * (x1 + x2)).floor() as usize).min(n.max(2) - 2);
+                let j = ((0.5 * (y1 + y2)).floor() as usize).min(m.max(2) - 2);
+                visited[(i, j)] = true;
             }
+        }
 
-            // Try to walk backwards.
-            let (axis, x, y, off) = curr;
-            let mut states = vec![curr];
-            if off == 0. { // In this case we can decide to walk both directions.
-                states.push((1-axis, y, x, off))
+        let symbol = |opt_lb: OptLineBoundary<F>, full: char, partial: char| {
+            match opt_lb {
+                Some(LineBoundary { a, b }) if a == F::ZERO && b == F::ONE => full,
+                Some(_) => partial,
+                None => ' '
             }
+        };
 
-            let mut next = None;
-            for (axis, x, y, off) in states {
-                if next.is_none() {
-                    let opt_prev = if off == 0. && y > 0 { 
-                        Some((axis, x, y-1)) 
-                    } else { None }; // previous.
-                    let opt_para = if off >  0. && x > 0 { 
-                        Some((axis, x-1, y)) 
-                    } else { None }; // parallel.
-                    let opt_orth = if x > 0 { 
-                        Some((1-axis, y, x-1))
-                    } else { None }; // orthogonal.
+        let mut out = String::new();
+        for j in (0..m).rev() {
+            // Row of corners with horizontal boundaries in between.
+            for i in 0..n {
+                out.push(if self.corners[(i, j)] { '+' } else { '.' });
+                if i < n - 1 {
+                    let c = symbol(self.segs[(1, j, i)], '=', '-');
+                    out.extend([c, c, c]);
+                }
+            }
+            out.push('\n');
+            if j == 0 { break; }
+            // Row of vertical boundaries with cell interiors in between.
+            for i in 0..n {
+                out.push(symbol(self.segs[(0, i, j-1)], '|', ':'));
+                if i < n - 1 {
+                    out.push_str(if visited[(i, j-1)] { " * " } else { "   " });
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
 
-                    // Attempt to walk to previous.
-                    if let Some(prev) = opt_prev {
-                        if let Some(LineBoundary { a: a_, b: b_ }) = rsd.segs[prev] {
-                            if off == 0. && b_ == 1. {
-                                // println!("prev");
-                                let (axis, x, y) = prev;
-                                let off = a_;
-                                next = Some((axis, x, y, off));
-                            }
-                        }
-                    }
+}
 
-                    // Attempt to walk to orthogonal.
-                    if next.is_none() && let Some(orth) = opt_orth {
-                        if let Some(LineBoundary { a: a_, b: b_ }) = rsd.segs[orth] {
-                            if (off == 0. && b_ == 1.) || off > 0. {
-                                // println!("orth");
-                                let (axis, x, y) = orth;
-                                let off = a_;
-                                next = Some((axis, x, y, off));
-                            } 
-                        }
-                    } 
 
-                    // Attempt to walk to parallel.
-                    if next.is_none() && let Some(para) = opt_para {
-                        if let Some(LineBoundary { a: a_, b: _ }) = rsd.segs[para] {
-                            if off >= a_ {
-                                // println!("para");
-                                let (axis, x, y) = para;
-                                let off = a_;
-                                next = Some((axis, x, y, off));
-                            }
-                        }
-                    }
-                }
-            }
+impl<F: Float + Send + Sync> FSD<F> {
 
-            if next.is_none() {
-                // Sanity check:
-                panic!("Should find next step in backwards walk at {curr:?}.\n{rsd:?}");
+    /// Like [`FSD::compute`], computing the cell boundaries per line and the corners per row in parallel (with the `parallel` feature).
+    pub fn compute_parallel<P: Point + Sync>(ps: &[P], qs: &[P], eps: f64, predicates: Predicates) -> FSD<F> {
+        let (n, m) = (ps.len(), qs.len());
+        let mut fsd = FSD::empty(n, m);
+        let lines: Vec<(usize, usize)> = (0..2).flat_map(|axis| (0..fsd.dims[axis].0).map(move |x| (axis, x))).collect();
+        let computed = parallel::map(lines.clone(), |(axis, x)| FSD::compute_line(ps, qs, axis, x, eps, predicates));
+        for ((axis, x), line) in lines.into_iter().zip(computed) {
+            fsd.assign_line(axis, x, line);
+        }
+        let rows = parallel::map((0..n).collect(), |i| qs.iter().map(|&q| corner(ps[i], q, eps, predicates)).collect::<Vec<bool>>());
+        for (i, row) in rows.into_iter().enumerate() {
+            for (j, free) in row.into_iter().enumerate() {
+                fsd.corners[(i,j)] = free;
             }
-            // println!("next: {next:?}");
-            curr = next.unwrap();
-            steps.push(position_to_ij(curr));
-
         }
+        fsd
+    }
 
-        steps.reverse();
-        Some(steps)
+    /// Like [`FSD::to_rsd`], propagating reachability in parallel (with the `parallel` feature), with identical results.
+    pub fn to_rsd_parallel(&self) -> Self {
+        self.to_rsd_parallel_with(MatchMode::SubcurveOfQ)
+    }
+
+    /// Like [`FSD::to_rsd_with`], propagating reachability in parallel (with the `parallel` feature), with identical results.
+    ///
+    /// The boundaries of cell (i, j) only depend on those of cells (i-1, j) and (i, j-1), so the cells of an anti-diagonal are propagated at once.
+    pub fn to_rsd_parallel_with(&self, mode: MatchMode) -> Self {
+        let fsd = self;
+        let (n, m) = (fsd.n, fsd.m);
+        let mut rsd = fsd.seed(mode);
+        rsd.errs.assign(&fsd.errs);
+        for d in 0..n + m - 1 {
+            let cells: Vec<(usize, usize)> = (d.saturating_sub(m - 1)..=d.min(n - 1)).map(|i| (i, d - i)).collect();
+            let reachable = parallel::map(cells, |(i, j)| [(0, i, j), (1, j, i)].map(|curr| (curr, fsd.reachable_at(&rsd, curr))));
+            for (curr, (seg, err, slack)) in reachable.into_iter().flatten() {
+                (rsd.segs[curr], rsd.errs[curr], rsd.slack) = (seg, err, rsd.slack.min(slack));
+            }
+        }
+        rsd
     }
+}
+
+/// Reachable intervals of a single column of the RSD, yielded by [`FSD::frontiers`].
+#[derive(Debug, Clone)]
+pub struct Frontier<F: Float = f64> {
+    /// Point index i on ps.
+    pub column: usize,
+    /// Reachable intervals on the vertical boundaries at i (one per segment of qs).
+    pub verticals: Vec<OptLineBoundary<F>>,
+    /// Reachable intervals on the horizontal boundaries between i and i+1 (one per point of qs, none for the last column).
+    pub horizontals: Vec<OptLineBoundary<F>>,
+}
 
+/// Iterator over the reachability frontier, see [`FSD::frontiers`].
+pub struct Frontiers<'a, F: Float = f64> {
+    fsd: &'a FSD<F>,
+    rsd: FSD<F>,
+    column: usize,
 }
+impl<F: Float> Frontiers<'_, F> {
 
+    /// Smallest margin of the interval comparisons made up to the current column (see [`FSD::slack`]).
+    pub fn slack(&self) -> f64 {
+        self.rsd.slack
+    }
+}
+impl<F: Float> Iterator for Frontiers<'_, F> {
+    type Item = Frontier<F>;
+
+    fn next(&mut self) -> Option<Frontier<F>> {
+        let (n, m) = (self.fsd.n, self.fsd.m);
+        let i = self.column;
+        if i >= n { return None; }
+        // Column order satisfies the propagation dependencies as well: every boundary only depends on boundaries left of or below it.
+        for j in 0..m {
+            for axis in 0..2 {
+                let (x, y) = [(i, j), (j, i)][axis];
+                self.fsd.propagate_at(&mut self.rsd, (axis, x, y));
+            }
+        }
+        self.column += 1;
+        let verticals = (0..m-1).map(|j| self.rsd.segs[(0, i, j)]).collect();
+        let horizontals = if i < n - 1 { (0..m).map(|j| self.rsd.segs[(1, j, i)]).collect() } else { vec![] };
+        Some(Frontier { column: i, verticals, horizontals })
+    }
+}
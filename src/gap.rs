@@ -0,0 +1,84 @@
+use crate::{curve::{validate_curve, validate_eps, Curve}, error::PcmError, grid::{Grid, PathRules, Sample}};
+
+/// Matching of ps against a subcurve of qs which may leave sections of either curve unmatched.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GapMatching {
+    /// Total penalty of the unmatched sections.
+    pub penalty: f64,
+    /// Positions (parameter on ps, parameter on qs) walked along.
+    pub steps: Vec<(f64, f64)>,
+    /// Unmatched sections as (first, last) position, walking horizontally skips part of ps and walking vertically part of qs.
+    pub gaps: Vec<((f64, f64), (f64, f64))>,
+}
+
+/// Compute the matching of ps against a subcurve of qs with minimal total gap penalty.
+///
+/// Walking through blocked space (leash length above eps) is only allowed along one of both curves,
+/// costing `penalty_p` per unit of length skipped on ps and `penalty_q` per unit of length skipped on qs (e.g. tunnels or signal dropouts).
+/// Both the start and end of the matching have to lie within eps.
+/// The parameter space is discretized with `resolution` samples per FSD cell (monotone moves right, up, or diagonal),
+/// so the result is an approximation of the continuous matching.
+pub fn gap_matching(ps: &Curve, qs: &Curve, eps: f64, penalty_p: f64, penalty_q: f64, resolution: usize) -> Result<Option<GapMatching>, PcmError> {
+    validate_curve(ps)?;
+    validate_curve(qs)?;
+    validate_eps(eps)?;
+    if !(penalty_p.is_finite() && penalty_p >= 0. && penalty_q.is_finite() && penalty_q >= 0.) {
+        return Err(PcmError::InvalidArgument(format!("Gap penalties should be non-negative and finite, got {penalty_p} and {penalty_q}.")));
+    }
+    let grid = Grid::new(ps, qs, resolution)?;
+    let rules = GapRules { free: grid.free(eps), grid: &grid, penalty_p, penalty_q };
+    let Some((penalty, path)) = grid.best_path(&rules) else { return Ok(None) };
+
+    // Merge consecutive moves into blocked space into gaps (from the last free position up to the next free position).
+    let free = &rules.free;
+    let mut gaps = vec![];
+    let mut opt_gap_start = None;
+    for (k, &(x, y)) in path.iter().enumerate() {
+        if !free[x][y] && opt_gap_start.is_none() {
+            opt_gap_start = Some(k - 1);
+        }
+        if free[x][y] && let Some(start) = opt_gap_start.take() {
+            gaps.push((grid.position(path[start]), grid.position((x, y))));
+        }
+    }
+
+    let steps = path.into_iter().map(|s| grid.position(s)).collect();
+    Ok(Some(GapMatching { penalty, steps, gaps }))
+}
+
+/// Paths starting and ending in free space, moving through blocked space only along one of both curves at a penalty per length skipped.
+struct GapRules<'a> {
+    grid: &'a Grid,
+    free: Vec<Vec<bool>>,
+    penalty_p: f64,
+    penalty_q: f64,
+}
+impl PathRules for GapRules<'_> {
+    type Cost = f64;
+
+    fn start(&self, (x, y): Sample) -> Option<f64> {
+        // Partial match: We may start anywhere (free) on the left boundary.
+        (x == 0 && self.free[x][y]).then_some(0.)
+    }
+
+    fn step(&self, penalty: &f64, (px, py): Sample, (x, y): Sample) -> Option<f64> {
+        // Moves into, within and out of blocked space all skip length, so a gap is charged from its last free sample up to its next free sample.
+        let (grid, free) = (self.grid, self.free[px][py] && self.free[x][y]);
+        match (x - px, y - py) {
+            _ if x == 0 => None,
+            _ if free => Some(*penalty),
+            // Diagonal moves are only allowed within free space.
+            (1, 1) => None,
+            (1, _) => Some(penalty + self.penalty_p * grid.p[px].distance(grid.p[x])),
+            _ => Some(penalty + self.penalty_q * grid.q[py].distance(grid.q[y])),
+        }
+    }
+
+    fn better(&self, c1: &f64, c2: &f64) -> bool {
+        c1 < c2
+    }
+
+    fn end(&self, (x, y): Sample) -> bool {
+        x == self.grid.size().0 - 1 && self.free[x][y]
+    }
+}
@@ -0,0 +1,69 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{curve::{subcurve, Curve}, vector::Vector};
+
+
+/// Random number generator of a seed, so generated curves can be reproduced.
+pub fn seeded(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// Construct curve with n number of random points in [0, field) squared.
+/// Chance of generating points which break general position is sufficiently small to ignore testing.
+pub fn random_curve<R: Rng + ?Sized>(n: usize, field: f64, rng: &mut R) -> Curve {
+    (0..n).map(|_| Vector::new(rng.gen_range(0.0..field), rng.gen_range(0.0..field))).collect()
+}
+
+/// Translate all points of curve c by offset, the Fréchet distance between c and the result is the length of offset.
+pub fn translate_curve(c: &Curve, offset: Vector) -> Curve {
+    c.iter().map(|&p| p + offset).collect()
+}
+
+/// Move every point of curve c by a random offset (uniform over the disk of radius deviation),
+/// so the Fréchet distance between c and the result is at most deviation.
+pub fn perturb_curve<R: Rng + ?Sized>(c: &Curve, deviation: f64, rng: &mut R) -> Curve {
+    c.iter().map(|&p| {
+        let (angle, radius) = (rng.gen_range(0.0..std::f64::consts::TAU), deviation * rng.gen_range(0.0..=1.0_f64).sqrt());
+        p + Vector::new(radius * angle.cos(), radius * angle.sin())
+    }).collect()
+}
+
+/// Curve with n (at least two) points evenly spaced on a circular arc around center, from angle start over angle sweep (radians, counter-clockwise).
+///
+/// Concentric arcs (same angles and number of points) have the difference of their radii as Fréchet distance,
+/// and the outer arc matches the inner one exactly from that threshold on.
+pub fn circular_arc(center: Vector, radius: f64, start: f64, sweep: f64, n: usize) -> Curve {
+    (0..n).map(|i| {
+        let angle = start + sweep * i as f64 / (n - 1) as f64;
+        center + Vector::new(radius * angle.cos(), radius * angle.sin())
+    }).collect()
+}
+
+/// Curve with n points starting at start, every point step further along the x-axis and alternately amplitude above and below start.
+///
+/// Every point lies at distance amplitude of the horizontal segment through start (covering the same x-range),
+/// so their Fréchet distance (and the threshold the zig-zag matches it from) is amplitude.
+pub fn zigzag(start: Vector, step: f64, amplitude: f64, n: usize) -> Curve {
+    (0..n).map(|i| start + Vector::new(step * i as f64, if i % 2 == 0 { amplitude } else { -amplitude })).collect()
+}
+
+/// Pair of curves with a known partial curve match.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedPair {
+    pub ps: Curve,
+    pub qs: Curve,
+    /// Parameter interval on qs ps was taken from.
+    pub interval: (f64, f64),
+    /// Threshold ps matches the subcurve of qs on interval within (an upper bound of its minimal partial matching threshold).
+    pub eps: f64,
+}
+
+/// Random curve qs of n (at least two) points in [0, field) squared, with ps a random subcurve of it perturbed by noise (see [`perturb_curve`]).
+pub fn subcurve_pair<R: Rng + ?Sized>(n: usize, field: f64, noise: f64, rng: &mut R) -> GeneratedPair {
+    let qs = random_curve(n, field, rng);
+    let last = (n - 1) as f64;
+    let t0 = rng.gen_range(0.0..last);
+    let t1 = rng.gen_range(t0..=last);
+    let ps = perturb_curve(&subcurve(&qs, t0, t1), noise, rng);
+    GeneratedPair { ps, qs, interval: (t0, t1), eps: noise }
+}
@@ -0,0 +1,100 @@
+use serde_json::{json, Value};
+
+use crate::{curve::{validate_curve, Curve}, error::PcmError, export::ExportOptions, partial_curve::Matching, vector::Vector};
+
+
+/// Read all curves out of GeoJSON (a FeatureCollection, Feature, or bare geometry).
+///
+/// Every LineString is a curve, every part of a MultiLineString is a separate curve (GeometryCollections are searched recursively).
+/// Other geometries (points, polygons) are skipped, coordinates beyond x and y (e.g. altitude) are ignored.
+pub fn curves_from_geojson(input: &str) -> Result<Vec<Curve>, PcmError> {
+    let value: Value = serde_json::from_str(input).map_err(|e| PcmError::Parse(format!("Invalid GeoJSON: {e}")))?;
+    let mut curves = vec![];
+    collect_curves(&value, &mut curves)?;
+    Ok(curves)
+}
+
+/// Read the single curve out of GeoJSON, see [`curves_from_geojson`].
+pub fn curve_from_geojson(input: &str) -> Result<Curve, PcmError> {
+    let mut curves = curves_from_geojson(input)?;
+    if curves.len() != 1 {
+        return Err(PcmError::Parse(format!("Expected a single curve in GeoJSON, found {}.", curves.len())));
+    }
+    Ok(curves.pop().unwrap())
+}
+
+/// Append the curves within a GeoJSON object.
+fn collect_curves(value: &Value, curves: &mut Vec<Curve>) -> Result<(), PcmError> {
+    match value["type"].as_str() {
+        Some("FeatureCollection") => {
+            let features = value["features"].as_array().ok_or(PcmError::Parse("FeatureCollection without features array.".to_string()))?;
+            for feature in features {
+                collect_curves(feature, curves)?;
+            }
+        },
+        Some("Feature") => if !value["geometry"].is_null() { collect_curves(&value["geometry"], curves)? },
+        Some("GeometryCollection") => {
+            let geometries = value["geometries"].as_array().ok_or(PcmError::Parse("GeometryCollection without geometries array.".to_string()))?;
+            for geometry in geometries {
+                collect_curves(geometry, curves)?;
+            }
+        },
+        Some("LineString") => curves.push(to_curve(&value["coordinates"])?),
+        Some("MultiLineString") => {
+            let parts = value["coordinates"].as_array().ok_or(PcmError::Parse("MultiLineString without coordinates array.".to_string()))?;
+            for part in parts {
+                curves.push(to_curve(part)?);
+            }
+        },
+        Some(_) => {},
+        None => return Err(PcmError::Parse("GeoJSON object without type.".to_string())),
+    }
+    Ok(())
+}
+
+/// Convert an array of positions into a (valid) curve.
+fn to_curve(coordinates: &Value) -> Result<Curve, PcmError> {
+    let positions = coordinates.as_array().ok_or(PcmError::Parse("LineString without coordinates array.".to_string()))?;
+    let curve = positions.iter().map(|position| {
+        match position.as_array().map(|p| p.iter().take(2).map(|v| v.as_f64()).collect::<Vec<_>>()).as_deref() {
+            Some(&[Some(x), Some(y)]) => Ok(Vector::new(x, y)),
+            _ => Err(PcmError::Parse(format!("Invalid position {position}, expected [x, y]."))),
+        }
+    }).collect::<Result<Curve, PcmError>>()?;
+    validate_curve(&curve)?;
+    Ok(curve)
+}
+
+/// Matching of ps against qs (at threshold eps) as GeoJSON FeatureCollection.
+///
+/// Features are the matched subcurve of qs (with eps, the interval on qs and its coverage as properties),
+/// followed by a correspondence segment per step connecting the matched points on ps and qs (with the step and its distance as properties).
+pub fn matching_to_geojson(matching: &Matching, eps: f64, options: &ExportOptions) -> String {
+    let coordinates = |c: &[Vector]| -> Vec<[f64; 2]> { c.iter().map(|p| [options.coordinate(p.x), options.coordinate(p.y)]).collect() };
+    let (start, end) = matching.covered_interval();
+    let mut features = vec![json!({
+        "type": "Feature",
+        "geometry": { "type": "LineString", "coordinates": coordinates(&matching.subcurve()) },
+        "properties": {
+            "kind": "subcurve",
+            "eps": eps,
+            "start": options.step(start),
+            "end": options.step(end),
+            "coverage": matching.coverage(),
+        }
+    })];
+    for &(i, j) in &matching.steps {
+        let (p, q) = matching.points_at((i, j));
+        features.push(json!({
+            "type": "Feature",
+            "geometry": { "type": "LineString", "coordinates": coordinates(&[p, q]) },
+            "properties": {
+                "kind": "correspondence",
+                "i": options.step(i),
+                "j": options.step(j),
+                "distance": options.coordinate(p.distance(q)),
+            }
+        }));
+    }
+    json!({ "type": "FeatureCollection", "features": features }).to_string()
+}
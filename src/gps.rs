@@ -0,0 +1,139 @@
+use crate::{curve::{validate_curve, Curve}, error::PcmError, vector::Vector};
+
+
+/// Mean earth radius (meters) of WGS84.
+pub const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Number of header lines of a GeoLife `.plt` file.
+const PLT_HEADER: usize = 6;
+
+/// Read all track segments (and routes) out of GPX, every segment with at least two points is a curve (x as longitude, y as latitude).
+///
+/// Only the `lat`/`lon` attributes of `<trkpt>` and `<rtept>` elements are read, everything else (elevation, time, extensions) is ignored.
+pub fn curves_from_gpx(input: &str) -> Result<Vec<Curve>, PcmError> {
+    let mut curves = vec![];
+    for (section, point) in [("trkseg", "trkpt"), ("rte", "rtept")] {
+        for body in elements(input, section) {
+            let curve = elements(body, point).into_iter().map(|element| {
+                let tag = &element[..element.find('>').unwrap_or(element.len())];
+                let coordinate = |name: &str| attribute(tag, name).and_then(|v| v.trim().parse::<f64>().ok());
+                match (coordinate("lon"), coordinate("lat")) {
+                    (Some(x), Some(y)) => Ok(Vector::new(x, y)),
+                    _ => Err(PcmError::Parse(format!("Invalid GPX point <{tag}>."))),
+                }
+            }).collect::<Result<Curve, PcmError>>()?;
+            // Single fixes (e.g. after a pause in recording) are no curve.
+            if curve.len() >= 2 {
+                validate_curve(&curve)?;
+                curves.push(curve);
+            }
+        }
+    }
+    Ok(curves)
+}
+
+/// Read the single curve out of GPX, see [`curves_from_gpx`].
+pub fn curve_from_gpx(input: &str) -> Result<Curve, PcmError> {
+    let mut curves = curves_from_gpx(input)?;
+    if curves.len() != 1 {
+        return Err(PcmError::Parse(format!("Expected a single curve in GPX, found {}.", curves.len())));
+    }
+    Ok(curves.pop().unwrap())
+}
+
+/// Read a trajectory of the GeoLife dataset (`.plt`, x as longitude, y as latitude).
+///
+/// The first six lines are a header, every other line is `latitude,longitude,0,altitude,days,date,time`.
+pub fn curve_from_plt(input: &str) -> Result<Curve, PcmError> {
+    let curve = input.lines().enumerate().skip(PLT_HEADER).filter(|(_, line)| !line.trim().is_empty()).map(|(k, line)| {
+        let values: Vec<Option<f64>> = line.split(',').take(2).map(|v| v.trim().parse::<f64>().ok()).collect();
+        match values.as_slice() {
+            [Some(lat), Some(lon)] => Ok(Vector::new(*lon, *lat)),
+            _ => Err(PcmError::Parse(format!("Invalid PLT point at line {}: {line}", k + 1))),
+        }
+    }).collect::<Result<Curve, PcmError>>()?;
+    validate_curve(&curve)?;
+    Ok(curve)
+}
+
+/// Elements `<name ...>...</name>` (or `<name .../>`) in input, each from right after its name up to its closing tag.
+fn elements<'a>(input: &'a str, name: &str) -> Vec<&'a str> {
+    let (open, close) = (format!("<{name}"), format!("</{name}>"));
+    let mut result = vec![];
+    let mut rest = input;
+    while let Some(k) = rest.find(&open) {
+        rest = &rest[k + open.len()..];
+        // Skip elements merely starting with the name (e.g. `<trkpt` within `<trkptx`).
+        if !rest.starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/') {
+            continue;
+        }
+        let end = match rest.find('>') {
+            Some(gt) if rest[..gt].ends_with('/') => gt,
+            _ => rest.find(&close).unwrap_or(rest.len()),
+        };
+        result.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    result
+}
+
+/// Value of the attribute (in single or double quotes) within a tag.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = tag;
+    while let Some(k) = rest.find(name) {
+        let preceded = rest[..k].ends_with(char::is_whitespace) || k == 0;
+        rest = &rest[k + name.len()..];
+        if preceded && let Some(value) = rest.trim_start().strip_prefix('=') {
+            let value = value.trim_start();
+            let quote = value.chars().next().filter(|&c| c == '"' || c == '\'')?;
+            return value[1..].split(quote).next();
+        }
+    }
+    None
+}
+
+/// Equirectangular projection of WGS84 coordinates (x as longitude, y as latitude) to meters around an origin.
+///
+/// Accurate up to tens of kilometers around the origin, which covers single trajectories, so distance thresholds are in meters.
+/// Project all curves that are compared with the same projection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalProjection {
+    /// Longitude and latitude mapped onto (0, 0).
+    pub origin: Vector,
+}
+impl LocalProjection {
+
+    pub fn new(origin: Vector) -> Self {
+        LocalProjection { origin }
+    }
+
+    /// Projection around the center of the bounding box of the curves, None if there are no points.
+    pub fn centered(curves: &[Curve]) -> Option<Self> {
+        let mut points = curves.iter().flatten();
+        let first = *points.next()?;
+        let (min, max) = points.fold((first, first), |(min, max), p| (min.min(p), max.max(p)));
+        Some(LocalProjection::new(0.5 * (min + max)))
+    }
+
+    /// Meters east (x) and north (y) of the origin.
+    pub fn apply(&self, p: Vector) -> Vector {
+        let scale = EARTH_RADIUS * std::f64::consts::PI / 180.;
+        Vector::new(scale * (p.x - self.origin.x) * self.origin.y.to_radians().cos(), scale * (p.y - self.origin.y))
+    }
+
+    /// Longitude and latitude of a point in meters, inverse of [`LocalProjection::apply`].
+    pub fn invert(&self, p: Vector) -> Vector {
+        let scale = EARTH_RADIUS * std::f64::consts::PI / 180.;
+        Vector::new(self.origin.x + p.x / (scale * self.origin.y.to_radians().cos()), self.origin.y + p.y / scale)
+    }
+
+    /// Curve in meters.
+    pub fn project(&self, c: &Curve) -> Curve {
+        c.iter().map(|&p| self.apply(p)).collect()
+    }
+
+    /// Curve in longitude and latitude, inverse of [`LocalProjection::project`].
+    pub fn unproject(&self, c: &Curve) -> Curve {
+        c.iter().map(|&p| self.invert(p)).collect()
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{curve::{validate_curve, validate_eps, Curve}, error::PcmError, fsd::FSD, partial_curve_graph_linear::{partial_curve_graph, Graph, NIDPair, Path}};
+
+
+/// Partial match of a curve against a path in a graph (map matching), see [`match_graph`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphMatch {
+    /// Nodes along the path, consecutive nodes are connected by an edge.
+    pub path: Path,
+    /// Positions (parameter on the curve, parameter on the path) walked along.
+    /// Path parameter t lies on edge `floor(t)` (between nodes `path[floor(t)]` and `path[floor(t)+1]`) at offset `t - floor(t)`.
+    pub steps: Vec<(f64, f64)>,
+}
+impl GraphMatch {
+
+    /// Edges along the path.
+    pub fn edges(&self) -> Vec<NIDPair> {
+        self.path.windows(2).map(|w| (w[0], w[1])).collect()
+    }
+
+    /// Edge and offset on the edge (unit interval, from its first node) of a path parameter.
+    pub fn edge_at(&self, t: f64) -> (NIDPair, f64) {
+        let k = (t.floor() as usize).min(self.path.len() - 2);
+        ((self.path[k], self.path[k+1]), t - k as f64)
+    }
+
+    /// Edge and offset the curve starts matching at.
+    pub fn start(&self) -> (NIDPair, f64) {
+        self.edge_at(self.steps[0].1)
+    }
+
+    /// Edge and offset the curve stops matching at.
+    pub fn end(&self) -> (NIDPair, f64) {
+        self.edge_at(self.steps.last().unwrap().1)
+    }
+}
+
+/// Partially match the curve against some path in the graph within threshold eps (Alt et al., using the linear sweep of [`partial_curve_graph`]).
+///
+/// The sweep over the free-space surface of the graph decides on and finds the path,
+/// the steps are obtained by partially matching the curve against the polyline of the path.
+pub fn match_graph(graph: &Graph, curve: &Curve, eps: f64) -> Result<Option<GraphMatch>, PcmError> {
+    validate_curve(curve)?;
+    validate_eps(eps)?;
+    let Some(path) = partial_curve_graph(graph, curve.clone(), eps) else { return Ok(None) };
+    let polyline: Curve = path.iter().map(|&u| graph.position(u).unwrap()).collect();
+    let steps = FSD::try_new(curve.clone(), polyline, eps)?.to_rsd().pcm_steps()
+        .ok_or(PcmError::InconsistentDiagram(format!("Curve does not match the polyline of the path {path:?} found in the graph.")))?;
+    Ok(Some(GraphMatch { path, steps }))
+}
@@ -0,0 +1,115 @@
+use crate::{curve::{point_on, Curve}, error::PcmError, vector::Vector};
+
+
+/// Sample (index along ps, index along qs) of a [`Grid`].
+pub(crate) type Sample = (usize, usize);
+
+/// Best cost to reach a sample, with a back-pointer to the previous sample.
+type Entry<C> = Option<(C, Option<Sample>)>;
+
+/// Start, move and end rules of a path through a [`Grid`], see [`Grid::best_path`].
+pub(crate) trait PathRules {
+    /// Cost of a path up to some sample.
+    type Cost;
+
+    /// Cost of a path starting at the sample, None if paths may not start there.
+    fn start(&self, at: Sample) -> Option<Self::Cost>;
+
+    /// Cost of a path reaching `from` at `cost` after moving on to `to`, None if the move is not allowed.
+    fn step(&self, cost: &Self::Cost, from: Sample, to: Sample) -> Option<Self::Cost>;
+
+    /// Finalize the cost of the best path to a sample before it is stored (once per sample, rather than once per move).
+    fn settle(&self, cost: Self::Cost) -> Self::Cost {
+        cost
+    }
+
+    /// Whether c1 is strictly better than c2 (both reaching the same sample, or both ending).
+    fn better(&self, c1: &Self::Cost, c2: &Self::Cost) -> bool;
+
+    /// Whether paths may end at the sample.
+    fn end(&self, at: Sample) -> bool;
+}
+
+/// Parameter space of ps and qs discretized with `resolution` samples per FSD cell.
+///
+/// Paths through it are monotone moves right, up, or diagonal between samples, so matchings found on it
+/// approximate continuous ones: only sampled points are checked against eps, not the free space in between.
+pub(crate) struct Grid {
+    resolution: f64,
+    /// Points of ps at every sample along it.
+    pub p: Vec<Vector>,
+    /// Points of qs at every sample along it.
+    pub q: Vec<Vector>,
+}
+impl Grid {
+
+    /// Sample both curves, the curves should be validated already.
+    pub fn new(ps: &Curve, qs: &Curve, resolution: usize) -> Result<Grid, PcmError> {
+        if resolution == 0 {
+            return Err(PcmError::InvalidArgument("Resolution should be at least one sample per cell.".to_string()));
+        }
+        let r = resolution as f64;
+        let p = (0..(ps.len() - 1) * resolution + 1).map(|x| point_on(ps, x as f64 / r)).collect();
+        let q = (0..(qs.len() - 1) * resolution + 1).map(|y| point_on(qs, y as f64 / r)).collect();
+        Ok(Grid { resolution: r, p, q })
+    }
+
+    /// Number of samples along ps and along qs.
+    pub fn size(&self) -> (usize, usize) {
+        (self.p.len(), self.q.len())
+    }
+
+    /// Position (parameter on ps, parameter on qs) of a sample.
+    pub fn position(&self, (x, y): Sample) -> (f64, f64) {
+        (x as f64 / self.resolution, y as f64 / self.resolution)
+    }
+
+    /// Whether the leash length at every sample is at most eps.
+    pub fn free(&self, eps: f64) -> Vec<Vec<bool>> {
+        self.p.iter().map(|p| self.q.iter().map(|q| p.distance(*q) <= eps).collect()).collect()
+    }
+
+    /// Best path under the rules with its cost, None if no path reaches an end.
+    ///
+    /// Dynamic program over the samples left to right, bottom to top, ties keep the earliest candidate
+    /// (starting over diagonal, horizontal and vertical moves at a sample, the lowest sample amongst ends).
+    pub fn best_path<R: PathRules>(&self, rules: &R) -> Option<(R::Cost, Vec<Sample>)> {
+        let (w, h) = self.size();
+        let mut best: Vec<Vec<Entry<R::Cost>>> = (0..w).map(|_| (0..h).map(|_| None).collect()).collect();
+        for x in 0..w {
+            for y in 0..h {
+                let to = (x, y);
+                let mut curr = rules.start(to).map(|cost| (cost, None));
+                let candidates = [(x > 0 && y > 0).then(|| (x - 1, y - 1)), (x > 0).then(|| (x - 1, y)), (y > 0).then(|| (x, y - 1))];
+                for from in candidates.into_iter().flatten() {
+                    if let Some((cost, _)) = &best[from.0][from.1] && let Some(cost) = rules.step(cost, from, to)
+                        && curr.as_ref().is_none_or(|(c, _)| rules.better(&cost, c)) {
+                        curr = Some((cost, Some(from)));
+                    }
+                }
+                best[x][y] = curr.map(|(cost, from)| (rules.settle(cost), from));
+            }
+        }
+
+        // Pick best end and walk back-pointers.
+        let mut end: Option<Sample> = None;
+        for x in 0..w {
+            for y in 0..h {
+                if rules.end((x, y)) && let Some((cost, _)) = &best[x][y]
+                    && end.is_none_or(|(ex, ey)| rules.better(cost, &best[ex][ey].as_ref().unwrap().0)) {
+                    end = Some((x, y));
+                }
+            }
+        }
+        let end = end?;
+        let mut curr = Some(end);
+        let mut path = vec![];
+        while let Some((x, y)) = curr {
+            path.push((x, y));
+            curr = best[x][y].as_ref().unwrap().1;
+        }
+        path.reverse();
+        let (cost, _) = best[end.0][end.1].take().unwrap();
+        Some((cost, path))
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{curve::{validate_curve, validate_eps, Curve, MAX_COORDINATE}, error::PcmError, fsd::{free_boundary, reachable_boundary, Boundaries, MatchMode}, lineboundary::OptLineBoundary, vector::{Point, Vector}, LineBoundary};
+
+
+/// Reachable space diagram of a growing curve P against a fixed reference curve Q, for online matching.
+///
+/// Every [`IncrementalFSD::push_point`] computes only the new column of the diagram (linear in the length of Q),
+/// the reachable columns so far are kept to walk back a matching with [`IncrementalFSD::pcm_steps`].
+#[derive(Debug, Clone)]
+pub struct IncrementalFSD<P: Point = Vector> {
+    /// Reference curve Q.
+    qs: Curve<P>,
+    /// Distance threshold.
+    eps: f64,
+    /// Last point pushed onto P.
+    last: Option<P>,
+    /// Reachable intervals on the vertical boundaries per point of P (one per segment of Q).
+    verticals: Vec<Vec<OptLineBoundary>>,
+    /// Reachable intervals on the horizontal boundaries per segment of P (one per point of Q).
+    horizontals: Vec<Vec<OptLineBoundary>>,
+}
+impl<P: Point> IncrementalFSD<P> {
+
+    /// Start matching against the reference curve qs, without any points on P yet.
+    pub fn new(qs: Curve<P>, eps: f64) -> IncrementalFSD<P> {
+        IncrementalFSD { qs, eps, last: None, verticals: vec![], horizontals: vec![] }
+    }
+
+    /// Like [`IncrementalFSD::new`], but reject malformed input with an error instead of panicking later on.
+    pub fn try_new(qs: Curve<P>, eps: f64) -> Result<IncrementalFSD<P>, PcmError> {
+        validate_curve(&qs)?;
+        validate_eps(eps)?;
+        Ok(IncrementalFSD::new(qs, eps))
+    }
+
+    /// Number of points pushed onto P.
+    pub fn len(&self) -> usize {
+        self.verticals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Append point p to P and compute reachability of the new column, returns whether P still matches a subcurve of Q.
+    ///
+    /// Rejects a non-finite (or too large) point with an error, leaving the diagram untouched.
+    pub fn push_point(&mut self, p: P) -> Result<bool, PcmError> {
+        if !p.is_bounded(MAX_COORDINATE) {
+            return Err(PcmError::InvalidPoint { index: self.len(), point: format!("{p:?}") });
+        }
+        let (qs, eps) = (&self.qs, self.eps);
+        let m = qs.len();
+        let Some(last) = self.last else {
+            // Initiate whole left border (we seek partial curve).
+            self.verticals.push((0..m-1).map(|j| free_boundary(p, qs[j], qs[j+1], eps)).collect());
+            self.last = Some(p);
+            return Ok(self.is_match());
+        };
+
+        let i = self.len();
+        let (prev_verticals, prev_horizontals) = (&self.verticals[i-1], self.horizontals.last());
+        let mut horizontals: Vec<OptLineBoundary> = Vec::with_capacity(m);
+        let mut verticals: Vec<OptLineBoundary> = Vec::with_capacity(m-1);
+        // Walk up the column, the horizontal boundary at j (between points i-1 and i) before the vertical boundary at i above it.
+        for j in 0..m {
+            let free = free_boundary(qs[j], last, p, eps);
+            // First horizontal boundary of every row starting at a free left cornerpoint is reachable (since neither prev, para, nor orth may exist).
+            let seed = if i == 1 && let Some(LineBoundary { a: 0., b: _ }) = free { free } else { None };
+            let orth = if j > 0 { prev_verticals[j-1] } else { None };
+            let para = if j > 0 { horizontals[j-1] } else { None };
+            let prev = prev_horizontals.and_then(|hs| hs[j]);
+            horizontals.push(reachable_boundary(seed, free, orth, para, prev));
+
+            if j < m - 1 {
+                let free = free_boundary(p, qs[j], qs[j+1], eps);
+                let orth = horizontals[j];
+                let para = prev_verticals[j];
+                let prev = if j > 0 { verticals[j-1] } else { None };
+                verticals.push(reachable_boundary(None, free, orth, para, prev));
+            }
+        }
+        self.horizontals.push(horizontals);
+        self.verticals.push(verticals);
+        self.last = Some(p);
+        Ok(self.is_match())
+    }
+
+    /// Whether P (all points pushed so far) matches a subcurve of Q.
+    pub fn is_match(&self) -> bool {
+        self.verticals.last().is_some_and(|column| column.iter().any(|boundary| boundary.is_some()))
+    }
+
+    /// Whether P can no longer match, whatever points are pushed next.
+    ///
+    /// The next column is only reachable from the current column, or along a row from a reachable cornerpoint on it.
+    pub fn is_dead(&self) -> bool {
+        let on_corner = self.horizontals.last().is_some_and(|row| row.iter().any(|boundary| matches!(boundary, Some(LineBoundary { a: _, b: 1. }))));
+        !self.is_empty() && !self.is_match() && !on_corner
+    }
+
+    /// Reachable intervals on the vertical boundaries of the current column (one per segment of Q).
+    pub fn frontier(&self) -> &[OptLineBoundary] {
+        self.verticals.last().map_or(&[], |column| column.as_slice())
+    }
+
+    /// Lowest parameter on Q the matching of P may end at (if any).
+    pub fn end(&self) -> Option<f64> {
+        self.frontier().iter().enumerate().find_map(|(j, boundary)| boundary.map(|LineBoundary { a, b: _ }| j as f64 + a))
+    }
+
+    /// Compute steps to walk along the curves for a partial curve matching of P so far (if any), see [`crate::FSD::pcm_steps`].
+    pub fn pcm_steps(&self) -> Option<Vec<(f64, f64)>> {
+        if self.is_empty() { return None; }
+        Some(self.steps_to(self.end_position()?))
+    }
+}
+impl<P: Point> Boundaries for IncrementalFSD<P> {
+
+    fn size(&self) -> (usize, usize) {
+        (self.len(), self.qs.len())
+    }
+
+    fn mode(&self) -> MatchMode {
+        MatchMode::SubcurveOfQ
+    }
+
+    fn boundary(&self, (axis, x, y): (usize, usize, usize)) -> OptLineBoundary {
+        let (columns, i, j) = if axis == 0 { (&self.verticals, x, y) } else { (&self.horizontals, y, x) };
+        columns.get(i).and_then(|column| column.get(j)).copied().flatten()
+    }
+}
@@ -0,0 +1,91 @@
+use std::{cmp::Ordering, rc::Rc};
+
+use crate::{curve::{validate_curve, validate_eps, Curve}, error::PcmError, grid::{Grid, PathRules, Sample}};
+
+
+/// Number of distance levels (relative to eps) the leash lengths are quantized into.
+const LEVELS: f64 = 1000.;
+
+/// Time spent per quantized leash length along a path, sorted from highest to lowest level as (level, count).
+type LevelCounts = Vec<(u32, u32)>;
+
+/// Lexicographic comparison: the path spending less time at the highest level is smaller, ties are broken by the next level.
+fn compare(c1: &LevelCounts, c2: &LevelCounts) -> Ordering {
+    for (&(l1, k1), &(l2, k2)) in c1.iter().zip(c2) {
+        if l1 != l2 { return l1.cmp(&l2); }
+        if k1 != k2 { return k1.cmp(&k2); }
+    }
+    c1.len().cmp(&c2.len())
+}
+
+/// Add a single unit of time spent at level to the counts.
+fn increment(counts: &LevelCounts, level: u32) -> LevelCounts {
+    let mut counts = counts.clone();
+    match counts.binary_search_by(|&(l, _)| level.cmp(&l)) {
+        Ok(k) => counts[k].1 += 1,
+        Err(k) => counts.insert(k, (level, 1))
+    }
+    counts
+}
+
+/// Compute steps of a lexicographic partial curve match of ps against a subcurve of qs.
+///
+/// Amongst all matchings with leash length below eps, minimize the time spent at the maximal leash length, then at the next largest, etc.
+/// The parameter space is discretized with `resolution` samples per FSD cell (monotone moves right, up, or diagonal),
+/// and leash lengths are quantized relative to eps, so the result is an approximation of the continuous lexicographic matching.
+pub fn lexicographic_steps(ps: &Curve, qs: &Curve, eps: f64, resolution: usize) -> Result<Option<Vec<(f64, f64)>>, PcmError> {
+    validate_curve(ps)?;
+    validate_curve(qs)?;
+    validate_eps(eps)?;
+    let grid = Grid::new(ps, qs, resolution)?;
+    // Quantized leash length per sample (None if beyond eps).
+    let levels = grid.p.iter().map(|p| grid.q.iter().map(|q| {
+        let d = p.distance(*q);
+        if d <= eps { Some((LEVELS * d / eps).round() as u32) } else { None }
+    }).collect()).collect();
+    let rules = LexicographicRules { levels, w: grid.size().0 };
+    Ok(grid.best_path(&rules).map(|(_, path)| path.into_iter().map(|s| grid.position(s)).collect()))
+}
+
+/// Counts of a path, shared with the path it extends, plus the level of the sample it moves to (if not added yet).
+type PathCounts = (Rc<LevelCounts>, Option<u32>);
+
+/// Paths through samples within eps, compared by the time they spend per level.
+struct LexicographicRules {
+    levels: Vec<Vec<Option<u32>>>,
+    w: usize,
+}
+impl PathRules for LexicographicRules {
+    type Cost = PathCounts;
+
+    fn start(&self, (x, y): Sample) -> Option<PathCounts> {
+        // Partial match: We may start anywhere on the left boundary.
+        let level = self.levels[x][y].filter(|_| x == 0)?;
+        Some((Rc::new(vec![(level, 1)]), None))
+    }
+
+    fn step(&self, (counts, _): &PathCounts, _: Sample, (x, y): Sample) -> Option<PathCounts> {
+        let level = self.levels[x][y].filter(|_| x > 0)?;
+        Some((counts.clone(), Some(level)))
+    }
+
+    fn settle(&self, (counts, level): PathCounts) -> PathCounts {
+        match level {
+            Some(level) => (Rc::new(increment(&counts, level)), None),
+            None => (counts, None),
+        }
+    }
+
+    fn better(&self, (c1, l1): &PathCounts, (c2, l2): &PathCounts) -> bool {
+        // Adding the same level to both counts keeps their order, so moves to the same sample compare without adding it.
+        if l1 == l2 {
+            return compare(c1, c2) == Ordering::Less;
+        }
+        let add = |counts: &LevelCounts, level: &Option<u32>| level.map_or_else(|| counts.clone(), |l| increment(counts, l));
+        compare(&add(c1, l1), &add(c2, l2)) == Ordering::Less
+    }
+
+    fn end(&self, (x, _): Sample) -> bool {
+        x == self.w - 1
+    }
+}
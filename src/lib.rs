@@ -1,21 +1,180 @@
 #![feature(let_chains)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(feature = "std"), feature(core_float_math))]
+extern crate alloc;
+
+pub mod error;
+pub mod float;
 pub mod vector;
 pub mod curve;
+#[cfg(feature = "std")]
+pub mod timed_curve;
 pub mod lineboundary;
+pub mod predicates;
 pub mod partial_curve;
+#[cfg(feature = "std")]
 pub mod partial_curve_graph_exponential;
+#[cfg(feature = "std")]
 pub mod partial_curve_graph_linear;
 pub mod fsd;
+#[cfg(feature = "std")]
+pub mod raster;
+#[cfg(feature = "std")]
+pub mod simplify;
+#[cfg(feature = "std")]
+mod grid;
+#[cfg(feature = "std")]
+pub mod lexicographic;
+pub mod parallel;
+#[cfg(feature = "fs")]
+pub mod checkpoint;
+#[cfg(feature = "fs")]
+pub mod audit;
+#[cfg(feature = "std")]
+pub mod precision;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod gap;
+#[cfg(feature = "std")]
+pub mod shared;
+#[cfg(feature = "std")]
+pub mod explain;
+pub mod bvh;
+#[cfg(feature = "std")]
+pub mod dedupe;
+#[cfg(feature = "std")]
+pub mod export;
+#[cfg(feature = "std")]
+pub mod cost;
+#[cfg(feature = "std")]
+pub mod calibrate;
+#[cfg(feature = "std")]
+pub mod discrete;
+#[cfg(feature = "std")]
+pub mod dtw;
+#[cfg(feature = "std")]
+pub mod geojson;
+#[cfg(feature = "std")]
+pub mod formats;
+#[cfg(feature = "std")]
+pub mod gps;
+#[cfg(feature = "std")]
+pub mod gen;
+#[cfg(feature = "std")]
+pub mod batch;
+#[cfg(feature = "std")]
+pub mod query;
+#[cfg(feature = "std")]
+pub mod graph;
+#[cfg(feature = "std")]
+pub mod cyclic;
+#[cfg(feature = "std")]
+pub mod transform;
+#[cfg(feature = "std")]
+pub mod sparse;
+#[cfg(feature = "std")]
+pub mod incremental;
+#[cfg(feature = "std")]
+pub mod slope;
+#[cfg(feature = "simd")]
+mod simd;
+pub mod decide;
+#[cfg(feature = "std")]
+pub mod prepared;
+#[cfg(feature = "std")]
+pub mod varying;
+#[cfg(feature = "std")]
+pub mod chunked;
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
 
 // Exporting functionality.
-pub use vector::Vector;
-pub use lineboundary::{LineBoundary, OptLineBoundary};
-pub use curve::Curve;
-pub use fsd::FSD;
+pub use error::PcmError;
+pub use float::Float;
+pub use vector::{Point, Vector, Vector3, VectorN};
+#[cfg(feature = "python")]
+pub use vector::PyVector;
+pub use lineboundary::{free_interval, LineBoundary, OptLineBoundary};
+pub use predicates::Predicates;
+pub use curve::{Curve, CurveExt};
+#[cfg(feature = "std")]
+pub use timed_curve::{timed_partial_curve_matching, TimeWindow, TimedCurve};
+pub use fsd::{Frontier, Frontiers, MatchMode, FSD};
+#[cfg(feature = "std")]
+pub use discrete::DiscreteFSD;
+#[cfg(feature = "std")]
+pub use dtw::DTW;
+#[cfg(feature = "std")]
+pub use sparse::SparseFSD;
+#[cfg(feature = "std")]
+pub use incremental::IncrementalFSD;
+#[cfg(feature = "std")]
+pub use slope::SlopeRSD;
+pub use decide::pcm_decide;
+#[cfg(feature = "std")]
+pub use prepared::PreparedCurve;
+#[cfg(feature = "std")]
+pub use varying::varying_partial_curve_matching;
+#[cfg(feature = "std")]
+pub use chunked::{chunked_matchings, ChunkedOccurrence};
+#[cfg(feature = "std")]
+pub use raster::{free_space_mask, DiagramArrays};
+#[cfg(feature = "fs")]
+pub use raster::{write_npy, write_npy_f64};
+#[cfg(feature = "std")]
+pub use simplify::{simplify_douglas_peucker, simplify_imai_iri, simplify_to_n};
+#[cfg(feature = "std")]
+pub use lexicographic::lexicographic_steps;
+#[cfg(feature = "fs")]
+pub use checkpoint::Checkpoint;
+#[cfg(feature = "fs")]
+pub use audit::{audited_partial_curve, AuditLog, AuditRecord};
+#[cfg(feature = "std")]
+pub use precision::{precision_check, PrecisionReport};
+#[cfg(feature = "std")]
+pub use cache::MatchCache;
+#[cfg(feature = "std")]
+pub use gap::{gap_matching, GapMatching};
+#[cfg(feature = "std")]
+pub use shared::{longest_shared_subcurve, SharedSubcurve};
+#[cfg(feature = "std")]
+pub use explain::{segment_matches, SegmentMatch};
+pub use bvh::{Aabb, SegmentBvh};
+#[cfg(feature = "std")]
+pub use dedupe::{dedupe, dedupe_with_coverage, DuplicateGroup};
+#[cfg(feature = "std")]
+pub use export::{curve_to_csv, curve_to_geojson, matrix_to_csv, steps_to_csv, steps_to_json, ExportOptions};
+#[cfg(feature = "std")]
+pub use cost::{min_cost_steps, DistanceWeighted, Move, SlopePenalty, StepCost, Uniform};
+#[cfg(feature = "std")]
+pub use calibrate::{calibrate, Calibration, CalibrationPoint};
+#[cfg(feature = "std")]
+pub use geojson::{curve_from_geojson, curves_from_geojson, matching_to_geojson};
+#[cfg(feature = "std")]
+pub use batch::{match_many, match_many_bidirectional, CandidateMatch};
+#[cfg(feature = "std")]
+pub use query::{nearest_curves, Neighbour};
+#[cfg(feature = "std")]
+pub use graph::{match_graph, GraphMatch};
+#[cfg(feature = "std")]
+pub use cyclic::{cyclic_matching, unroll_loop, CyclicMatching};
+#[cfg(feature = "std")]
+pub use transform::{best_transform, Alignment, Transform, TransformSearch};
+#[cfg(feature = "std")]
+pub use formats::{curve_from_csv, curve_from_polyline, curve_from_wkt, curve_to_polyline, curve_to_wkt, curves_from_wkt};
+#[cfg(feature = "std")]
+pub use gps::{curve_from_gpx, curve_from_plt, curves_from_gpx, LocalProjection};
+#[cfg(feature = "std")]
+pub use gen::{circular_arc, perturb_curve, random_curve, seeded, subcurve_pair, translate_curve, zigzag, GeneratedPair};
 pub use partial_curve::*;
+#[cfg(feature = "std")]
 pub use partial_curve_graph_exponential::*;
+#[cfg(feature = "std")]
 pub use partial_curve_graph_linear::*;
 
+#[cfg(feature = "std")]
 #[allow(non_upper_case_globals)]
 const sanity_check: bool = true;
 pub const EPS: f64 = 0.00001;
\ No newline at end of file
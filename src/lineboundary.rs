@@ -1,16 +1,20 @@
-use crate::vector::Vector;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{float::Float, fsd::free_boundary, vector::Point};
 
 /// Unit-distance one-dimensional boundary
 #[derive(Debug, Clone, Copy)]
-pub struct LineBoundary {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LineBoundary<F: Float = f64> {
     /// Starting point, somewhere on the unit interval, but must be smaller than b.
-    pub a: f64,
+    pub a: F,
     /// End point, somewhere on the unit interval, but must be larger than a.
-    pub b: f64
+    pub b: F
 }
-impl LineBoundary {
+impl<F: Float> LineBoundary<F> {
     
-    pub fn new(a: f64, b: f64) -> OptLineBoundary {
+    pub fn new(a: F, b: F) -> OptLineBoundary<F> {
         if a < b {
             Some(LineBoundary { a, b })
         } else {
@@ -19,7 +23,7 @@ impl LineBoundary {
     }
 
     /// Union two line boundaries.
-    pub fn union(opt_b1: OptLineBoundary, opt_b2: OptLineBoundary) -> OptLineBoundary {
+    pub fn union(opt_b1: OptLineBoundary<F>, opt_b2: OptLineBoundary<F>) -> OptLineBoundary<F> {
         if opt_b1.is_none() { opt_b2 } 
         else if opt_b2.is_none() { opt_b1 }
         else {
@@ -33,11 +37,47 @@ impl LineBoundary {
         }
     }
 
+    /// Check the other is a subset of self.
+    pub fn has_subset(&self, other: Self) -> bool {
+        self.b >= other.b && self.a <= other.a
+    }
+
+    /// Widen the endpoints to doubles (lossless).
+    pub fn to_f64(self) -> LineBoundary {
+        LineBoundary { a: self.a.to_f64(), b: self.b.to_f64() }
+    }
+
+    /// Round the endpoints of a double boundary to this scalar.
+    ///
+    /// Endpoints only round onto 0 or 1 when they are exactly 0 or 1 (these mark free cornerpoints),
+    /// and an interval too short for this scalar is widened to its smallest representable extent rather than dropped.
+    pub fn from_f64(LineBoundary { a, b }: LineBoundary) -> LineBoundary<F> {
+        // Smallest and largest values strictly inside the unit interval.
+        let (lo, hi) = (F::MIN_POSITIVE, F::ONE - F::EPSILON / F::from_f64(2.));
+        let round = |t: f64| if t == 0. || t == 1. { F::from_f64(t) } else { F::from_f64(t).max(lo).min(hi) };
+        let (a, b, was_valid) = (round(a), round(b), a < b);
+        let two = F::from_f64(2.);
+        if a < b || !was_valid {
+            LineBoundary { a, b }
+        } else if b < hi { // Both endpoints rounded onto the same value.
+            LineBoundary { a, b: (a * (F::ONE + two * F::EPSILON)).min(hi) }
+        } else {
+            LineBoundary { a: hi * (F::ONE - F::EPSILON), b }
+        }
+    }
+}
+impl LineBoundary {
+
     /// Compute unit-distance free space line boundary between point p and line segment q.
-    pub fn compute(p: Vector, q0: Vector, q1: Vector, eps: f64 ) -> OptLineBoundary {
+    ///
+    /// Intervals not longer than twice their error bound (see [`LineBoundary::compute_error`]) can not be told apart from empty ones, and are dropped.
+    pub fn compute<P: Point>(p: P, q0: P, q1: P, eps: f64 ) -> OptLineBoundary {
         let v = q1 - q0;
+        if v.dot(v) == 0. { // Degenerate segment: either fully free or fully blocked.
+            return if p.distance(q0) < eps { Some(LineBoundary { a: 0., b: 1. }) } else { None };
+        }
         let vli = 1. / v.dot(v).sqrt();
-        let vn = vli * v;
+        let vn = v.scale(vli);
 
         let l = p - q0;
         let tca = l.dot(vn);
@@ -52,7 +92,7 @@ impl LineBoundary {
         // let p0 = q0 + t0 * v; // First point of intersection.
         // let p1 = q0 + t1 * v; // Second point of intersection.
 
-        if t1 < 0. || t0 > 1. || t1 - t0 < 0.0001 { return None; }
+        if t1 < 0. || t0 > 1. || t1 - t0 <= 2. * LineBoundary::compute_error(p, q0, q1, eps) { return None; }
         Some(
             LineBoundary {
                 a: t0.min(1.).max(0.),
@@ -61,17 +101,41 @@ impl LineBoundary {
         )
     }
 
-    /// Check the other is a subset of self.
-    pub fn has_subset(&self, other: Self) -> bool {
-        self.b >= other.b && self.a <= other.a
+    /// Upper bound on the floating point error of the endpoints computed by [`LineBoundary::compute`].
+    /// 
+    /// Grows without bound as the disc around p becomes tangent to the segment, which is exactly where the interval is fragile.
+    pub fn compute_error<P: Point>(p: P, q0: P, q1: P, eps: f64) -> f64 {
+        let v = q1 - q0;
+        if v.dot(v) == 0. { return 0.; }
+        let vli = 1. / v.dot(v).sqrt();
+        let l = p - q0;
+        let tca = l.dot(v.scale(vli));
+        let d2 = l.dot(l) - tca * tca;
+        let e2 = eps * eps;
+
+        // Rounding in the squared terms is amplified by the square root near tangency.
+        let u = 8. * f64::EPSILON;
+        let err_e2d2 = u * (e2 + l.dot(l) + tca * tca);
+        let thc = (e2 - d2).max(0.).sqrt();
+        let err_thc = if thc > 0. { err_e2d2 / (2. * thc) } else { err_e2d2.sqrt() };
+        vli * (u * (tca.abs() + thc) + err_thc)
     }
 
 }
 
+/// Compute the free interval on segment (q0, q1) of points within eps distance to point p.
+/// 
+/// The interval is expressed in unit-distance parameters of the segment (0 at q0, 1 at q1),
+/// with endpoints snapped onto the segment endpoints like the cell boundaries of [`crate::FSD`].
+pub fn free_interval<P: Point>(p: P, q0: P, q1: P, eps: f64) -> OptLineBoundary {
+    free_boundary(p, q0, q1, eps)
+}
+
 /// LineBoundaries are ponentially empty which we denote with the None type.
-pub type OptLineBoundary = Option<LineBoundary>;
+pub type OptLineBoundary<F = f64> = Option<LineBoundary<F>>;
 
 /// Print OptLineBoundary (for debugging purposes).
+#[cfg(feature = "std")]
 pub fn print_lb(opt_lb : OptLineBoundary) {
     if opt_lb.is_none() {
         print!("(     -     )");
@@ -0,0 +1,17 @@
+//! Parallel code paths, using rayon if the `parallel` feature is enabled and a single-threaded fallback otherwise.
+
+use alloc::vec::Vec;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Map f over the items, in parallel if the `parallel` feature is enabled.
+#[cfg(feature = "parallel")]
+pub fn map<T, R, F>(items: Vec<T>, f: F) -> Vec<R> where T: Send, R: Send, F: Fn(T) -> R + Sync + Send {
+    items.into_par_iter().map(f).collect()
+}
+
+/// Map f over the items, in parallel if the `parallel` feature is enabled.
+#[cfg(not(feature = "parallel"))]
+pub fn map<T, R, F>(items: Vec<T>, f: F) -> Vec<R> where F: Fn(T) -> R {
+    items.into_iter().map(f).collect()
+}
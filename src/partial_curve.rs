@@ -1,18 +1,671 @@
+#[cfg(feature = "python")]
 use pyo3::pyfunction;
-use crate::{curve::Curve, fsd::FSD};
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use alloc::{format, string::ToString, vec, vec::Vec};
+
+use crate::{bvh::SegmentBvh, curve::{arc_length, distance_to_segment, point_on, subcurve, validate_curve, validate_eps, Curve}, error::PcmError, fsd::{free_boundary, Boundaries, FSDPosition, MatchMode, FSD}, parallel, vector::{Point, Vector}, LineBoundary, EPS};
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
 
 /// Returns any subcurve of qs (if it exists) with Fréchet distance to ps below threshold epsilon.
+/// 
+/// Malformed input is reported as a `ValueError`.
+#[cfg(feature = "python")]
 #[pyfunction]
-pub fn partial_curve(ps: Curve, qs: Curve, eps: f64) -> Option<(f64, f64)> {
-    let fsd = FSD::new(ps, qs, eps);
+pub fn partial_curve(ps: Curve, qs: Curve, eps: f64) -> Result<Option<(f64, f64)>, PcmError> {
+    try_partial_curve(ps, qs, eps)
+}
+
+/// Returns any subcurve of qs (if it exists) with Fréchet distance to ps below threshold epsilon, or an error on malformed input.
+pub fn try_partial_curve<P: Point>(ps: Curve<P>, qs: Curve<P>, eps: f64) -> Result<Option<(f64, f64)>, PcmError> {
+    let fsd = FSD::try_new(ps, qs, eps)?;
     let rsd = fsd.to_rsd();
     let opt_steps = rsd.pcm_steps();
     if opt_steps.is_none() { 
-       None
+       Ok(None)
     } else {
         let steps = opt_steps.unwrap();
         let start= steps[0].1;
         let end = steps.last().unwrap().1;
-        Some((start, end))
+        Ok(Some((start, end)))
+    }
+}
+
+/// Like [`try_partial_curve`], with the segments of qs indexed (see [`SegmentBvh::new`], built once per target) to reject ps without building the diagram
+/// if some vertex of ps lies beyond eps of qs (see [`SegmentBvh::may_match`]).
+pub fn try_partial_curve_indexed(ps: &Curve, qs: &Curve, index: &SegmentBvh, eps: f64) -> Result<Option<(f64, f64)>, PcmError> {
+    validate_curve(ps)?;
+    validate_curve(qs)?;
+    validate_eps(eps)?;
+    if !index.may_match(ps, eps) {
+        return Ok(None);
+    }
+    try_partial_curve(ps.clone(), qs.clone(), eps)
+}
+
+/// Partial curve match of ps (the query) against a subcurve of the target curve qs.
+///
+/// The steps form a polygonal chain through the diagram, walked along by a parameter t from 0 up to the number of steps minus one
+/// (integer part is the step index, fractional part the offset towards the next step, like curve parameters).
+/// The methods rely on the steps and both curves being non-empty, as checked by [`Matching::new`] (and on deserialization).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(try_from = "MatchingData"))]
+pub struct Matching {
+    /// Positions (parameter on ps, parameter on qs) walked along.
+    pub steps: Vec<(f64, f64)>,
+    /// Curve ps matched.
+    pub query: Curve,
+    /// Curve qs matched against.
+    pub target: Curve,
+}
+
+/// Fields of a [`Matching`] as serialized, checked by [`Matching::new`] on deserialization.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct MatchingData {
+    steps: Vec<(f64, f64)>,
+    query: Curve,
+    target: Curve,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<MatchingData> for Matching {
+    type Error = PcmError;
+
+    fn try_from(data: MatchingData) -> Result<Self, PcmError> {
+        Matching::new(data.query, data.target, data.steps)
+    }
+}
+
+impl Matching {
+
+    /// Construct a matching, fails if the steps or either curve are empty.
+    pub fn new(query: Curve, target: Curve, steps: Vec<(f64, f64)>) -> Result<Self, PcmError> {
+        if steps.is_empty() {
+            return Err(PcmError::EmptySteps);
+        }
+        if query.is_empty() || target.is_empty() {
+            return Err(PcmError::EmptyCurve { len: 0 });
+        }
+        Ok(Matching { steps, query, target })
+    }
+
+    /// Raw positions (parameter on ps, parameter on qs) walked along.
+    pub fn to_steps(&self) -> Vec<(f64, f64)> {
+        self.steps.clone()
+    }
+
+    /// Position (parameter on ps, parameter on qs) at parameter t along the steps (clamped to the steps).
+    pub fn position(&self, t: f64) -> (f64, f64) {
+        let k = (t.max(0.).floor() as usize).min(self.steps.len() - 1);
+        let s = t - k as f64;
+        if k == self.steps.len() - 1 || s <= 0. {
+            return self.steps[k];
+        }
+        let ((i1, j1), (i2, j2)) = (self.steps[k], self.steps[k+1]);
+        (i1 + s * (i2 - i1), j1 + s * (j2 - j1))
+    }
+
+    /// Points on ps and qs coupled at parameter t along the steps.
+    pub fn evaluate(&self, t: f64) -> (Vector, Vector) {
+        self.points_at(self.position(t))
+    }
+
+    /// Points on ps and qs at a position (parameter on ps, parameter on qs).
+    pub fn points_at(&self, (i, j): (f64, f64)) -> (Vector, Vector) {
+        (point_on(&self.query, i), point_on(&self.target, j))
+    }
+
+    /// Parameter interval [t_start, t_end] on qs covered by the matching.
+    pub fn covered_interval(&self) -> (f64, f64) {
+        (self.steps[0].1, self.steps.last().unwrap().1)
+    }
+
+    /// Sub-polyline of qs covered by the matching (with interpolated endpoints).
+    pub fn subcurve(&self) -> Curve {
+        let (t0, t1) = self.covered_interval();
+        subcurve(&self.target, t0, t1)
+    }
+
+    /// Fraction of qs (in arc length) covered by the matching.
+    pub fn coverage(&self) -> f64 {
+        let (t0, t1) = self.covered_interval();
+        let total = arc_length(&self.target, (self.target.len() - 1) as f64);
+        if total == 0. { return 1.; }
+        (arc_length(&self.target, t1) - arc_length(&self.target, t0)) / total
+    }
+
+    /// Largest distance between the points of ps and qs the matching couples (the Fréchet distance it realizes).
+    pub fn max_distance(&self) -> f64 {
+        // Within a piece both points move linearly, so the distance is convex and largest at either end.
+        let (p, q) = self.evaluate((self.steps.len() - 1) as f64);
+        self.pieces().into_iter().map(|((p, q), _)| p.distance(q)).fold(p.distance(q), f64::max)
+    }
+
+    /// Integral of the coupled distance along the matching, with respect to the arc length walked along both curves.
+    pub fn integral_distance(&self) -> f64 {
+        self.pieces().into_iter().map(|((p0, q0), (p1, q1))| {
+            // Composite Simpson's rule, the distance is the norm of a linear function on a piece.
+            let k = 8;
+            let distance = |s: f64| ((1. - s) * p0 + s * p1).distance((1. - s) * q0 + s * q1);
+            let weight = |l: usize| if l == 0 || l == k { 1. } else if l % 2 == 1 { 4. } else { 2. };
+            let sum: f64 = (0..=k).map(|l| weight(l) * distance(l as f64 / k as f64)).sum();
+            sum / (3. * k as f64) * (p0.distance(p1) + q0.distance(q1))
+        }).sum()
+    }
+
+    /// Mean coupled distance along the matching (the integral divided by the arc length walked along both curves).
+    /// 
+    /// A matching which does not move at all has the distance of its single position as mean.
+    pub fn mean_distance(&self) -> f64 {
+        let length: f64 = self.pieces().into_iter().map(|((p0, q0), (p1, q1))| p0.distance(p1) + q0.distance(q1)).sum();
+        if length == 0. { return self.max_distance(); }
+        self.integral_distance() / length
+    }
+
+    /// Pieces of the matching along which both curves are linear, as pairs of (point on ps, point on qs) at either end.
+    fn pieces(&self) -> Vec<((Vector, Vector), (Vector, Vector))> {
+        let mut pieces = vec![];
+        for (k, w) in self.steps.windows(2).enumerate() {
+            let ((i1, j1), (i2, j2)) = (w[0], w[1]);
+            // Split the step where it crosses a vertex of either curve.
+            let mut splits = vec![0., 1.];
+            for (t1, t2) in [(i1, i2), (j1, j2)] {
+                let (lo, hi) = (t1.min(t2), t1.max(t2));
+                splits.extend((lo.floor() as usize + 1..hi.ceil() as usize).map(|v| (v as f64 - t1) / (t2 - t1)));
+            }
+            splits.sort_by(f64::total_cmp);
+            for s in splits.windows(2) {
+                pieces.push((self.evaluate(k as f64 + s[0]), self.evaluate(k as f64 + s[1])));
+            }
+        }
+        pieces
     }
-}
\ No newline at end of file
+}
+
+/// Compute a partial curve match (if any) of ps against a subcurve of qs below threshold epsilon.
+pub fn partial_curve_matching(ps: Curve, qs: Curve, eps: f64) -> Result<Option<Matching>, PcmError> {
+    let fsd = FSD::try_new(ps.clone(), qs.clone(), eps)?;
+    fsd.to_rsd().pcm_steps().map(|steps| Matching::new(ps, qs, steps)).transpose()
+}
+
+/// Returns the interval on qs and its sub-polyline (if a partial match exists) with Fréchet distance to ps below threshold epsilon.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn partial_curve_subcurve(ps: Curve, qs: Curve, eps: f64) -> Result<Option<(f64, f64, Curve)>, PcmError> {
+    let opt_matching = partial_curve_matching(ps, qs, eps)?;
+    Ok(opt_matching.map(|matching| {
+        let (start, end) = matching.covered_interval();
+        (start, end, matching.subcurve())
+    }))
+}
+
+/// Partial match of ps whose end lies within one maximal reachable interval on the right boundary of the RSD, see [`all_matchings`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Occurrence {
+    /// Maximal parameter interval on qs matchings of ps may end in (reachable intervals connecting at a cornerpoint are merged).
+    pub exit: (f64, f64),
+    /// Matching ending at the start of the exit interval.
+    pub matching: Matching,
+}
+
+/// Enumerate every occurrence of ps along qs below threshold epsilon, one matching per maximal exit interval (ordered along qs).
+pub fn all_matchings(ps: Curve, qs: Curve, eps: f64) -> Result<Vec<Occurrence>, PcmError> {
+    let rsd = FSD::try_new(ps.clone(), qs.clone(), eps)?.to_rsd();
+    let (n, m) = (rsd.n, rsd.m);
+
+    // Lowest boundary, offset and end parameter of every maximal exit interval.
+    let mut exits: Vec<(usize, f64, f64)> = vec![];
+    for y in 0..m-1 {
+        let Some(LineBoundary { a, b }) = rsd.segs[(0, n-1, y)] else { continue };
+        match exits.last_mut() {
+            Some((_, _, end)) if *end == y as f64 && a == 0. => *end = y as f64 + b,
+            _ => exits.push((y, a, y as f64 + b)),
+        }
+    }
+
+    exits.into_iter().map(|(y, a, end)| {
+        let steps = rsd.steps_to((0, n-1, y, a));
+        Ok(Occurrence { exit: (y as f64 + a, end), matching: Matching::new(ps.clone(), qs.clone(), steps)? })
+    }).collect()
+}
+
+/// Returns the interval on qs of every occurrence of ps along qs below threshold epsilon (see [`all_matchings`]).
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn partial_curve_all(ps: Curve, qs: Curve, eps: f64) -> Result<Vec<(f64, f64)>, PcmError> {
+    Ok(all_matchings(ps, qs, eps)?.into_iter().map(|occurrence| occurrence.matching.covered_interval()).collect())
+}
+
+/// Compute steps of a match of ps against qs below threshold epsilon, with the boundary conditions of the match mode.
+pub fn match_steps<P: Point>(ps: Curve<P>, qs: Curve<P>, eps: f64, mode: MatchMode) -> Result<Option<Vec<(f64, f64)>>, PcmError> {
+    let fsd = FSD::try_new(ps, qs, eps)?;
+    Ok(fsd.to_rsd_with(mode).pcm_steps())
+}
+
+/// Steps of a match as (parameter on ps, parameter on qs), independent of which curve was matched onto a subcurve of the other.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrientedSteps {
+    pub steps: Vec<(f64, f64)>,
+    /// Whether qs was matched onto (a subcurve of) ps instead of the other way around.
+    pub swapped: bool,
+}
+
+/// Like [`match_steps`], but if swap is set qs is matched onto (a subcurve of) ps instead.
+/// 
+/// Steps are returned in the orientation of the arguments either way, so callers do not have to swap arguments and re-interpret coordinates themselves.
+pub fn oriented_match_steps(ps: Curve, qs: Curve, eps: f64, mode: MatchMode, swap: bool) -> Result<Option<OrientedSteps>, PcmError> {
+    let opt_steps = if swap {
+        match_steps(qs, ps, eps, mode)?.map(|steps| steps.into_iter().map(|(j, i)| (i, j)).collect())
+    } else {
+        match_steps(ps, qs, eps, mode)?
+    };
+    Ok(opt_steps.map(|steps| OrientedSteps { steps, swapped: swap }))
+}
+
+/// Start and end parameter of a subcurve.
+type Interval = (f64, f64);
+
+/// Returns the matched intervals on ps and qs (if a partial match exists), where qs is matched onto a subcurve of ps if swap is set.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn partial_curve_oriented(ps: Curve, qs: Curve, eps: f64, swap: bool) -> Result<Option<(Interval, Interval, bool)>, PcmError> {
+    let opt = oriented_match_steps(ps, qs, eps, MatchMode::SubcurveOfQ, swap)?;
+    Ok(opt.map(|OrientedSteps { steps, swapped }| {
+        let (first, last) = (steps[0], *steps.last().unwrap());
+        ((first.0, last.0), (first.1, last.1), swapped)
+    }))
+}
+
+/// Largest fraction of qs (in arc length) covered by a subcurve ps partially matches against (see [`max_coverage_steps`]), None without a match.
+fn max_coverage(ps: &Curve, qs: &Curve, eps: f64) -> Result<Option<f64>, PcmError> {
+    max_coverage_steps(ps.clone(), qs.clone(), eps)?.map(|steps| Ok(Matching::new(ps.clone(), qs.clone(), steps)?.coverage())).transpose()
+}
+
+/// Largest fraction of qs (in arc length) covered by a subcurve ps partially matches against (see [`max_coverage_steps`]), zero without a match.
+pub fn match_coverage(ps: &Curve, qs: &Curve, eps: f64) -> Result<f64, PcmError> {
+    Ok(max_coverage(ps, qs, eps)?.unwrap_or(0.))
+}
+
+/// Check whether ps and qs follow the same route: both partially match each other, each covering at least min_coverage (fraction of arc length) of the other.
+/// 
+/// Unlike a single partial match, a short probe does not count as the same route as a long target it happens to lie along.
+pub fn try_same_route(ps: &Curve, qs: &Curve, eps: f64, min_coverage: f64) -> Result<bool, PcmError> {
+    let covers = |ps, qs| -> Result<bool, PcmError> { Ok(max_coverage(ps, qs, eps)?.is_some_and(|coverage| coverage >= min_coverage)) };
+    Ok(covers(ps, qs)? && covers(qs, ps)?)
+}
+
+/// Check whether ps and qs follow the same route (mutual partial matches each covering at least min_coverage of the other).
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn same_route(ps: Curve, qs: Curve, eps: f64, min_coverage: f64) -> Result<bool, PcmError> {
+    try_same_route(&ps, &qs, eps, min_coverage)
+}
+
+/// Check steps form a matching between ps and qs within threshold eps:
+/// steps are non-empty, lie within the parameter ranges of both curves, are monotonic, and every step pairs points within eps (up to [`EPS`]).
+pub fn validate_steps<P: Point>(ps: &Curve<P>, qs: &Curve<P>, steps: &[(f64, f64)], eps: f64) -> Result<(), PcmError> {
+    validate_steps_with(ps, qs, steps, eps, true)
+}
+
+/// Like [`validate_steps`], but for a weak matching (steps may walk backwards along either curve).
+pub fn validate_weak_steps<P: Point>(ps: &Curve<P>, qs: &Curve<P>, steps: &[(f64, f64)], eps: f64) -> Result<(), PcmError> {
+    validate_steps_with(ps, qs, steps, eps, false)
+}
+
+fn validate_steps_with<P: Point>(ps: &Curve<P>, qs: &Curve<P>, steps: &[(f64, f64)], eps: f64, monotone: bool) -> Result<(), PcmError> {
+    if steps.is_empty() {
+        return Err(PcmError::EmptySteps);
+    }
+    let (n, m) = (ps.len() as f64, qs.len() as f64);
+    for &(i, j) in steps {
+        if !(0. <= i && i <= n - 1. && 0. <= j && j <= m - 1.) {
+            return Err(PcmError::StepOutOfRange { step: (i, j) });
+        }
+    }
+    for w in steps.windows(2) {
+        let ((i1, j1), (i2, j2)) = (w[0], w[1]);
+        if monotone && (i1 > i2 || j1 > j2) {
+            return Err(PcmError::NonMonotoneSteps { from: (i1, j1), to: (i2, j2) });
+        }
+    }
+    for &(i, j) in steps {
+        let d = point_on(ps, i).distance(point_on(qs, j));
+        if d.is_nan() || d >= eps + EPS {
+            return Err(PcmError::StepExceedsThreshold { step: (i, j), distance: d, eps });
+        }
+    }
+    Ok(())
+}
+
+/// Distance per vertex of ps to the point on qs it is matched against by the steps.
+/// 
+/// A vertex is matched against the first point of qs the steps reach it with (steps are linearly interpolated in between).
+pub fn residuals(ps: &Curve, qs: &Curve, steps: &[(f64, f64)]) -> Vec<f64> {
+    let mut result = vec![];
+    if steps.is_empty() || qs.is_empty() { return result; }
+    let mut k = 0;
+    for (i, p) in ps.iter().enumerate() {
+        let x = i as f64;
+        // Walk to the first step at or beyond vertex i.
+        while k < steps.len() - 1 && steps[k].0 < x {
+            k += 1;
+        }
+        let (x1, y1) = if k > 0 { steps[k-1] } else { steps[k] };
+        let (x2, y2) = steps[k];
+        let y = if x2 > x1 { y1 + (y2 - y1) * (x - x1) / (x2 - x1) } else { y2 };
+        result.push(p.distance(point_on(qs, y)));
+    }
+    result
+}
+
+/// Like [`partial_curve`], additionally returns the distance of every vertex of ps to its matched point on qs.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn partial_curve_residuals(ps: Curve, qs: Curve, eps: f64) -> Result<Option<(f64, f64, Vec<f64>)>, PcmError> {
+    let fsd = FSD::try_new(ps.clone(), qs.clone(), eps)?;
+    let Some(steps) = fsd.to_rsd().pcm_steps() else { return Ok(None) };
+    let start = steps[0].1;
+    let end = steps.last().unwrap().1;
+    Ok(Some((start, end, residuals(&ps, &qs, &steps))))
+}
+
+/// Compute steps of the partial match which covers the longest subcurve of qs (in arc length).
+/// 
+/// Every connected free interval on the left boundary of the FSD is a candidate start (starting at its lowest point dominates starting higher up).
+/// Per start we walk to the highest reachable point on the right boundary and keep the pair with the largest arc length in between.
+/// 
+/// Every candidate start propagates its own RSD, so with up to m - 1 left boundary components this takes O(n m^2) time (against O(n m) for a single match),
+/// though candidates run in parallel if enabled.
+pub fn max_coverage_steps(ps: Curve, qs: Curve, eps: f64) -> Result<Option<Vec<(f64, f64)>>, PcmError> {
+    let fsd = FSD::try_new(ps, qs.clone(), eps)?;
+    let n = fsd.n;
+    let m = fsd.m;
+
+    // Only consider the lowest interval of a connected left boundary component.
+    let starts: Vec<(usize, f64)> = (0..m-1).filter_map(|y| {
+        let LineBoundary { a, b: _ } = fsd.segs[(0, 0, y)]?;
+        if y > 0 && a == 0. && matches!(fsd.segs[(0, 0, y-1)], Some(LineBoundary { a: _, b: 1. })) {
+            return None;
+        }
+        Some((y, y as f64 + a))
+    }).collect();
+
+    // Candidate starts are independent, evaluate them in parallel (if enabled).
+    let candidates = parallel::map(starts, |(y, start)| {
+        // Highest reachable point on the right boundary.
+        let rsd = fsd.to_rsd_from(y);
+        let top = (0..m-1).rev().find(|&j| rsd.segs[(0, n-1, j)].is_some())?;
+        let LineBoundary { a: _, b } = rsd.segs[(0, n-1, top)].unwrap();
+        let mut steps = rsd.steps_to((0, n-1, top, b));
+
+        // The greedy walk may arrive higher on the left boundary, continue down to the lowest start.
+        if steps[0].1 > start {
+            steps.insert(0, (0., start));
+        }
+
+        let coverage = arc_length(&qs, steps.last().unwrap().1) - arc_length(&qs, start);
+        Some((coverage, steps))
+    });
+
+    let mut best: Option<(f64, Vec<(f64, f64)>)> = None;
+    for (coverage, steps) in candidates.into_iter().flatten() {
+        if best.as_ref().is_none_or(|(c, _)| coverage > *c) {
+            best = Some((coverage, steps));
+        }
+    }
+
+    Ok(best.map(|(_, steps)| steps))
+}
+
+/// Positions (parameter on ps, parameter on qs) walked along by a matching.
+type Steps = Vec<(f64, f64)>;
+
+/// Point of the free space of point p0 + s (p1 - p0) against point q0 + t (q1 - q0) with the largest s, over t in [t0, 1] (as (s, t)).
+/// 
+/// The free space within a cell is convex, so its upper boundary in s is concave in t:
+/// it peaks where the ellipse is furthest along (p0, p1), or at the end of [t0, 1] closest to that.
+fn furthest_free<P: Point>(p0: P, p1: P, q0: P, q1: P, t0: f64, eps: f64) -> Option<(f64, f64)> {
+    let (u, v, w) = (p1 - p0, q1 - q0, p0 - q0);
+    let vv = v.dot(v);
+    let mut ts = vec![t0, 1.];
+    if vv > 0. {
+        // Parts orthogonal to (q0, q1), the ellipse is furthest along (p0, p1) where its distance to the supporting line of (q0, q1) is eps.
+        let (u_, w_) = (u - v.scale(u.dot(v) / vv), w - v.scale(w.dot(v) / vv));
+        let (a, b, c) = (u_.dot(u_), 2. * u_.dot(w_), w_.dot(w_) - eps * eps);
+        if a > 0. && b * b - 4. * a * c >= 0. {
+            let s = (-b + (b * b - 4. * a * c).sqrt()) / (2. * a);
+            ts.push(((w + u.scale(s)).dot(v) / vv).clamp(t0, 1.));
+        }
+    }
+    ts.into_iter()
+        .filter_map(|t| free_boundary(q0 + v.scale(t), p0, p1, eps).map(|LineBoundary { a: _, b }| (b, t)))
+        .max_by(|(s0, _), (s1, _)| s0.total_cmp(s1))
+}
+
+/// Prefix length, boundary position walked back from, and the final step inside a cell (if the prefix ends there).
+type PrefixEnd = (f64, FSDPosition, Option<(f64, f64)>);
+
+/// Compute the longest prefix of ps (as parameter on ps) that partially matches a subcurve of qs, with the steps of that match.
+/// 
+/// The prefix may end inside a cell: the reachable free space of a cell is walked into straight from its lowest entry point, up to its point furthest along ps.
+/// Returns None if not even the first point of ps lies within eps of qs.
+pub fn longest_prefix_steps(ps: Curve, qs: Curve, eps: f64) -> Result<Option<(f64, Steps)>, PcmError> {
+    let rsd = FSD::try_new(ps.clone(), qs.clone(), eps)?.to_rsd();
+    let n = rsd.n;
+    let m = rsd.m;
+
+    // Furthest reachable position along ps, either on a vertical (at its lowest point) or at the end of a horizontal,
+    // or inside a cell (reached in a final step from a position on its boundary).
+    let mut best: Option<PrefixEnd> = None;
+    for x in 0..n {
+        for y in 0..m-1 {
+            if let Some(LineBoundary { a, b: _ }) = rsd.segs[(0, x, y)] && best.is_none_or(|(t, _, _)| x as f64 > t) {
+                best = Some((x as f64, (0, x, y, a), None));
+            }
+        }
+    }
+    for j in 0..m {
+        for y in 0..n-1 {
+            if let Some(LineBoundary { a: _, b }) = rsd.segs[(1, j, y)] && best.is_none_or(|(t, _, _)| y as f64 + b > t) {
+                best = Some((y as f64 + b, (1, j, y, b), None));
+            }
+        }
+    }
+    for i in 0..n-1 {
+        for j in 0..m-1 {
+            // Entering from the bottom every free point of the cell to the right of the entry is reachable, from the left every one above it.
+            let (entry, t0) = match (rsd.segs[(1, j, i)], rsd.segs[(0, i, j)]) {
+                (Some(LineBoundary { a, b: _ }), _) => ((1, j, i, a), 0.),
+                (None, Some(LineBoundary { a, b: _ })) => ((0, i, j, a), a),
+                _ => continue,
+            };
+            if let Some((s, t)) = furthest_free(ps[i], ps[i+1], qs[j], qs[j+1], t0, eps) && best.is_none_or(|(u, _, _)| i as f64 + s > u) {
+                best = Some((i as f64 + s, entry, Some((i as f64 + s, j as f64 + t))));
+            }
+        }
+    }
+
+    Ok(best.map(|(t, position, last)| {
+        let mut steps = rsd.steps_to(position);
+        steps.extend(last);
+        // Offsets snapped off non-free cornerpoints (see `snap_boundary`) start at zero.
+        let snapped = |c: f64| if c <= f64::MIN_POSITIVE { 0. } else { c };
+        (t, steps.into_iter().map(|(i, j)| (snapped(i), snapped(j))).collect())
+    }))
+}
+
+/// Returns the longest prefix of ps (as parameter on ps) that partially matches a subcurve of qs, together with that subcurve.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn partial_curve_longest_prefix(ps: Curve, qs: Curve, eps: f64) -> Result<Option<(f64, f64, f64)>, PcmError> {
+    let opt = longest_prefix_steps(ps, qs, eps)?;
+    Ok(opt.map(|(t, steps)| (t, steps[0].1, steps.last().unwrap().1)))
+}
+
+/// Weak lower bound on the threshold at which ps partially matches a subcurve of qs, cheap to compute (O((n + m) log m)).
+/// 
+/// Every vertex of ps has to be matched, so below its distance to qs the vertical FSD boundary of that vertex is fully blocked.
+/// The bound ignores the order along qs (and the boundaries between vertices), see [`try_partial_curve_or_bound`] for a tight one.
+pub fn eps_lower_bound(ps: &Curve, qs: &Curve) -> Result<f64, PcmError> {
+    validate_curve(ps)?;
+    validate_curve(qs)?;
+    let bvh = SegmentBvh::new(qs);
+    Ok(ps.iter().map(|&p| bvh.nearest_distance(p)).fold(0., f64::max))
+}
+
+/// Like [`try_partial_curve`], but on failure returns a lower bound on the threshold that is required instead (so thresholds can be tuned in a single call).
+/// 
+/// The bound is the largest threshold found to fail while searching the partial Fréchet distance (see [`partial_frechet_distance`]),
+/// so it lies within `EPS * EPS` (relative) below it, and never below eps itself.
+/// Finding it costs about as much as computing the distance (a binary search over the critical values, then bisection).
+pub fn try_partial_curve_or_bound(ps: Curve, qs: Curve, eps: f64) -> Result<Result<Interval, f64>, PcmError> {
+    match try_partial_curve(ps.clone(), qs.clone(), eps)? {
+        Some(interval) => Ok(Ok(interval)),
+        None => Ok(Err(threshold_bracket(&ps, &qs, MatchMode::SubcurveOfQ)?.0.max(eps))),
+    }
+}
+
+/// Returns the subcurve of qs (if it exists) with Fréchet distance to ps below threshold epsilon, otherwise a lower bound on the threshold required.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn partial_curve_or_bound(ps: Curve, qs: Curve, eps: f64) -> Result<(Option<Interval>, Option<f64>), PcmError> {
+    match try_partial_curve_or_bound(ps, qs, eps)? {
+        Ok(interval) => Ok((Some(interval), None)),
+        Err(bound) => Ok((None, Some(bound)))
+    }
+}
+
+/// Smallest threshold (up to tolerance) at which ps partially matches a subcurve of qs, found by bisection on the decision.
+pub fn min_partial_eps(ps: &Curve, qs: &Curve, tolerance: f64) -> Result<f64, PcmError> {
+    if !(tolerance.is_finite() && tolerance > 0.) {
+        return Err(PcmError::InvalidArgument(format!("Tolerance should be positive and finite, got {tolerance}.")));
+    }
+    let decide = |eps: f64| -> Result<bool, PcmError> {
+        Ok(FSD::try_new(ps.clone(), qs.clone(), eps)?.to_rsd().check_pcm())
+    };
+    // Every pair of points lies within the largest vertex distance, so the full diagram is free there.
+    let mut hi = ps.iter().flat_map(|p| qs.iter().map(|q| p.distance(*q))).fold(tolerance, f64::max);
+    let mut lo = 0.;
+    decide(hi)?;
+    while hi - lo > tolerance {
+        let mid = 0.5 * (lo + hi);
+        if decide(mid)? { hi = mid; } else { lo = mid; }
+    }
+    Ok(hi)
+}
+
+/// Distance of the point on segment (a, b) equidistant to points u and v (if any), where the free space may start or stop being monotonically passable.
+fn equidistant_value(a: Vector, b: Vector, u: Vector, v: Vector) -> Option<f64> {
+    let w = v - u;
+    let denom = (b - a).dot(w);
+    if denom == 0. { return None; }
+    let s = (0.5 * (v.dot(v) - u.dot(u)) - a.dot(w)) / denom;
+    if (0. ..=1.).contains(&s) { Some((a + s * (b - a)).distance(u)) } else { None }
+}
+
+/// Thresholds at which the reachable space of ps against qs may change: distances between the endpoints (corners opening), vertex-segment distances (boundaries opening)
+/// and distances to points equidistant to two vertices of the other curve (passages opening between boundaries).
+fn critical_values(ps: &Curve, qs: &Curve) -> Vec<f64> {
+    let mut values = vec![ps[0].distance(qs[0]), ps[ps.len()-1].distance(qs[qs.len()-1])];
+    for (c1, c2) in [(ps, qs), (qs, ps)] {
+        for w in c2.windows(2) {
+            for &p in c1 {
+                values.push(distance_to_segment(p, w[0], w[1]));
+            }
+            for k in 0..c1.len() {
+                for l in k+1..c1.len() {
+                    values.extend(equidistant_value(w[0], w[1], c1[k], c1[l]));
+                }
+            }
+        }
+    }
+    values.sort_by(f64::total_cmp);
+    values.dedup();
+    values
+}
+
+/// Whether ps matches qs below threshold eps with the boundary conditions of the match mode.
+fn decide_mode(ps: &Curve, qs: &Curve, eps: f64, mode: MatchMode) -> Result<bool, PcmError> {
+    Ok(eps > 0. && FSD::try_new(ps.clone(), qs.clone(), eps)?.to_rsd_with(mode).check_pcm())
+}
+
+/// Thresholds (lo, hi) bracketing the smallest threshold at which ps matches qs with the boundary conditions of the match mode:
+/// the decision fails at lo and succeeds at hi, which lie within `EPS * EPS` (relative) of each other.
+///
+/// Brackets the threshold by binary search over the critical values (see [`critical_values`]), then refines it within the bracket by bisection on the decision
+/// (the decision procedure only reports free intervals of some minimal length, so the threshold is slightly above the exact critical value).
+fn threshold_bracket(ps: &Curve, qs: &Curve, mode: MatchMode) -> Result<(f64, f64), PcmError> {
+    validate_curve(ps)?;
+    validate_curve(qs)?;
+    // Every pair of points lies within the largest vertex distance, so the full diagram is free beyond it.
+    let max = ps.iter().flat_map(|p| qs.iter().map(|q| p.distance(*q))).fold(EPS, f64::max);
+    let mut candidates = critical_values(ps, qs);
+    candidates.push(2. * max);
+
+    // Smallest critical value with a match (the decision is monotone in the threshold).
+    let (mut lo_k, mut hi_k) = (0, candidates.len() - 1);
+    while lo_k < hi_k {
+        let mid = (lo_k + hi_k) / 2;
+        if decide_mode(ps, qs, candidates[mid], mode)? { hi_k = mid; } else { lo_k = mid + 1; }
+    }
+    let mut hi = candidates[hi_k];
+    let mut lo = if hi_k > 0 { candidates[hi_k - 1] } else { 0. };
+
+    while hi - lo > EPS * EPS * hi.max(1.) {
+        let mid = 0.5 * (lo + hi);
+        if decide_mode(ps, qs, mid, mode)? { hi = mid; } else { lo = mid; }
+    }
+    Ok((lo, hi))
+}
+
+/// Smallest threshold at which ps matches qs with the boundary conditions of the match mode (see [`threshold_bracket`]), with the steps of a match at that threshold.
+fn min_threshold(ps: &Curve, qs: &Curve, mode: MatchMode) -> Result<(f64, Steps), PcmError> {
+    let (_, hi) = threshold_bracket(ps, qs, mode)?;
+    let steps = FSD::new(ps.clone(), qs.clone(), hi).to_rsd_with(mode).pcm_steps().ok_or(PcmError::InconsistentDiagram("Decision and steps disagree at the minimal threshold.".to_string()))?;
+    Ok((hi, steps))
+}
+
+/// Partial Fréchet distance: smallest threshold at which ps partially matches a subcurve of qs, with a matching at that threshold.
+pub fn partial_frechet_distance(ps: &Curve, qs: &Curve) -> Result<(f64, Matching), PcmError> {
+    let (eps, steps) = min_threshold(ps, qs, MatchMode::SubcurveOfQ)?;
+    Ok((eps, Matching::new(ps.clone(), qs.clone(), steps)?))
+}
+
+/// Fréchet distance between ps and qs (both matched end-to-end), or an error on malformed input.
+pub fn try_frechet_distance(ps: &Curve, qs: &Curve) -> Result<f64, PcmError> {
+    Ok(min_threshold(ps, qs, MatchMode::Full)?.0)
+}
+
+/// Returns the Fréchet distance between ps and qs (both matched end-to-end).
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn frechet_distance(ps: Curve, qs: Curve) -> Result<f64, PcmError> {
+    try_frechet_distance(&ps, &qs)
+}
+
+/// Returns the partial Fréchet distance of ps to qs with the interval on qs matched at that threshold.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn partial_curve_distance(ps: Curve, qs: Curve) -> Result<(f64, f64, f64), PcmError> {
+    let (eps, matching) = partial_frechet_distance(&ps, &qs)?;
+    let (start, end) = matching.covered_interval();
+    Ok((eps, start, end))
+}
+
+/// Minimal partial matching threshold (see [`min_partial_eps`]) of every curve in ps_family against every curve in qs_family.
+/// Row i holds the thresholds of `ps_family[i]`, pairs are evaluated in parallel (if enabled).
+pub fn min_eps_matrix(ps_family: &[Curve], qs_family: &[Curve], tolerance: f64) -> Result<Vec<Vec<f64>>, PcmError> {
+    let pairs: Vec<(usize, usize)> = (0..ps_family.len()).flat_map(|i| (0..qs_family.len()).map(move |j| (i, j))).collect();
+    let values = parallel::map(pairs, |(i, j)| min_partial_eps(&ps_family[i], &qs_family[j], tolerance)).into_iter().collect::<Result<Vec<f64>, PcmError>>()?;
+    Ok((0..ps_family.len()).map(|i| values[i * qs_family.len()..(i + 1) * qs_family.len()].to_vec()).collect())
+}
+
+/// Returns the subcurve of qs (if any partial match exists) with the longest arc length that has Fréchet distance to ps below threshold epsilon.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn partial_curve_max_coverage(ps: Curve, qs: Curve, eps: f64) -> Result<Option<(f64, f64)>, PcmError> {
+    let opt_steps = max_coverage_steps(ps, qs, eps)?;
+    Ok(opt_steps.map(|steps| (steps[0].1, steps.last().unwrap().1)))
+}
@@ -1,5 +1,5 @@
-use pyo3::pyclass;
-use pyo3::pyfunction;
+#[cfg(feature = "python")]
+use pyo3::{pyclass, pyfunction};
 use std::collections::BTreeMap as Map;
 use std::collections::BTreeSet as Set;
 use std::iter::zip;
@@ -19,7 +19,7 @@ const DEBUG: bool = false;
 type FSD2 = Vec<Vec<Vec<OptLineBoundary>>>; // (x, y, horizontal|vertical)
 
 /// Graph structure which stores relevant information a simple undirected graph.
-#[pyclass]
+#[cfg_attr(feature = "python", pyclass)]
 pub struct ExponentialGraph {
     /// Nodes with (x,y) coordinate.
     nodes: Map<NID, Vector>,
@@ -227,7 +227,7 @@ fn is_top_empty(fsd: &FSD2) -> bool {
     !extract_top(fsd).into_iter().any(|lb| lb.is_some())
 }
 
-#[pyfunction]
+#[cfg_attr(feature = "python", pyfunction)]
 pub fn make_exponential_graph(vertices: Vec<(NID, Vector)>, edges: Vec<(NID, NID)>) -> ExponentialGraph {
     ExponentialGraph::new(vertices, edges)
 }
@@ -278,7 +278,7 @@ fn print_fsd(fsd: &FSD2) {
 /// 
 /// todo (optimization): Lazily evaluate FDij's, initiate by checking solely the left boundary of every FDij.
 #[allow(non_snake_case)]
-#[pyfunction]
+#[cfg_attr(feature = "python", pyfunction)]
 pub fn partial_curve_graph_exponential(graph: &ExponentialGraph, ps: Curve, eps: f64) -> Option<Vec<NID>> {
 
     // Initiate FDij's and initial reachable paths.
@@ -1,5 +1,5 @@
-use pyo3::pyclass;
-use pyo3::pyfunction;
+#[cfg(feature = "python")]
+use pyo3::{pyclass, pyfunction};
 
 use crate::curve::Curve;
 use crate::lineboundary::print_lb;
@@ -123,7 +123,7 @@ impl Ord        for PathPointer {
 /// LinearGraph structure which stores relevant information a simple undirected graph.
 /// Essentially a graph consists of a collection of nodes and edges.
 /// For simplicity we assume the graph to be vectorized, which means that edges have no curvature (beyond a straight line segment between the vertices the edge connects).
-#[pyclass]
+#[cfg_attr(feature = "python", pyclass)]
 pub struct Graph {
     /// Adjacent nodes.
     adj: Map<NID, Set<NID>> ,
@@ -134,8 +134,7 @@ pub struct Graph {
     nid_list: Vec<NID>,
     /// Node vectors.
     vec_list: Vec<Vector>,
-    /// Functionality not used, but acts as a sanity check mechanism to check transformation is the identity function.
-    #[allow(dead_code)]
+    /// Back-link for NIDs (to their position in `nid_list`), also acts as a sanity check mechanism to check transformation is the identity function.
     nid_map: Map<NID, NID>,
 
     /// Map edge to unique identifier.
@@ -153,6 +152,11 @@ impl Graph {
         path.iter().map(|&u| self.nid_list[u]).collect()
     }
 
+    /// Position of a node (if it is in the graph).
+    pub fn position(&self, nid: NID) -> Option<Vector> {
+        self.nid_map.get(&nid).map(|&i| self.vec_list[i])
+    }
+
 
     /// Store a graph struct, re-used as we apply to different curves.
     pub fn new(vertices: Vec<(NID, Vector)>, edges: Vec<(NID, NID)>) -> Self {
@@ -227,7 +231,7 @@ impl Graph {
 
 
 /// Make a graph out of a list of vertices and a list of edges.
-#[pyfunction]
+#[cfg_attr(feature = "python", pyfunction)]
 pub fn make_graph(vertices: Vec<(NID, Vector)>, edges: Vec<(NID, NID)>) -> Graph {
     Graph::new(vertices, edges)
 }
@@ -406,7 +410,7 @@ fn unique<T>(elements: Vec<T>) -> Set<T> where T: Ord {
 /// * Creating initial events.
 /// * Sweeping.
 #[allow(non_snake_case)]
-#[pyfunction]
+#[cfg_attr(feature = "python", pyfunction)]
 pub fn partial_curve_graph(graph: &Graph, curve: Curve, eps: f64) -> Option<Vec<NID>> {
 
     let n = curve.len() - 1; // Number of intervals.
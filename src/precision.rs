@@ -0,0 +1,64 @@
+use ndarray::s;
+
+use crate::{curve::Curve, error::PcmError, float::Float, fsd::FSD, predicates::Predicates, vector::Vector, LineBoundary};
+
+
+/// Outcome of running the same partial curve matching case in single and double precision, and with exact predicates.
+///
+/// Single and double precision run with floating point predicates, the decision with [`Predicates::Exact`] is the reference both are compared against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrecisionReport {
+    /// Decision in double precision.
+    pub decision_f64: bool,
+    /// Decision in single precision.
+    pub decision_f32: bool,
+    /// Decision with exact predicates (in double precision).
+    pub decision_exact: bool,
+    /// Lowest reachable point on the right boundary (as parameter on qs) in double precision.
+    pub end_f64: Option<f64>,
+    /// Lowest reachable point on the right boundary (as parameter on qs) in single precision.
+    pub end_f32: Option<f64>,
+    /// Robustness of the double precision decision (see [`FSD::robustness`]).
+    pub slack: f64,
+}
+impl PrecisionReport {
+
+    /// Check both precisions agree with the exact decision and (up to tolerance) with each other on where the match ends.
+    pub fn is_consistent(&self, tolerance: f64) -> bool {
+        self.decision_f64 == self.decision_exact && self.decision_f32 == self.decision_exact && match (self.end_f64, self.end_f32) {
+            (Some(e1), Some(e2)) => (e1 - e2).abs() <= tolerance,
+            (None, None) => true,
+            _ => false
+        }
+    }
+
+    /// Check the double precision decision can flip under rounding.
+    pub fn is_fragile(&self) -> bool {
+        self.slack < 0.
+    }
+}
+
+/// Lowest reachable point on the right boundary of a reachability-space diagram.
+fn lowest_end<F: Float>(rsd: &FSD<F>) -> Option<f64> {
+    rsd.segs.slice(s![0, rsd.n-1, ..rsd.m-1]).iter().enumerate()
+        .find_map(|(j, lb)| lb.map(|LineBoundary { a, b: _ }| j as f64 + a.to_f64()))
+}
+
+/// Run the partial curve match of ps against qs in single and double precision, and with exact predicates, and report the results.
+///
+/// Single precision is the `FSD<f32>` diagram of both curves rounded to f32.
+/// Disagreement flags inputs on which the result is numerically fragile and should be treated with caution.
+pub fn precision_check(ps: &Curve, qs: &Curve, eps: f64) -> Result<PrecisionReport, PcmError> {
+    let rsd64 = FSD::try_new(ps.clone(), qs.clone(), eps)?.to_rsd();
+    let (ps32, qs32): (Curve<Vector<f32>>, Curve<Vector<f32>>) = (ps.iter().map(|p| p.cast()).collect(), qs.iter().map(|q| q.cast()).collect());
+    let rsd32 = FSD::<f32>::compute(&ps32, &qs32, eps, Predicates::Float).to_rsd();
+    let exact = FSD::<f64>::compute(ps, qs, eps, Predicates::Exact).to_rsd();
+    Ok(PrecisionReport {
+        decision_f64: rsd64.check_pcm(),
+        decision_f32: rsd32.check_pcm(),
+        decision_exact: exact.check_pcm(),
+        end_f64: lowest_end(&rsd64),
+        end_f32: lowest_end(&rsd32),
+        slack: rsd64.robustness(),
+    })
+}
@@ -0,0 +1,186 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{lineboundary::OptLineBoundary, vector::Point, LineBoundary};
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+
+
+/// How the free space of a point against a segment is decided when constructing a diagram, see [`crate::FSD::new_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Predicates {
+    /// Plain floating point arithmetic (fastest).
+    /// Degenerate input (points at exactly eps distance, boundaries tangent to the disc) may go either way, and intervals shorter than 1e-4 are dropped.
+    #[default]
+    Float,
+    /// Adaptive precision: floating point with an error bound, falling back to exact arithmetic whenever the sign is uncertain.
+    /// Points are free when strictly closer than eps (so exactly tangent boundaries are empty), corners and boundary endpoints agree by construction.
+    ///
+    /// Exactness assumes coordinates and eps below about 1e70 (the fourth-degree tangency predicate would overflow beyond), and no underflow.
+    Exact,
+}
+
+
+// =================================
+// === Floating point expansions ===
+// =================================
+
+/// Sum as (rounded sum, rounding error).
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let b_virtual = s - a;
+    (s, (a - (s - b_virtual)) + (b - b_virtual))
+}
+
+/// Product as (rounded product, rounding error).
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let p = a * b;
+    (p, a.mul_add(b, -p))
+}
+
+/// Exact value as a sum of non-overlapping components of increasing magnitude (Shewchuk).
+#[derive(Debug, Clone, Default)]
+struct Expansion(Vec<f64>);
+impl Expansion {
+
+    /// Exact difference of two doubles.
+    fn diff(a: f64, b: f64) -> Expansion {
+        let (s, err) = two_sum(a, -b);
+        Expansion(vec![err, s])
+    }
+
+    /// Exact product of two doubles.
+    fn product(a: f64, b: f64) -> Expansion {
+        let (p, err) = two_product(a, b);
+        Expansion(vec![err, p])
+    }
+
+    /// Add a double (grow-expansion, preserving the component invariant).
+    fn grow(&mut self, b: f64) {
+        let mut q = b;
+        for x in self.0.iter_mut() {
+            let (s, err) = two_sum(q, *x);
+            *x = err;
+            q = s;
+        }
+        self.0.push(q);
+        self.0.retain(|&x| x != 0.);
+    }
+
+    fn add(mut self, rhs: &Expansion) -> Expansion {
+        for &b in &rhs.0 {
+            self.grow(b);
+        }
+        self
+    }
+
+    fn neg(self) -> Expansion {
+        Expansion(self.0.into_iter().map(|x| -x).collect())
+    }
+
+    fn sub(self, rhs: &Expansion) -> Expansion {
+        self.add(&rhs.clone().neg())
+    }
+
+    fn mul(&self, rhs: &Expansion) -> Expansion {
+        let mut result = Expansion::default();
+        for &a in &self.0 {
+            for &b in &rhs.0 {
+                result = result.add(&Expansion::product(a, b));
+            }
+        }
+        result
+    }
+
+    /// Sign of the exact value, the largest component dominates all others.
+    fn sign(&self) -> f64 {
+        self.0.last().map_or(0., |x| x.signum())
+    }
+}
+
+/// Exact dot product of differences (a - b) and (c - d), per coordinate.
+fn exact_dot(a: &[f64], b: &[f64], c: &[f64], d: &[f64]) -> Expansion {
+    (0..a.len()).fold(Expansion::default(), |sum, k| sum.add(&Expansion::diff(a[k], b[k]).mul(&Expansion::diff(c[k], d[k]))))
+}
+
+
+// ==================
+// === Predicates ===
+// ==================
+
+/// Relative error bound of the floating point filters, generous enough for points of up to a couple dozen dimensions.
+const FILTER: f64 = 64. * f64::EPSILON;
+
+/// Sign of value, unless its magnitude falls within the error bound (then None).
+fn filtered(value: f64, bound: f64) -> Option<f64> {
+    (value.abs() > bound).then(|| value.signum())
+}
+
+/// Whether point p lies strictly within distance eps of point q.
+pub(crate) fn inside<P: Point>(p: P, q: P, eps: f64) -> bool {
+    let (d2, e2) = ((p - q).dot(p - q), eps * eps);
+    let sign = filtered(e2 - d2, FILTER * (e2 + d2)).unwrap_or_else(|| {
+        let (p, q) = (p.coordinates(), q.coordinates());
+        Expansion::product(eps, eps).sub(&exact_dot(&p, &q, &p, &q)).sign()
+    });
+    sign > 0.
+}
+
+/// Whether p projects strictly within segment (q0, q1), that is `(p - q0)·v > 0` and `(q1 - p)·v > 0` with `v = q1 - q0`.
+fn projects_inside<P: Point>(p: P, q0: P, q1: P) -> bool {
+    let v = q1 - q0;
+    [(p, q0), (q1, p)].into_iter().all(|(a, b)| {
+        let value = (a - b).dot(v);
+        let sign = filtered(value, FILTER * (a - b).dot(a - b).sqrt() * v.dot(v).sqrt()).unwrap_or_else(|| {
+            let (a, b, q0, q1) = (a.coordinates(), b.coordinates(), q0.coordinates(), q1.coordinates());
+            exact_dot(&a, &b, &q1, &q0).sign()
+        });
+        sign > 0.
+    })
+}
+
+/// Whether the line through q0 and q1 passes strictly within distance eps of p,
+/// that is `eps²|v|² - (|l|²|v|² - (l·v)²) > 0` with `l = p - q0` and `v = q1 - q0` (Lagrange's identity in the exact case).
+fn meets_line<P: Point>(p: P, q0: P, q1: P, eps: f64) -> bool {
+    let (l, v) = (p - q0, q1 - q0);
+    let (ll, vv, lv, e2) = (l.dot(l), v.dot(v), l.dot(v), eps * eps);
+    let value = e2 * vv - (ll * vv - lv * lv);
+    let sign = filtered(value, FILTER * (e2 * vv + 2. * ll * vv)).unwrap_or_else(|| {
+        let (p, q0, q1) = (p.coordinates(), q0.coordinates(), q1.coordinates());
+        let (l, v): (Vec<_>, Vec<_>) = (0..p.len()).map(|k| (Expansion::diff(p[k], q0[k]), Expansion::diff(q1[k], q0[k]))).unzip();
+        let vv = v.iter().fold(Expansion::default(), |sum, x| sum.add(&x.mul(x)));
+        let mut value = Expansion::product(eps, eps).mul(&vv);
+        for i in 0..p.len() {
+            for j in i+1..p.len() {
+                let cross = l[i].mul(&v[j]).sub(&l[j].mul(&v[i]));
+                value = value.sub(&cross.mul(&cross));
+            }
+        }
+        value.sign()
+    });
+    sign > 0.
+}
+
+/// Free interval on segment (q0, q1) of points strictly within distance eps of p, with existence and endpoints at cornerpoints decided exactly.
+///
+/// The remaining endpoints are computed in floating point, and nudged where needed to keep `0 < a < b < 1` consistent with the exact decisions.
+pub(crate) fn exact_boundary<P: Point>(p: P, q0: P, q1: P, eps: f64) -> OptLineBoundary {
+    let (c0, c1) = (inside(p, q0, eps), inside(p, q1, eps));
+    if c0 && c1 { // The disc is convex.
+        return Some(LineBoundary { a: 0., b: 1. });
+    }
+    if !(c0 || c1 || (projects_inside(p, q0, q1) && meets_line(p, q0, q1, eps))) {
+        return None;
+    }
+
+    let (l, v) = (p - q0, q1 - q0);
+    let vv = v.dot(v);
+    let tca = l.dot(v) / vv;
+    let thc = ((eps * eps - (l.dot(l) - tca * tca * vv)).max(0.) / vv).sqrt();
+    // Smallest and largest parameters strictly inside the unit interval.
+    let (lo, hi) = (f64::MIN_POSITIVE, 1. - f64::EPSILON / 2.);
+    let a = if c0 { 0. } else { (tca - thc).clamp(lo, hi) };
+    let b = if c1 { 1. } else { (tca + thc).clamp(lo, hi) };
+    let b = if a < b { b } else { f64::from_bits(a.to_bits() + 1).min(hi) };
+    let a = if a < b { a } else { f64::from_bits(b.to_bits() - 1) };
+    Some(LineBoundary { a, b })
+}
@@ -0,0 +1,66 @@
+use crate::{bvh::SegmentBvh, curve::{validate_curve, validate_eps, Curve}, error::PcmError, fsd::FSD, partial_curve::Matching, EPS};
+
+
+/// Target curve prepared once (indexing its segments, see [`SegmentBvh`]) for matching many query curves against it.
+///
+/// Every query is rejected early when some vertex lies beyond eps of the target (see [`SegmentBvh::may_match`]).
+/// Otherwise the diagram is only built against the window of the target between the first segment near the start of the query
+/// and the last segment near its end, which is all a matching can span. For short queries against a long target this window is typically small.
+#[derive(Debug, Clone)]
+pub struct PreparedCurve {
+    curve: Curve,
+    index: SegmentBvh,
+}
+impl PreparedCurve {
+
+    pub fn new(qs: Curve) -> PreparedCurve {
+        let index = SegmentBvh::new(&qs);
+        PreparedCurve { curve: qs, index }
+    }
+
+    /// Like [`PreparedCurve::new`], but reject a malformed curve with an error instead of panicking later on.
+    pub fn try_new(qs: Curve) -> Result<PreparedCurve, PcmError> {
+        validate_curve(&qs)?;
+        Ok(PreparedCurve::new(qs))
+    }
+
+    /// Target curve.
+    pub fn curve(&self) -> &Curve {
+        &self.curve
+    }
+
+    /// Index over the segments of the target curve.
+    pub fn index(&self) -> &SegmentBvh {
+        &self.index
+    }
+
+    /// Range of points (first, last) on the target a matching of ps may span, if any.
+    fn window(&self, ps: &Curve, eps: f64) -> Option<(usize, usize)> {
+        // Slightly widened, so rounding in the free-space computations cannot exclude a segment.
+        let radius = eps + EPS;
+        if !self.index.may_match(ps, radius) {
+            return None;
+        }
+        // A matching starts on a segment near the first point of ps, and ends (no earlier) on a segment near its last point.
+        let first = *self.index.query_disk(ps[0], radius).first()?;
+        let last = *self.index.query_disk(ps[ps.len() - 1], radius).last()?;
+        if first > last + 1 {
+            return None;
+        }
+        Some((first.min(last), first.max(last) + 1))
+    }
+
+    /// Compute a partial curve match (if any) of ps against a subcurve of the target below threshold epsilon, like [`crate::partial_curve_matching`].
+    pub fn partial_curve_matching(&self, ps: &Curve, eps: f64) -> Result<Option<Matching>, PcmError> {
+        validate_curve(ps)?;
+        validate_eps(eps)?;
+        let Some((first, last)) = self.window(ps, eps) else { return Ok(None) };
+        let opt_steps = FSD::from_slices(ps, &self.curve[first..=last], eps).to_rsd().pcm_steps();
+        opt_steps.map(|steps| Matching::new(ps.clone(), self.curve.clone(), steps.into_iter().map(|(i, j)| (i, j + first as f64)).collect())).transpose()
+    }
+
+    /// Returns any subcurve of the target (if it exists) with Fréchet distance to ps below threshold epsilon, like [`crate::try_partial_curve`].
+    pub fn partial_curve(&self, ps: &Curve, eps: f64) -> Result<Option<(f64, f64)>, PcmError> {
+        Ok(self.partial_curve_matching(ps, eps)?.map(|matching| matching.covered_interval()))
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{curve::{validate_curve, Curve}, error::PcmError, fsd::FSD, parallel, partial_curve::{eps_lower_bound, min_partial_eps}};
+
+
+/// Candidate of the dataset with its minimal partial matching threshold, see [`nearest_curves`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Neighbour {
+    /// Index of the candidate into the dataset.
+    pub index: usize,
+    /// Smallest threshold (up to tolerance) at which the query partially matches a subcurve of the candidate.
+    pub eps: f64,
+}
+
+/// Retrieve the k candidates of the dataset with the smallest minimal threshold at which the query partially matches a subcurve of them (ordered by threshold, then index).
+///
+/// Candidates are visited in order of a lower bound on their threshold (the largest distance of a query vertex to the candidate), in batches evaluated in parallel (if enabled).
+/// Once k candidates are found, a candidate not matching at the k-th threshold is rejected by a single decision,
+/// and the search stops at the first lower bound beyond the k-th threshold.
+pub fn nearest_curves(query: &Curve, dataset: &[Curve], k: usize, tolerance: f64) -> Result<Vec<Neighbour>, PcmError> {
+    validate_curve(query)?;
+    let bounds = parallel::map(dataset.iter().enumerate().collect(), |(index, qs)| {
+        eps_lower_bound(query, qs).map(|bound| (bound, index)).map_err(|e| PcmError::Candidate { index, source: Box::new(e) })
+    }).into_iter().collect::<Result<Vec<_>, PcmError>>()?;
+    let mut order = bounds;
+    order.sort_by(|(b1, i1), (b2, i2)| b1.total_cmp(b2).then(i1.cmp(i2)));
+
+    let mut nearest: Vec<Neighbour> = vec![];
+    if k == 0 {
+        return Ok(nearest);
+    }
+    for batch in order.chunks(k) {
+        // Threshold a candidate has to beat to enter the k nearest (if k are found already).
+        let kth = (nearest.len() == k).then(|| nearest[k - 1].eps);
+        if kth.is_some_and(|eps| batch[0].0 > eps) {
+            break;
+        }
+        let results = parallel::map(batch.to_vec(), |(bound, index)| -> Result<Option<Neighbour>, PcmError> {
+            let qs = &dataset[index];
+            if let Some(eps) = kth && (bound > eps || !FSD::from_slices(query, qs, eps).to_rsd().check_pcm()) {
+                return Ok(None);
+            }
+            Ok(Some(Neighbour { index, eps: min_partial_eps(query, qs, tolerance)? }))
+        });
+        for opt_neighbour in results {
+            nearest.extend(opt_neighbour?);
+        }
+        nearest.sort_by(|n1, n2| n1.eps.total_cmp(&n2.eps).then(n1.index.cmp(&n2.index)));
+        nearest.truncate(k);
+    }
+    Ok(nearest)
+}
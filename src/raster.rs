@@ -0,0 +1,123 @@
+#[cfg(feature = "fs")]
+use std::{fs::File, io::Write, path::Path};
+
+#[cfg(feature = "fs")]
+use ndarray::{Array, Dimension};
+use ndarray::{Array2, Array3};
+
+use crate::{curve::{point_on, validate_curve, Curve}, error::PcmError, float::Float, fsd::FSD, lineboundary::LineBoundary};
+
+
+/// Rasterize the free space between curve P (horizontal axis) and curve Q (vertical axis).
+///
+/// Samples `resolution` pixels per FSD cell along both axes, thus the mask has shape `((n-1)*resolution+1, (m-1)*resolution+1)`.
+/// A pixel is true if the corresponding points on P and Q are closer than eps (strictly, like the cornerpoints of [`FSD`]).
+pub fn free_space_mask(ps: &Curve, qs: &Curve, eps: f64, resolution: usize) -> Result<Array2<bool>, PcmError> {
+    validate_curve(ps)?;
+    validate_curve(qs)?;
+    if resolution == 0 {
+        return Err(PcmError::InvalidArgument("Resolution should be at least one pixel per cell.".to_string()));
+    }
+    let w = (ps.len() - 1) * resolution + 1;
+    let h = (qs.len() - 1) * resolution + 1;
+    let r = resolution as f64;
+    Ok(Array2::from_shape_fn([w, h], |(x, y)| {
+        let p = point_on(ps, x as f64 / r);
+        let q = point_on(qs, y as f64 / r);
+        p.distance(q) < eps
+    }))
+}
+
+/// Cell boundaries and corners of a diagram as plain numeric arrays, see [`FSD::to_arrays`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagramArrays {
+    /// Vertical boundaries with shape `(n, m-1, 2)`: the interval `[a, b]` at point x of ps along segment y of qs, NaN if empty.
+    pub verticals: Array3<f64>,
+    /// Horizontal boundaries with shape `(m, n-1, 2)`: the interval `[a, b]` at point x of qs along segment y of ps, NaN if empty.
+    pub horizontals: Array3<f64>,
+    /// Corner points with shape `(n, m)`, true if free (on a RSD: if a reachable interval starts or ends at it).
+    pub corners: Array2<bool>,
+}
+
+impl<F: Float> FSD<F> {
+
+    /// Boundary intervals (free on a FSD, reachable on a RSD) and corners as numeric arrays, for analysis or plotting outside of Rust (e.g. with [`write_npy_f64`]).
+    pub fn to_arrays(&self) -> DiagramArrays {
+        let intervals = |axis: usize| {
+            let (w, h) = self.dims[axis];
+            Array3::from_shape_fn((w, h, 2), |(x, y, k)| match self.segs[(axis, x, y)] {
+                Some(boundary) => { let LineBoundary { a, b } = boundary.to_f64(); [a, b][k] },
+                None => f64::NAN,
+            })
+        };
+        DiagramArrays { verticals: intervals(0), horizontals: intervals(1), corners: Array2::from_shape_fn((self.n, self.m), |(i, j)| self.corner_free(i, j)) }
+    }
+
+    /// Sample the cell boundaries at `resolution` points per cell along both axes, thus the grid has shape `((n-1)*resolution+1, (m-1)*resolution+1)`.
+    ///
+    /// A sample on a cell boundary is 1 if free (on a RSD: reachable) and 0 if not, samples within cells are NaN since the diagram only stores its boundaries
+    /// (rasterize the free space within cells from the curves with [`free_space_mask`]).
+    pub fn to_dense_grid(&self, resolution: usize) -> Result<Array2<f64>, PcmError> {
+        if resolution == 0 {
+            return Err(PcmError::InvalidArgument("Resolution should be at least one pixel per cell.".to_string()));
+        }
+        let r = resolution;
+        let contains = |seg: (usize, usize, usize), off: f64| self.segs[seg].is_some_and(|b| { let LineBoundary { a, b } = b.to_f64(); a <= off && off <= b });
+        let (w, h) = ((self.n - 1) * r + 1, (self.m - 1) * r + 1);
+        Ok(Array2::from_shape_fn((w, h), |(x, y)| {
+            let (i, j, dx, dy) = (x / r, y / r, x % r, y % r);
+            let free = match (dx, dy) {
+                (0, 0) => self.corner_free(i, j),
+                (0, _) => contains((0, i, j), dy as f64 / r as f64),
+                (_, 0) => contains((1, j, i), dx as f64 / r as f64),
+                _ => return f64::NAN,
+            };
+            if free { 1. } else { 0. }
+        }))
+    }
+
+    /// Whether corner (i, j) is free, on a RSD (which does not track corners) whether a reachable interval starts or ends at it.
+    fn corner_free(&self, i: usize, j: usize) -> bool {
+        if !self.is_rsd {
+            return self.corners[(i, j)];
+        }
+        let starts = |seg| self.segs[seg].is_some_and(|b: LineBoundary<F>| b.a == F::ZERO);
+        let ends = |seg| self.segs[seg].is_some_and(|b: LineBoundary<F>| b.b == F::ONE);
+        (j + 1 < self.m && starts((0, i, j))) || (j > 0 && ends((0, i, j - 1))) || (i + 1 < self.n && starts((1, j, i))) || (i > 0 && ends((1, j, i - 1)))
+    }
+}
+
+/// Write a `.npy` file (readable with `numpy.load`) of the given data type descriptor, shape and raw little-endian data.
+#[cfg(feature = "fs")]
+fn write_npy_raw<P: AsRef<Path>>(path: P, descr: &str, shape: &[usize], data: &[u8]) -> std::io::Result<()> {
+    let shape = match shape {
+        [k] => format!("({k},)"),
+        _ => format!("({})", shape.iter().map(|k| k.to_string()).collect::<Vec<_>>().join(", ")),
+    };
+    let mut header = format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': {shape}, }}");
+    // Magic string (6), version (2) and header length (2) precede the header, total is padded to a multiple of 64.
+    let unpadded = 10 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+
+    let mut file = File::create(path)?;
+    file.write_all(b"\x93NUMPY\x01\x00")?;
+    file.write_all(&(header.len() as u16).to_le_bytes())?;
+    file.write_all(header.as_bytes())?;
+    file.write_all(data)?;
+    Ok(())
+}
+
+/// Write a boolean mask as a `.npy` file (readable with `numpy.load`).
+#[cfg(feature = "fs")]
+pub fn write_npy<P: AsRef<Path>>(path: P, mask: &Array2<bool>) -> std::io::Result<()> {
+    let data: Vec<u8> = mask.iter().map(|&free| free as u8).collect();
+    write_npy_raw(path, "|b1", mask.shape(), &data)
+}
+
+/// Write a numeric array of any dimension (e.g. [`DiagramArrays`] or [`FSD::to_dense_grid`]) as a `.npy` file of doubles.
+#[cfg(feature = "fs")]
+pub fn write_npy_f64<P: AsRef<Path>, D: Dimension>(path: P, array: &Array<f64, D>) -> std::io::Result<()> {
+    let data: Vec<u8> = array.iter().flat_map(|v| v.to_le_bytes()).collect();
+    write_npy_raw(path, "<f8", array.shape(), &data)
+}
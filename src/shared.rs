@@ -0,0 +1,78 @@
+use crate::{curve::{validate_curve, validate_eps, Curve}, error::PcmError, grid::{Grid, PathRules, Sample}, vector::Vector};
+
+/// Subcurve of ps matched against a subcurve of qs, see [`longest_shared_subcurve`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedSubcurve {
+    /// Mean of the arc lengths of both matched subcurves.
+    pub length: f64,
+    /// Positions (parameter on ps, parameter on qs) walked along.
+    pub steps: Vec<(f64, f64)>,
+}
+impl SharedSubcurve {
+
+    /// Parameter interval [t_start, t_end] on ps covered by the matching.
+    pub fn interval_p(&self) -> (f64, f64) {
+        (self.steps[0].0, self.steps.last().unwrap().0)
+    }
+
+    /// Parameter interval [t_start, t_end] on qs covered by the matching.
+    pub fn interval_q(&self) -> (f64, f64) {
+        (self.steps[0].1, self.steps.last().unwrap().1)
+    }
+}
+
+/// Compute the longest subcurve of ps matching a subcurve of qs within threshold eps (free start and end on both curves), e.g. road segments two trajectories share.
+///
+/// Length is the mean of the arc lengths of both subcurves, matchings shorter than `min_length` are not reported (a single free point always matches).
+/// The parameter space is discretized with `resolution` samples per FSD cell (monotone moves right, up, or diagonal through free samples),
+/// so the result is an approximation of the continuous matching.
+pub fn longest_shared_subcurve(ps: &Curve, qs: &Curve, eps: f64, min_length: f64, resolution: usize) -> Result<Option<SharedSubcurve>, PcmError> {
+    validate_curve(ps)?;
+    validate_curve(qs)?;
+    validate_eps(eps)?;
+    if !(min_length.is_finite() && min_length >= 0.) {
+        return Err(PcmError::InvalidArgument(format!("Minimum length should be non-negative and finite, got {min_length}.")));
+    }
+    let grid = Grid::new(ps, qs, resolution)?;
+    // Arc length up to every sample.
+    let arc_length = |points: &[Vector]| points.iter().scan((0., points[0]), |(length, prev), &p| { *length += prev.distance(p); *prev = p; Some(*length) }).collect();
+    let rules = SharedRules { free: grid.free(eps), length_p: arc_length(&grid.p), length_q: arc_length(&grid.q) };
+
+    let Some(((start, reached), path)) = grid.best_path(&rules) else { return Ok(None) };
+    let length = 0.5 * (reached - start);
+    if length < min_length {
+        return Ok(None);
+    }
+    let steps = path.into_iter().map(|s| grid.position(s)).collect();
+    Ok(Some(SharedSubcurve { length, steps }))
+}
+
+/// Paths through free samples starting and ending anywhere, as the arc length sums at their start and at the sample reached.
+///
+/// A path is better if it is longer, at the same sample that is if it started at a smaller sum.
+/// (A predecessor always started at a smaller sum than starting anew, so only samples without a free predecessor start.)
+struct SharedRules {
+    free: Vec<Vec<bool>>,
+    length_p: Vec<f64>,
+    length_q: Vec<f64>,
+}
+impl PathRules for SharedRules {
+    type Cost = (f64, f64);
+
+    fn start(&self, (x, y): Sample) -> Option<(f64, f64)> {
+        let sum = self.length_p[x] + self.length_q[y];
+        self.free[x][y].then_some((sum, sum))
+    }
+
+    fn step(&self, &(start, _): &(f64, f64), _: Sample, (x, y): Sample) -> Option<(f64, f64)> {
+        self.free[x][y].then_some((start, self.length_p[x] + self.length_q[y]))
+    }
+
+    fn better(&self, (start1, reached1): &(f64, f64), (start2, reached2): &(f64, f64)) -> bool {
+        reached1 - start1 > reached2 - start2
+    }
+
+    fn end(&self, (x, y): Sample) -> bool {
+        self.free[x][y]
+    }
+}
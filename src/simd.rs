@@ -0,0 +1,50 @@
+use std::simd::{f64x4, StdFloat};
+
+use crate::{float::Float, fsd::{free_boundary, snap_boundary}, lineboundary::OptLineBoundary, vector::Vector};
+
+
+/// Number of cell boundaries computed at once.
+const LANES: usize = 4;
+
+/// Free boundaries of point p against every segment of qs (see [`free_boundary`]), computed four segments at once.
+///
+/// Performs the same operations in the same order as the scalar path (without fused multiply-adds), so for `f64` coordinates the boundaries agree bit for bit.
+/// The case distinctions are made per lane afterwards, degenerate segments fall back to the scalar path.
+pub(crate) fn free_boundaries<F: Float>(p: Vector<F>, qs: &[Vector<F>], eps: f64) -> Vec<OptLineBoundary> {
+    let segments = qs.len().saturating_sub(1);
+    let mut result = Vec::with_capacity(segments);
+    let (px, py, e2) = (f64x4::splat(p.x.to_f64()), f64x4::splat(p.y.to_f64()), eps * eps);
+    let mut j = 0;
+    while j + LANES <= segments {
+        let lanes = |f: fn(&Vector<F>) -> F, offset: usize| f64x4::from_array(std::array::from_fn(|k| f(&qs[j + k + offset]).to_f64()));
+        let (q0x, q0y, q1x, q1y) = (lanes(|q| q.x, 0), lanes(|q| q.y, 0), lanes(|q| q.x, 1), lanes(|q| q.y, 1));
+
+        let (vx, vy) = (q1x - q0x, q1y - q0y);
+        let vv = vx * vx + vy * vy;
+        let vli = f64x4::splat(1.) / vv.sqrt();
+        let (vnx, vny) = (vli * vx, vli * vy);
+        let (lx, ly) = (px - q0x, py - q0y);
+        let ll = lx * lx + ly * ly;
+        let tca = lx * vnx + ly * vny;
+        let d2 = ll - tca * tca;
+        let thc = (f64x4::splat(e2) - d2).sqrt();
+        let (t0, t1) = (vli * (tca - thc), vli * (tca + thc));
+        let (mx, my) = (px - q1x, py - q1y);
+        let (d0, d1) = (ll.sqrt(), (mx * mx + my * my).sqrt());
+
+        let (vv, d2, t0, t1, d0, d1) = (vv.to_array(), d2.to_array(), t0.to_array(), t1.to_array(), d0.to_array(), d1.to_array());
+        for k in 0..LANES {
+            result.push(if vv[k] == 0. {
+                free_boundary(p, qs[j + k], qs[j + k + 1], eps)
+            } else if d2[k] > e2 || t1[k] < 0. || t0[k] > 1. || t1[k] - t0[k] < 0.0001 {
+                None
+            } else {
+                // Snap boundary endpoints like the scalar path.
+                snap_boundary(t0[k].clamp(0., 1.), t1[k].clamp(0., 1.), d0[k] < eps, d1[k] < eps)
+            });
+        }
+        j += LANES;
+    }
+    result.extend((j..segments).map(|j| free_boundary(p, qs[j], qs[j + 1], eps)));
+    result
+}
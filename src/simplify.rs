@@ -0,0 +1,108 @@
+//! Curve simplification within a Fréchet error, to shrink dense traces before building a diagram.
+//!
+//! Both [`simplify_douglas_peucker`] and [`simplify_imai_iri`] return a subsequence of the vertices at Fréchet distance at most eps to the input.
+//! By the triangle inequality, simplifying ps by eps_p and qs by eps_q changes the partial Fréchet distance by at most eps_p + eps_q:
+//! a match at threshold eps on the simplified curves implies a match at eps + eps_p + eps_q on the originals,
+//! and no match at eps on the simplified curves implies no match at eps - eps_p - eps_q on the originals.
+use std::collections::VecDeque;
+
+use crate::{curve::{distance_to_segment, Curve}, lineboundary::LineBoundary};
+
+
+/// Check whether the subcurve c[i..=j] is within Fréchet distance eps to the line segment (c[i], c[j]).
+///
+/// Walk the vertices in order and greedily match each against the lowest feasible parameter on the segment.
+fn is_valid_shortcut(c: &Curve, i: usize, j: usize, eps: f64) -> bool {
+    let (p0, p1) = (c[i], c[j]);
+    if p0.distance(p1) == 0. { // Degenerate segment, every vertex must be near the point itself.
+        return c[i..=j].iter().all(|p| p.distance(p0) <= eps);
+    }
+    // Endpoints match exactly, interior vertices have to match monotonically along the segment.
+    let mut t = 0.;
+    for &p in &c[i+1..j] {
+        match LineBoundary::compute(p, p0, p1, eps) {
+            Some(LineBoundary { a, b }) if b >= t => t = t.max(a),
+            _ => return false
+        }
+    }
+    true
+}
+
+/// Simplification of curve c within Fréchet distance eps (Douglas–Peucker, with a Fréchet rather than Hausdorff acceptance test).
+///
+/// Recursively replaces a subcurve by its shortcut if valid, otherwise splits at the vertex farthest from the shortcut.
+/// Faster than [`simplify_imai_iri`] in practice, but not minimal in the number of vertices.
+pub fn simplify_douglas_peucker(c: &Curve, eps: f64) -> Curve {
+    let n = c.len();
+    if n <= 2 { return c.clone(); }
+
+    // Explicit stack of subcurves (i, j) still to simplify, popping the leftmost first keeps the kept vertices in order.
+    let mut kept = vec![c[0]];
+    let mut stack = vec![(0, n - 1)];
+    while let Some((i, j)) = stack.pop() {
+        if j == i + 1 || is_valid_shortcut(c, i, j, eps) {
+            kept.push(c[j]);
+            continue;
+        }
+        let k = (i+1..j).max_by(|&k, &l| distance_to_segment(c[k], c[i], c[j]).total_cmp(&distance_to_segment(c[l], c[i], c[j]))).unwrap();
+        stack.push((k, j));
+        stack.push((i, k));
+    }
+    kept
+}
+
+/// Minimum-vertex simplification of curve c within Fréchet distance eps (Imai–Iri).
+///
+/// Builds the graph of all valid shortcuts and returns a shortest path from the first to the last vertex.
+/// Guarantee: the Fréchet distance between c and the result is at most eps (see the [module documentation](self) on how that composes with matching).
+pub fn simplify_imai_iri(c: &Curve, eps: f64) -> Curve {
+    let n = c.len();
+    if n <= 2 { return c.clone(); }
+
+    // Breadth-first search over shortcut graph (shortcuts are checked lazily).
+    let mut prev: Vec<Option<usize>> = vec![None; n];
+    let mut queue = VecDeque::from([0]);
+    while let Some(i) = queue.pop_front() {
+        if i == n - 1 { break; }
+        for j in (i+1..n).rev() {
+            if prev[j].is_none() && is_valid_shortcut(c, i, j, eps) {
+                prev[j] = Some(i);
+                queue.push_back(j);
+            }
+        }
+    }
+
+    // Reconstruct path (consecutive vertices are always a valid shortcut, so the last vertex is reached).
+    let mut indices = vec![n - 1];
+    while let Some(i) = prev[*indices.last().unwrap()] {
+        indices.push(i);
+    }
+    indices.reverse();
+    indices.into_iter().map(|i| c[i]).collect()
+}
+
+/// Simplify curve c to at most k vertices (k >= 2) with the smallest Fréchet error found by bisection on eps.
+///
+/// Returns the simplified curve with the tolerance it was simplified at, which bounds its Fréchet distance to c (zero if c is left unchanged).
+pub fn simplify_to_n(c: &Curve, k: usize) -> (Curve, f64) {
+    let k = k.max(2);
+    if c.len() <= k { return (c.clone(), 0.); }
+
+    // Any two points lie within the bounding box diagonal, so that bounds the error.
+    let pmin = c.iter().fold(c[0], |acc, v| acc.min(v));
+    let pmax = c.iter().fold(c[0], |acc, v| acc.max(v));
+    let mut hi = pmin.distance(pmax);
+    let mut lo = 0.;
+    let mut best = simplify_imai_iri(c, hi);
+    for _ in 0..50 {
+        let mid = 0.5 * (lo + hi);
+        let simplified = simplify_imai_iri(c, mid);
+        if simplified.len() <= k {
+            hi = mid;
+            best = simplified;
+        } else {
+            lo = mid;
+        }
+    }
+    (best, hi)
+}
@@ -0,0 +1,172 @@
+use ndarray::Array3;
+
+use crate::{error::PcmError, float::Float, fsd::{Boundaries, MatchMode, FSD}, EPS};
+
+
+/// Closed intervals (possibly single points) on a cell boundary, sorted and disjoint.
+type Intervals = Vec<(f64, f64)>;
+
+/// Position on a cell boundary as (axis, x, y, offset), like the positions walked in the (unconstrained) RSD.
+type Position = (usize, usize, usize, f64);
+
+/// Reachable space of a FSD when the slope of the path through it is bounded, see [`FSD::to_rsd_with_slopes`].
+///
+/// Slopes are measured in parameter space (segments advanced on qs per segment advanced on ps),
+/// which for curves sampled at a fixed rate bounds the relative speed of both.
+/// Unlike the unconstrained RSD, a cell boundary may be reachable in several disjoint intervals.
+#[derive(Debug, Clone)]
+pub struct SlopeRSD {
+    /// Width (number of points on ps).
+    pub n: usize,
+    /// Height (number of points on qs).
+    pub m: usize,
+    /// Boundary conditions the RSD is computed with.
+    pub mode: MatchMode,
+    pub min_slope: f64,
+    pub max_slope: f64,
+    /// Reachable intervals per cell boundary, addressed as (axis, x, y) like `FSD::segs`.
+    pub reach: Array3<Intervals>,
+}
+
+/// Slope times a length, where a zero length stays zero (also for an infinite slope).
+fn mul(slope: f64, length: f64) -> f64 {
+    if length == 0. { 0. } else { slope * length }
+}
+
+/// Length over a slope, where a zero length stays zero (also for a zero slope).
+fn div(length: f64, slope: f64) -> f64 {
+    if length == 0. { 0. } else { length / slope }
+}
+
+/// Sort and merge overlapping intervals.
+fn normalize(mut intervals: Intervals) -> Intervals {
+    intervals.sort_by(|(a1, _), (a2, _)| a1.total_cmp(a2));
+    let mut result: Intervals = vec![];
+    for (a, b) in intervals {
+        match result.last_mut() {
+            Some((_, b_)) if a <= *b_ => *b_ = b_.max(b),
+            _ => result.push((a, b)),
+        }
+    }
+    result
+}
+
+impl<F: Float> FSD<F> {
+
+    /// Compute reachable space diagram of paths whose slope (segments on qs per segment on ps) lies within [min_slope, max_slope].
+    ///
+    /// A slope range of `[0, f64::INFINITY]` reaches the same space as [`FSD::to_rsd_with`].
+    /// Free space within a cell is convex, so a straight line between reachable boundary points is free, and the reachable part
+    /// of the right and top boundary of a cell follows from the cone of slopes out of its left and bottom boundary.
+    pub fn to_rsd_with_slopes(&self, mode: MatchMode, min_slope: f64, max_slope: f64) -> Result<SlopeRSD, PcmError> {
+        if !(0. <= min_slope && min_slope <= max_slope && max_slope > 0.) {
+            return Err(PcmError::InvalidArgument(format!("Slopes should satisfy 0 <= min <= max and max > 0, got [{min_slope}, {max_slope}].")));
+        }
+        let (n, m) = (self.n, self.m);
+        let (lo, hi) = (min_slope, max_slope);
+        let free = |seg| self.boundary(seg).map(|b| (b.a, b.b));
+        let clip = |intervals: Intervals, seg| {
+            let Some((fa, fb)) = free(seg) else { return vec![] };
+            normalize(intervals.into_iter().filter_map(|(a, b)| { let (a, b) = (a.max(fa), b.min(fb)); (a <= b).then_some((a, b)) }).collect())
+        };
+
+        // Initiate the boundaries the match mode may start at.
+        let mut reach = Array3::from_elem((2, n.max(m), m.max(n)), vec![]);
+        if matches!(mode, MatchMode::Full | MatchMode::PrefixOfQ) {
+            for axis in 0..2 {
+                reach[(axis, 0, 0)] = clip(vec![(0., 0.)], (axis, 0, 0));
+            }
+        } else {
+            for y in 0..m-1 {
+                reach[(0, 0, y)] = clip(vec![(0., 1.)], (0, 0, y));
+            }
+        }
+        if mode == MatchMode::SubcurveOfBoth {
+            for x in 0..n-1 {
+                reach[(1, 0, x)] = clip(vec![(0., 1.)], (1, 0, x));
+            }
+        }
+
+        // Propagate cell by cell from its left and bottom boundary to its right and top boundary.
+        for x in 0..n-1 {
+            for y in 0..m-1 {
+                let (left, bottom) = (reach[(0, x, y)].clone(), reach[(1, y, x)].clone());
+                let (mut right, mut top) = (vec![], vec![]);
+                for &(a, b) in &left {
+                    right.push((a + lo, b + hi));
+                    top.push((div(1. - b, hi), div(1. - a, lo)));
+                }
+                for &(a, b) in &bottom {
+                    right.push((mul(lo, 1. - b), mul(hi, 1. - a)));
+                    top.push((a + div(1., hi), b + div(1., lo)));
+                }
+                reach[(0, x+1, y)] = clip(right, (0, x+1, y));
+                reach[(1, y+1, x)] = clip(top, (1, y+1, x));
+            }
+        }
+
+        Ok(SlopeRSD { n, m, mode, min_slope, max_slope, reach })
+    }
+}
+
+impl SlopeRSD {
+
+    /// Reachable position the match mode may end at (if any), preferring the lowest point on the right boundary, then the leftmost point on the top boundary.
+    fn end_position(&self) -> Option<Position> {
+        let (n, m) = (self.n, self.m);
+        let corner = || {
+            if self.reach[(0, n-1, m-2)].last().is_some_and(|&(_, b)| b == 1.) { Some((0, n-1, m-2, 1.)) }
+            else if self.reach[(1, m-1, n-2)].last().is_some_and(|&(_, b)| b == 1.) { Some((1, m-1, n-2, 1.)) }
+            else { None }
+        };
+        let right = || (0..m-1).find_map(|y| self.reach[(0, n-1, y)].first().map(|&(a, _)| (0, n-1, y, a)));
+        let top = || (0..n-1).find_map(|x| self.reach[(1, m-1, x)].first().map(|&(a, _)| (1, m-1, x, a)));
+        match self.mode {
+            MatchMode::Full | MatchMode::SuffixOfQ => corner(),
+            MatchMode::PrefixOfQ | MatchMode::SubcurveOfQ => right(),
+            MatchMode::SubcurveOfBoth => right().or_else(top),
+        }
+    }
+
+    /// Check whether a matching may start at the position.
+    fn is_start(&self, (axis, x, y, off): Position) -> bool {
+        let corner = x == 0 && y == 0 && off == 0.;
+        match self.mode {
+            MatchMode::Full | MatchMode::PrefixOfQ => corner,
+            MatchMode::SuffixOfQ | MatchMode::SubcurveOfQ => (axis == 0 && x == 0) || (axis == 1 && y == 0 && off == 0.),
+            MatchMode::SubcurveOfBoth => x == 0 || (axis == 0 && y == 0 && off == 0.),
+        }
+    }
+
+    /// Check for a partial curve match with bounded slope.
+    pub fn check_pcm(&self) -> bool {
+        self.end_position().is_some()
+    }
+
+    /// Compute steps to walk along curves for a partial matching solution with bounded slope.
+    ///
+    /// Walks backwards from the end, to a point on the left or bottom boundary of the current cell within the slope range (up to [`EPS`]).
+    pub fn pcm_steps(&self) -> Option<Vec<(f64, f64)>> {
+        let (lo, hi) = (self.min_slope, self.max_slope);
+        let mut curr = self.end_position()?;
+        let to_ij = |(axis, x, y, off): Position| [(x as f64, y as f64 + off), (y as f64 + off, x as f64)][axis];
+        let mut steps = vec![to_ij(curr)];
+        while !self.is_start(curr) {
+            let (axis, x, y, off) = curr;
+            // Cell to the lower left of the current boundary, with the offset ranges on its left and bottom boundary reaching the current position.
+            let ((cx, cy), from_left, from_bottom) = if axis == 0 {
+                ((x - 1, y), (off - hi, off - lo), (1. - div(off, lo), 1. - div(off, hi)))
+            } else {
+                ((y, x - 1), (1. - mul(hi, off), 1. - mul(lo, off)), (off - div(1., lo), off - div(1., hi)))
+            };
+            let candidates = [((0, cx, cy), from_left), ((1, cy, cx), from_bottom)];
+            curr = candidates.into_iter().find_map(|(seg, (a_, b_))| {
+                self.reach[seg].iter().find(|&&(a, b)| a <= b_ + EPS && a_ - EPS <= b).map(|&(a, b)| (seg.0, seg.1, seg.2, a_.clamp(a, b)))
+            }).expect("Reachable position should have a reachable predecessor within the slope range.");
+            steps.push(to_ij(curr));
+        }
+        steps.reverse();
+        steps.dedup();
+        Some(steps)
+    }
+}
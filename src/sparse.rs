@@ -0,0 +1,118 @@
+use crate::{curve::{validate_curve, validate_eps, Curve}, error::PcmError, fsd::{free_boundary, reachable_boundary, Boundaries, MatchMode}, lineboundary::OptLineBoundary, vector::Point, LineBoundary};
+
+
+/// Boundary intervals along a single line of the diagram, sorted by position on the line.
+type Line = Vec<(usize, LineBoundary)>;
+
+/// Sparse Free-Space Diagram: only non-empty cell boundaries are stored (per boundary line, compressed like the rows of a sparse matrix).
+///
+/// Answers the same queries as the dense [`crate::FSD`] (in subcurve match mode), with memory scaling in the number of free cell boundaries instead of n·m.
+/// Boundaries are addressed alike, as (axis, x, y) with `dims = [(n,m-1), (m,n-1)]`.
+#[derive(Debug, Clone)]
+pub struct SparseFSD {
+    /// Width (number of points on ps).
+    pub n: usize,
+    /// Height (number of points on qs).
+    pub m: usize,
+    /// Non-empty boundaries per axis and line x.
+    lines: [Vec<Line>; 2],
+    /// FSD and RSD are the same struct, use this boolean to ensure being in the correct space.
+    pub is_rsd: bool,
+}
+impl SparseFSD {
+
+    /// Compute the sparse free-space diagram between curve P (points ps) and curve Q (points qs).
+    pub fn new<P: Point>(ps: Curve<P>, qs: Curve<P>, eps: f64) -> SparseFSD {
+        let (n, m) = (ps.len(), qs.len());
+        let lines = [(&ps, &qs), (&qs, &ps)].map(|(c1, c2)| {
+            c1.iter().map(|&p| (0..c2.len() - 1).filter_map(|y| free_boundary(p, c2[y], c2[y+1], eps).map(|b| (y, b))).collect()).collect()
+        });
+        SparseFSD { n, m, lines, is_rsd: false }
+    }
+
+    /// Like [`SparseFSD::new`], but reject malformed input with an error instead of panicking.
+    pub fn try_new<P: Point>(ps: Curve<P>, qs: Curve<P>, eps: f64) -> Result<SparseFSD, PcmError> {
+        validate_curve(&ps)?;
+        validate_curve(&qs)?;
+        validate_eps(eps)?;
+        Ok(SparseFSD::new(ps, qs, eps))
+    }
+
+    /// Number of non-empty boundaries stored.
+    pub fn len(&self) -> usize {
+        self.lines.iter().flatten().map(|line| line.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Free (or reachable) interval on the cell boundary (axis, x, y), like `FSD::segs[(axis, x, y)]`.
+    pub fn boundary(&self, (axis, x, y): (usize, usize, usize)) -> OptLineBoundary {
+        let line = &self.lines[axis][x];
+        line.binary_search_by_key(&y, |&(y, _)| y).ok().map(|k| line[k].1)
+    }
+
+    /// Non-empty boundaries as ((axis, x, y), interval).
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize, usize), LineBoundary)> + '_ {
+        self.lines.iter().enumerate().flat_map(|(axis, lines)| {
+            lines.iter().enumerate().flat_map(move |(x, line)| line.iter().map(move |&(y, b)| ((axis, x, y), b)))
+        })
+    }
+
+    /// Compute the reachable space diagram (starting anywhere on the left boundary), like [`crate::FSD::to_rsd`].
+    pub fn to_rsd(&self) -> SparseFSD {
+        let fsd = self;
+        assert!(!fsd.is_rsd); // Sanity check: Check it is already an RSD.
+        let mut rsd = SparseFSD { n: fsd.n, m: fsd.m, lines: [vec![vec![]; fsd.n], vec![vec![]; fsd.m]], is_rsd: true };
+
+        // Only free boundaries can be reachable, walk them in the order of the dense propagation (bottom to top, left to right).
+        let mut order: Vec<_> = fsd.iter().map(|(curr, b)| {
+            let (axis, x, y) = curr;
+            let key = if axis == 0 { (y, x, 0) } else { (x, y, 1) };
+            (key, curr, b)
+        }).collect();
+        order.sort_by_key(|&(key, _, _)| key);
+
+        for (_, curr, free) in order {
+            let (axis, x, y) = curr;
+            let LineBoundary { a, b: _ } = free;
+            // Seed the left border and the first horizontal boundary of every row starting at a free left cornerpoint.
+            let seed = if (axis == 0 && x == 0) || (axis == 1 && y == 0 && a == 0.) { Some(free) } else { None };
+            let orth = if x > 0 { rsd.boundary((1-axis, y, x-1)) } else { None };
+            let para = if x > 0 { rsd.boundary((axis, x-1, y)) } else { None };
+            let prev = if y > 0 { rsd.boundary((axis, x, y-1)) } else { None };
+            let reachable = reachable_boundary(seed, Some(free), orth, para, prev);
+            if let Some(boundary) = reachable {
+                rsd.lines[axis][x].push((y, boundary));
+            }
+        }
+        rsd
+    }
+
+    /// Check for a partial curve match.
+    pub fn check_pcm(&self) -> bool {
+        let rsd = if self.is_rsd { self } else { &self.to_rsd() };
+        rsd.end_position().is_some()
+    }
+
+    /// Compute steps to walk along curves for a partial curve matching solution, like [`crate::FSD::pcm_steps`].
+    pub fn pcm_steps(&self) -> Option<Vec<(f64, f64)>> {
+        let rsd = if self.is_rsd { self } else { &self.to_rsd() };
+        Some(rsd.steps_to(rsd.end_position()?))
+    }
+}
+impl Boundaries for SparseFSD {
+
+    fn size(&self) -> (usize, usize) {
+        (self.n, self.m)
+    }
+
+    fn mode(&self) -> MatchMode {
+        MatchMode::SubcurveOfQ
+    }
+
+    fn boundary(&self, seg: (usize, usize, usize)) -> OptLineBoundary {
+        SparseFSD::boundary(self, seg)
+    }
+}
@@ -0,0 +1,208 @@
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{curve::{validate_curve, validate_eps, Curve}, error::PcmError, fsd::FSD, lineboundary::LineBoundary, partial_curve::Matching, vector::Vector};
+
+/// A polygonal chain with a (non-decreasing) timestamp per point.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimedCurve {
+    /// Curve points.
+    pub points: Curve,
+    /// Timestamp per curve point.
+    pub times: Vec<f64>
+}
+impl TimedCurve {
+
+    /// Construct a timed curve, fails if the curve is empty, the number of timestamps mismatches, or a timestamp decreases (or is not finite).
+    pub fn new(points: Curve, times: Vec<f64>) -> Result<Self, PcmError> {
+        if points.is_empty() {
+            return Err(PcmError::EmptyCurve { len: 0 });
+        }
+        if points.len() != times.len() {
+            return Err(PcmError::InvalidArgument(format!("Expected a timestamp per curve point ({}), got {}.", points.len(), times.len())));
+        }
+        for (index, &time) in times.iter().enumerate() {
+            if !time.is_finite() || (index > 0 && time < times[index - 1]) {
+                return Err(PcmError::InvalidTimestamp { index, time });
+            }
+        }
+        Ok(TimedCurve { points, times })
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Time of the first point.
+    pub fn start_time(&self) -> f64 {
+        self.times[0]
+    }
+
+    /// Time of the last point.
+    pub fn end_time(&self) -> f64 {
+        *self.times.last().unwrap()
+    }
+
+    /// Position at time t (clamped to the time range of the curve), linearly interpolated between points.
+    pub fn point_at_time(&self, t: f64) -> Vector {
+        let t = t.max(self.start_time()).min(self.end_time());
+        // Index of the segment containing t.
+        let i = self.times.partition_point(|&ti| ti <= t).max(1) - 1;
+        if i == self.len() - 1 || self.times[i+1] == self.times[i] {
+            return self.points[i];
+        }
+        let off = (t - self.times[i]) / (self.times[i+1] - self.times[i]);
+        (1. - off) * self.points[i] + off * self.points[i+1]
+    }
+
+    /// Time at curve parameter t (integer part is the segment index, fractional part the offset on that segment).
+    pub fn time_at(&self, t: f64) -> f64 {
+        let i = (t.max(0.).floor() as usize).min(self.len() - 1);
+        if i == self.len() - 1 {
+            return self.times[i];
+        }
+        let off = t - i as f64;
+        (1. - off) * self.times[i] + off * self.times[i+1]
+    }
+
+    /// Extract the subcurve between times t0 and t1 (with interpolated endpoints).
+    ///
+    /// Returns None if the time window does not overlap the curve.
+    pub fn slice_by_time(&self, t0: f64, t1: f64) -> Option<TimedCurve> {
+        let t0 = t0.max(self.start_time());
+        let t1 = t1.min(self.end_time());
+        if t0 > t1 {
+            return None;
+        }
+        let mut points = vec![self.point_at_time(t0)];
+        let mut times = vec![t0];
+        for (&p, &t) in self.points.iter().zip(&self.times) {
+            if t > t0 && t < t1 {
+                points.push(p);
+                times.push(t);
+            }
+        }
+        points.push(self.point_at_time(t1));
+        times.push(t1);
+        Some(TimedCurve { points, times })
+    }
+
+    /// Average speed per segment (infinite for segments without elapsed time).
+    pub fn speeds(&self) -> Vec<f64> {
+        (0..self.len().saturating_sub(1)).map(|i| {
+            let d = self.points[i].distance(self.points[i+1]);
+            let dt = self.times[i+1] - self.times[i];
+            if dt > 0. { d / dt } else if d == 0. { 0. } else { f64::INFINITY }
+        }).collect()
+    }
+
+    /// Drop timestamps.
+    pub fn to_curve(&self) -> Curve {
+        self.points.clone()
+    }
+}
+
+impl From<TimedCurve> for Curve {
+    fn from(c: TimedCurve) -> Self {
+        c.points
+    }
+}
+
+/// Admissible lags between the timestamps a matching couples: the time on qs minus the time on ps lies within [min_lag, max_lag].
+///
+/// Without a window a geometric matching may couple positions passed hours apart, or in an order no two vehicles could have driven.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeWindow {
+    pub min_lag: f64,
+    pub max_lag: f64,
+}
+impl TimeWindow {
+
+    pub fn new(min_lag: f64, max_lag: f64) -> Self {
+        TimeWindow { min_lag, max_lag }
+    }
+
+    /// Coupled timestamps differ at most offset (either way).
+    pub fn max_offset(offset: f64) -> Self {
+        TimeWindow::new(-offset, offset)
+    }
+
+    /// Temporal order: qs passes every coupled position no earlier than ps, and at most max_lag later (e.g. a vehicle following ps).
+    pub fn following(max_lag: f64) -> Self {
+        TimeWindow::new(0., max_lag)
+    }
+
+    /// Whether the lag (time on qs minus time on ps) is admissible.
+    pub fn contains(&self, lag: f64) -> bool {
+        self.min_lag <= lag && lag <= self.max_lag
+    }
+
+    /// Check the window is non-empty (bounds may be infinite).
+    pub fn validate(&self) -> Result<(), PcmError> {
+        if self.min_lag.is_nan() || self.max_lag.is_nan() || self.min_lag > self.max_lag {
+            return Err(PcmError::InvalidArgument(format!("Time window [{}, {}] is empty.", self.min_lag, self.max_lag)));
+        }
+        Ok(())
+    }
+}
+
+/// Parameter interval on a segment with times (t0, t1) whose times t are admissible, given the admissible times [lo, hi].
+///
+/// Endpoints are decided by the admissibility of the segment endpoints (like the cornerpoints), so both stay consistent under rounding.
+fn admissible(t0: f64, t1: f64, lo: f64, hi: f64, allowed: impl Fn(f64) -> bool) -> Option<(f64, f64)> {
+    if t0 == t1 {
+        return allowed(t0).then_some((0., 1.));
+    }
+    let a = if allowed(t0) { 0. } else { ((lo - t0) / (t1 - t0)).max(0.) };
+    let b = if allowed(t1) { 1. } else { ((hi - t0) / (t1 - t0)).min(1.) };
+    Some((a, b))
+}
+
+impl FSD {
+
+    /// Free space diagram of timed curves, where free space additionally requires the coupled timestamps to lie within the window.
+    ///
+    /// Timestamps are interpolated linearly along segments, so within a cell the admissible lags form a band which keeps the free space convex
+    /// (reachability and steps are computed as usual, e.g. with [`FSD::to_rsd_with`]).
+    pub fn new_timed(ps: &TimedCurve, qs: &TimedCurve, eps: f64, window: TimeWindow) -> FSD {
+        let mut fsd = FSD::from_slices(&ps.points, &qs.points, eps);
+        for axis in 0..2 {
+            let (w, h) = fsd.dims[axis];
+            for x in 0..w {
+                for y in 0..h {
+                    let Some(LineBoundary { a, b }) = fsd.segs[(axis, x, y)] else { continue };
+                    // Vertical boundaries run along qs at a point of ps, horizontal ones along ps at a point of qs.
+                    let range = if axis == 0 {
+                        let tp = ps.times[x];
+                        admissible(qs.times[y], qs.times[y+1], tp + window.min_lag, tp + window.max_lag, |t| window.contains(t - tp))
+                    } else {
+                        let tq = qs.times[x];
+                        admissible(ps.times[y], ps.times[y+1], tq - window.max_lag, tq - window.min_lag, |t| window.contains(tq - t))
+                    };
+                    fsd.segs[(axis, x, y)] = range.and_then(|(lo, hi)| LineBoundary::new(a.max(lo), b.min(hi)));
+                }
+            }
+        }
+        for i in 0..fsd.n {
+            for j in 0..fsd.m {
+                fsd.corners[(i, j)] &= window.contains(qs.times[j] - ps.times[i]);
+            }
+        }
+        fsd
+    }
+}
+
+/// Compute a partial curve match (if any) of ps against a subcurve of qs below threshold epsilon, coupling only timestamps within the window.
+pub fn timed_partial_curve_matching(ps: &TimedCurve, qs: &TimedCurve, eps: f64, window: TimeWindow) -> Result<Option<Matching>, PcmError> {
+    validate_curve(&ps.points)?;
+    validate_curve(&qs.points)?;
+    validate_eps(eps)?;
+    window.validate()?;
+    let rsd = FSD::new_timed(ps, qs, eps, window).to_rsd();
+    rsd.pcm_steps().map(|steps| Matching::new(ps.to_curve(), qs.to_curve(), steps)).transpose()
+}
@@ -0,0 +1,106 @@
+use crate::{bvh::Aabb, curve::{validate_curve, Curve}, error::PcmError, parallel, partial_curve::min_partial_eps, vector::Vector};
+
+
+/// Rotation (counter-clockwise by an angle in radians, about the origin) followed by a translation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub rotation: f64,
+    pub translation: Vector,
+}
+impl Transform {
+
+    /// Rotation by angle about point center, followed by translation.
+    pub fn about(center: Vector, rotation: f64, translation: Vector) -> Self {
+        let rotated = Transform { rotation, translation: Vector::new(0., 0.) }.apply(center);
+        Transform { rotation, translation: center - rotated + translation }
+    }
+
+    pub fn apply(&self, p: Vector) -> Vector {
+        let (sin, cos) = self.rotation.sin_cos();
+        Vector::new(cos * p.x - sin * p.y, sin * p.x + cos * p.y) + self.translation
+    }
+
+    pub fn apply_curve(&self, c: &Curve) -> Curve {
+        c.iter().map(|&p| self.apply(p)).collect()
+    }
+}
+
+/// Family of transforms to search over, see [`best_transform`].
+///
+/// Transforms rotate ps about its centroid and translate it, the search samples a grid over both and refines around the best sample.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransformSearch {
+    /// Box the centroid of ps is translated into (by default the bounding box of qs).
+    pub translations: Option<Aabb>,
+    /// Largest rotation (radians) either way, zero for translations only.
+    pub max_rotation: f64,
+    /// Grid samples per dimension.
+    pub resolution: usize,
+    /// Refinement rounds, each sampling around the best transform at half the previous spacing.
+    pub refinements: usize,
+    /// Tolerance of the minimal threshold per transform (see [`min_partial_eps`]).
+    pub tolerance: f64,
+}
+impl Default for TransformSearch {
+    fn default() -> Self {
+        TransformSearch { translations: None, max_rotation: 0., resolution: 9, refinements: 8, tolerance: 1e-3 }
+    }
+}
+
+/// Transform of ps found by [`best_transform`], with the smallest threshold ps then partially matches qs at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Alignment {
+    pub transform: Transform,
+    pub eps: f64,
+}
+
+/// Search the family of transforms for the one minimizing the partial Fréchet distance of the transformed ps against qs (grid search with local refinement).
+///
+/// Every sample computes a minimal threshold (in parallel, if enabled), so the cost grows with the resolution squared (cubed with rotations).
+/// The partial Fréchet distance is not convex in the transform, a local minimum between grid samples may be missed.
+pub fn best_transform(ps: &Curve, qs: &Curve, search: &TransformSearch) -> Result<Alignment, PcmError> {
+    validate_curve(ps)?;
+    validate_curve(qs)?;
+    if search.resolution < 2 || !(search.max_rotation.is_finite() && search.max_rotation >= 0.) {
+        return Err(PcmError::InvalidArgument(format!("Transform search needs a resolution of at least 2 and a non-negative rotation, got {} and {}.", search.resolution, search.max_rotation)));
+    }
+    let centroid = (1. / ps.len() as f64) * ps.iter().fold(Vector::new(0., 0.), |acc, &p| acc + p);
+    let bounds = search.translations.unwrap_or_else(|| qs.windows(2).map(|w| Aabb::of_segment(w[0], w[1])).reduce(|a, b| a.union(&b)).unwrap());
+    let evaluate = |(x, y, rotation): (f64, f64, f64)| -> Result<Alignment, PcmError> {
+        let transform = Transform::about(centroid, rotation, Vector::new(x, y) - centroid);
+        let eps = min_partial_eps(&transform.apply_curve(ps), qs, search.tolerance)?;
+        Ok(Alignment { transform, eps })
+    };
+
+    // Samples (x, y, rotation) centered around a sample, with the given spacing per dimension.
+    let k = search.resolution;
+    let grid = |center: (f64, f64, f64), spacing: (f64, f64, f64), k: usize| {
+        let offset = |l: usize, s: f64| (l as f64 - 0.5 * (k - 1) as f64) * s;
+        let rotations = if spacing.2 > 0. { k } else { 1 };
+        let mut samples = vec![];
+        for a in 0..k {
+            for b in 0..k {
+                for c in 0..rotations {
+                    samples.push((center.0 + offset(a, spacing.0), center.1 + offset(b, spacing.1), center.2 + offset(c, spacing.2)));
+                }
+            }
+        }
+        samples
+    };
+    let best_of = |samples: Vec<(f64, f64, f64)>| -> Result<((f64, f64, f64), Alignment), PcmError> {
+        let alignments = parallel::map(samples.clone(), evaluate).into_iter().collect::<Result<Vec<_>, PcmError>>()?;
+        Ok(samples.into_iter().zip(alignments).min_by(|(_, a), (_, b)| a.eps.total_cmp(&b.eps)).unwrap())
+    };
+
+    let (center, size) = (bounds.center(), bounds.max - bounds.min);
+    let mut spacing = (size.x / (k - 1) as f64, size.y / (k - 1) as f64, 2. * search.max_rotation / (k - 1) as f64);
+    let (mut sample, mut best) = best_of(grid((center.x, center.y, 0.), spacing, k))?;
+    for _ in 0..search.refinements {
+        spacing = (0.5 * spacing.0, 0.5 * spacing.1, 0.5 * spacing.2);
+        let (refined, alignment) = best_of(grid(sample, spacing, 3))?;
+        if alignment.eps < best.eps {
+            (sample, best) = (refined, alignment);
+        }
+    }
+    Ok(best)
+}
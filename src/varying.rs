@@ -0,0 +1,81 @@
+use crate::{curve::{validate_curve, Curve}, error::PcmError, fsd::{free_boundary, snap_boundary, FSD}, lineboundary::OptLineBoundary, partial_curve::Matching, vector::Point};
+
+
+/// Free interval on the cell boundary of point p against segment (q0, q1), with threshold e0 at q0 interpolated linearly to e1 at q1.
+///
+/// The distance minus a linear threshold is convex, so the free part is a single interval.
+/// It is found among the roots of `|p - q(t)|^2 - e(t)^2` (both sides are non-negative on the segment).
+pub(crate) fn varying_boundary<P: Point>(p: P, q0: P, q1: P, e0: f64, e1: f64) -> OptLineBoundary {
+    if e0 == e1 {
+        return free_boundary(p, q0, q1, e0);
+    }
+    let (v, l, de) = (q1 - q0, p - q0, e1 - e0);
+    let (a, b, c) = (v.dot(v) - de * de, -2. * (l.dot(v) + e0 * de), l.dot(l) - e0 * e0);
+    let f = |t: f64| (a * t + b) * t + c;
+    let mut candidates = vec![0., 1.];
+    if a == 0. {
+        if b != 0. { candidates.push(-c / b); }
+    } else if b * b - 4. * a * c >= 0. {
+        let root = (b * b - 4. * a * c).sqrt();
+        candidates.extend([(-b - root) / (2. * a), (-b + root) / (2. * a)]);
+    }
+    candidates.retain(|t| (0. ..=1.).contains(t));
+    candidates.sort_by(f64::total_cmp);
+    // Pieces between consecutive candidates are either free or blocked entirely, the free ones are adjacent.
+    let free: Vec<(f64, f64)> = candidates.windows(2).map(|w| (w[0], w[1])).filter(|&(t0, t1)| f(0.5 * (t0 + t1)) <= 0.).collect();
+    let (lo, hi) = (free.first()?.0, free.last()?.1);
+    snap_boundary(lo, hi, p.distance(q0) < e0, p.distance(q1) < e1)
+}
+
+/// Check a threshold per point of qs is usable: one per point, each positive and finite.
+fn validate_thresholds<P>(qs: &Curve<P>, eps: &[f64]) -> Result<(), PcmError> {
+    if eps.len() != qs.len() {
+        return Err(PcmError::InvalidArgument(format!("Expected a threshold per point of qs ({}), got {}.", qs.len(), eps.len())));
+    }
+    if let Some(&e) = eps.iter().find(|e| !(e.is_finite() && **e > 0.)) {
+        return Err(PcmError::InvalidThreshold(e));
+    }
+    Ok(())
+}
+
+impl FSD {
+
+    /// Construct FSD with a threshold per point of qs (e.g. wider on highways, tighter in dense urban grids), interpolated linearly along its segments.
+    ///
+    /// Vertical boundaries (along segments of qs) use the interpolated threshold, horizontal boundaries and corners the threshold of their point of qs.
+    /// The free space within a cell stays convex, so reachability and steps are computed as usual.
+    pub fn new_varying<P: Point>(ps: &[P], qs: &[P], eps: &[f64]) -> FSD {
+        let mut fsd = FSD::from_slices(ps, qs, eps.iter().copied().fold(0., f64::max));
+        for (x, &p) in ps.iter().enumerate() {
+            for (y, (q, e)) in qs.windows(2).zip(eps.windows(2)).enumerate() {
+                fsd.segs[(0, x, y)] = varying_boundary(p, q[0], q[1], e[0], e[1]);
+            }
+        }
+        for (x, (&q, &e)) in qs.iter().zip(eps).enumerate() {
+            for (y, p) in ps.windows(2).enumerate() {
+                fsd.segs[(1, x, y)] = free_boundary(q, p[0], p[1], e);
+            }
+        }
+        for (i, &p) in ps.iter().enumerate() {
+            for (j, (&q, &e)) in qs.iter().zip(eps).enumerate() {
+                fsd.corners[(i, j)] = p.distance(q) < e;
+            }
+        }
+        fsd
+    }
+
+    /// Like [`FSD::new_varying`], with the threshold as a function of the parameter on qs sampled at its points.
+    pub fn new_varying_with<P: Point>(ps: &[P], qs: &[P], eps: impl Fn(f64) -> f64) -> FSD {
+        let eps: Vec<f64> = (0..qs.len()).map(|j| eps(j as f64)).collect();
+        FSD::new_varying(ps, qs, &eps)
+    }
+}
+
+/// Compute a partial curve match (if any) of ps against a subcurve of qs, with a threshold per point of qs (see [`FSD::new_varying`]).
+pub fn varying_partial_curve_matching(ps: &Curve, qs: &Curve, eps: &[f64]) -> Result<Option<Matching>, PcmError> {
+    validate_curve(ps)?;
+    validate_curve(qs)?;
+    validate_thresholds(qs, eps)?;
+    let rsd = FSD::new_varying(ps, qs, eps).to_rsd();
+    rsd.pcm_steps().map(|steps| Matching::new(ps.clone(), qs.clone(), steps)).transpose()
+}
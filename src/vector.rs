@@ -1,18 +1,25 @@
-use std::ops::{Add, Div, Mul, Sub};
+use core::{fmt::Debug, ops::{Add, Div, Mul, Sub}};
+#[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
-#[pyclass]
-pub struct Vector {
-    pub x: f64,
-    pub y: f64
-}
-impl Vector {
-    pub fn dot(self, rhs: Self) -> f64 {
+use alloc::{vec, vec::Vec};
+
+use crate::{float::Float, fsd::free_boundary, lineboundary::OptLineBoundary};
+
+/// Planar point, with `f64` coordinates by default (use `Vector<f32>` for half the memory).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Vector<F: Float = f64> {
+    pub x: F,
+    pub y: F
+}
+impl<F: Float> Vector<F> {
+    pub fn dot(self, rhs: Self) -> F {
         self.x * rhs.x + self.y * rhs.y
     }
-    pub fn distance(self, rhs: Self) -> f64 {
+    pub fn distance(self, rhs: Self) -> F {
         (rhs - self).dot(rhs - self).sqrt()
     }
     pub fn min(&self, rhs: &Self) -> Self {
@@ -28,15 +35,18 @@ impl Vector {
         }
     }
 }
-#[pymethods]
-impl Vector {
-    #[new]
-    pub fn new(x: f64, y: f64) -> Self {
+impl<F: Float> Vector<F> {
+    pub fn new(x: F, y: F) -> Self {
         Vector { x, y }
     }
+
+    /// Convert into another scalar (rounding to nearest when narrowing).
+    pub fn cast<G: Float>(self) -> Vector<G> {
+        Vector { x: G::from_f64(self.x.to_f64()), y: G::from_f64(self.y.to_f64()) }
+    }
 }
-impl Div for Vector {
-    type Output = Vector;
+impl<F: Float> Div for Vector<F> {
+    type Output = Vector<F>;
     fn div(self, rhs: Self) -> Self::Output {
         Vector {
             x: self.x / rhs.x,
@@ -44,8 +54,8 @@ impl Div for Vector {
         }
     }
 }
-impl Mul for Vector {
-    type Output = Vector;
+impl<F: Float> Mul for Vector<F> {
+    type Output = Vector<F>;
     fn mul(self, rhs: Self) -> Self::Output {
         Vector {
             x: self.x * rhs.x,
@@ -53,8 +63,8 @@ impl Mul for Vector {
         }
     }
 }
-impl Add for Vector {
-    type Output = Vector;
+impl<F: Float> Add for Vector<F> {
+    type Output = Vector<F>;
     fn add(self, rhs: Self) -> Self::Output {
         Vector {
             x: self.x + rhs.x,
@@ -62,8 +72,8 @@ impl Add for Vector {
         }
     }
 }
-impl Sub for Vector {
-    type Output = Vector;
+impl<F: Float> Sub for Vector<F> {
+    type Output = Vector<F>;
     fn sub(self, rhs: Self) -> Self::Output {
         Vector { 
             x: self.x - rhs.x,
@@ -71,7 +81,7 @@ impl Sub for Vector {
         }
     }
 }
-impl std::ops::Mul<Vector> for f64 {
+impl core::ops::Mul<Vector> for f64 {
     type Output = Vector;
 
     fn mul(self, rhs: Vector) -> Vector {
@@ -82,3 +92,118 @@ impl std::ops::Mul<Vector> for f64 {
     }
 }
 
+/// Python counterpart of [`Vector`] (pyo3 classes cannot be generic), curves convert from and into lists of these.
+#[cfg(feature = "python")]
+#[pyclass(name = "Vector")]
+#[derive(Debug, Clone, Copy)]
+pub struct PyVector {
+    x: f64,
+    y: f64
+}
+#[cfg(feature = "python")]
+#[pymethods]
+impl PyVector {
+    #[new]
+    fn py_new(x: f64, y: f64) -> Self {
+        PyVector { x, y }
+    }
+}
+#[cfg(feature = "python")]
+impl<'py> FromPyObject<'py> for Vector {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let PyVector { x, y } = ob.extract()?;
+        Ok(Vector::new(x, y))
+    }
+}
+#[cfg(feature = "python")]
+impl IntoPy<PyObject> for Vector {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        PyVector { x: self.x, y: self.y }.into_py(py)
+    }
+}
+
+
+/// Point type curves can be built from, the free-space computations only need these operations (and thus work in any dimension).
+pub trait Point: Copy + Debug + PartialEq + Add<Output = Self> + Sub<Output = Self> {
+    fn dot(self, rhs: Self) -> f64;
+    /// Multiply every coordinate with s.
+    fn scale(self, s: f64) -> Self;
+    /// Check all coordinates are finite and at most bound in magnitude.
+    fn is_bounded(&self, bound: f64) -> bool;
+    /// Coordinates (for exact arithmetic, see [`crate::predicates`]).
+    fn coordinates(self) -> Vec<f64>;
+    fn distance(self, rhs: Self) -> f64 {
+        (rhs - self).dot(rhs - self).sqrt()
+    }
+    /// Free interval on every segment of qs of points within distance eps of this point, as in a column of the free-space diagram.
+    fn free_boundaries(self, qs: &[Self], eps: f64) -> Vec<OptLineBoundary> {
+        qs.windows(2).map(|q| free_boundary(self, q[0], q[1], eps)).collect()
+    }
+}
+impl<F: Float> Point for Vector<F> {
+    fn dot(self, rhs: Self) -> f64 {
+        Vector::dot(self, rhs).to_f64()
+    }
+    fn scale(self, s: f64) -> Self {
+        let s = F::from_f64(s);
+        Vector::new(s * self.x, s * self.y)
+    }
+    fn is_bounded(&self, bound: f64) -> bool {
+        self.x.to_f64().abs() <= bound && self.y.to_f64().abs() <= bound
+    }
+    fn coordinates(self) -> Vec<f64> {
+        vec![self.x.to_f64(), self.y.to_f64()]
+    }
+    #[cfg(feature = "simd")]
+    fn free_boundaries(self, qs: &[Self], eps: f64) -> Vec<OptLineBoundary> {
+        // Vectorized in double precision only, where widening the coordinates leaves the arithmetic unchanged.
+        if F::EPSILON.to_f64() != f64::EPSILON {
+            return qs.windows(2).map(|q| free_boundary(self, q[0], q[1], eps)).collect();
+        }
+        crate::simd::free_boundaries(self, qs, eps)
+    }
+}
+
+/// Point in D dimensions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VectorN<const D: usize>(pub [f64; D]);
+
+/// Point in three dimensions.
+pub type Vector3 = VectorN<3>;
+impl Vector3 {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        VectorN([x, y, z])
+    }
+}
+impl<const D: usize> Point for VectorN<D> {
+    fn dot(self, rhs: Self) -> f64 {
+        self.0.iter().zip(rhs.0).map(|(a, b)| a * b).sum()
+    }
+    fn scale(self, s: f64) -> Self {
+        VectorN(self.0.map(|a| s * a))
+    }
+    fn is_bounded(&self, bound: f64) -> bool {
+        self.0.iter().all(|a| a.abs() <= bound)
+    }
+    fn coordinates(self) -> Vec<f64> {
+        self.0.to_vec()
+    }
+}
+impl<const D: usize> Add for VectorN<D> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        VectorN(core::array::from_fn(|k| self.0[k] + rhs.0[k]))
+    }
+}
+impl<const D: usize> Sub for VectorN<D> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        VectorN(core::array::from_fn(|k| self.0[k] - rhs.0[k]))
+    }
+}
+impl<const D: usize> Mul<VectorN<D>> for f64 {
+    type Output = VectorN<D>;
+    fn mul(self, rhs: VectorN<D>) -> VectorN<D> {
+        rhs.scale(self)
+    }
+}
@@ -0,0 +1,29 @@
+//! Character rendering of diagrams for debugging.
+use pcm::*;
+
+#[test]
+fn golden_diagram() {
+    // ps along the x-axis, qs slightly above it with a short first segment.
+    let ps = vec![Vector::new(0., 0.), Vector::new(1., 0.), Vector::new(2., 0.)];
+    let qs = vec![Vector::new(0., 0.1), Vector::new(0.2, 0.1), Vector::new(2., 0.1)];
+    let fsd = FSD::new(ps, qs, 0.5);
+    // Rows top to bottom are the corners at qs[2], qs[1] and qs[0], with the vertical boundaries in between.
+    let expected = concat!(
+        ".   .---+\n",
+        ":   :   :\n",
+        "+---.   .\n",
+        "|        \n",
+        "+---.   .\n",
+    );
+    assert_eq!(fsd.to_ascii(None), expected);
+
+    let steps = [(0., 1.), (1., 1.2), (2., 1.8)];
+    let expected = concat!(
+        ".   .---+\n",
+        ": * : * :\n",
+        "+---.   .\n",
+        "|        \n",
+        "+---.   .\n",
+    );
+    assert_eq!(fsd.to_ascii(Some(&steps)), expected);
+}
@@ -0,0 +1,48 @@
+//! Audit logs append every decision as a JSON line.
+use pcm::{audit::curve_hash, *};
+use serde_json::Value;
+
+fn curve(points: &[(f64, f64)]) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x, y)).collect()
+}
+
+/// Records in the log, one JSON object per line.
+fn read(path: &std::path::Path) -> Vec<Value> {
+    std::fs::read_to_string(path).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+}
+
+#[test]
+fn appends_records() {
+    let path = std::env::temp_dir().join(format!("pcm_audit_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let ps = curve(&[(0., 0.), (1., 0.)]);
+    let qs = curve(&[(-1., 0.1), (3., 0.1)]);
+
+    let mut log = AuditLog::open(&path).unwrap();
+    let (a, b) = audited_partial_curve(&mut log, ps.clone(), qs.clone(), 0.5, true).unwrap().unwrap();
+    assert_eq!(audited_partial_curve(&mut log, ps.clone(), qs.clone(), 0.05, false).unwrap(), None);
+    drop(log);
+    // Reopening appends rather than truncates.
+    let mut log = AuditLog::open(&path).unwrap().with_options(ExportOptions::default().with_coordinate_decimals(2));
+    assert!(audited_partial_curve(&mut log, ps.clone(), qs.clone(), -1., false).is_err());
+    drop(log);
+
+    let records = read(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(records.len(), 3);
+    let (matched, unmatched, rejected) = (&records[0], &records[1], &records[2]);
+    assert_eq!(matched["ps_hash"], curve_hash(&ps));
+    assert_eq!(matched["qs_hash"], curve_hash(&qs));
+    assert_eq!((matched["ps_len"].as_u64(), matched["qs_len"].as_u64()), (Some(2), Some(2)));
+    assert_eq!(matched["decision"], true);
+    assert_eq!(matched["interval"], serde_json::json!([a, b]));
+    assert!((matched["matched_length"].as_f64().unwrap() - 4. * (b - a)).abs() < 1e-9);
+    assert!((matched["min_eps"].as_f64().unwrap() - 0.1).abs() < 1e-5);
+    assert!(matched["error"].is_null());
+    assert_eq!(unmatched["decision"], false);
+    assert!(unmatched["interval"].is_null() && unmatched["min_eps"].is_null() && unmatched["with_min_eps"] == false);
+    assert_eq!(rejected["decision"], false);
+    assert_eq!(rejected["eps"], -1.);
+    assert!(rejected["error"].as_str().is_some_and(|e| !e.is_empty()));
+    assert!(records.iter().all(|r| r["timestamp"].as_f64().unwrap() > 0. && r["elapsed_ms"].as_f64().unwrap() >= 0.));
+}
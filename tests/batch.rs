@@ -0,0 +1,52 @@
+//! One-vs-many matching agrees with matching every candidate on its own.
+use pcm::*;
+
+/// Zigzag curve of n points shifted by (dx, dy).
+fn zigzag(n: usize, dx: f64, dy: f64) -> Curve {
+    (0..n).map(|i| Vector::new(dx + i as f64, dy + (i % 2) as f64)).collect()
+}
+
+#[test]
+fn agrees_with_single_matches() {
+    let query = zigzag(4, 2., 0.1);
+    let candidates: Vec<Curve> = (0..30).map(|k| zigzag(3 + k % 7, (k % 5) as f64 - 1., 0.05 * k as f64)).collect();
+    let eps = 0.4;
+    let results = match_many(&query, &candidates, eps).unwrap();
+    assert_eq!(results.len(), candidates.len());
+    assert!(results.iter().any(|r| r.is_match()) && results.iter().any(|r| !r.is_match()));
+    for (qs, result) in candidates.iter().zip(&results) {
+        assert_eq!(result.interval(), try_partial_curve(query.clone(), qs.clone(), eps).unwrap());
+        if result.is_match() {
+            validate_steps(&query, qs, &result.steps, eps).unwrap();
+            assert!(result.eps < eps + EPS);
+            assert!(validate_steps(&query, qs, &result.steps, result.eps * 0.9).is_err());
+        } else {
+            assert_eq!(result.eps, f64::INFINITY);
+        }
+    }
+}
+
+#[test]
+fn malformed_input() {
+    let query = zigzag(4, 0., 0.);
+    let candidates = vec![query.clone()];
+    assert!(match_many(&query, &candidates, 0.).is_err());
+    assert!(match_many(&vec![query[0]], &candidates, 1.).is_err());
+    let error = match_many(&query, &[query.clone(), vec![]], 1.).unwrap_err();
+    assert!(matches!(error, PcmError::Candidate { index: 1, .. }));
+    assert!(error.to_string().starts_with("Candidate 1"));
+    assert!(match_many(&query, &[], 1.).unwrap().is_empty());
+}
+
+#[test]
+fn either_direction() {
+    let query = zigzag(4, 2., 0.1);
+    let candidates = vec![zigzag(8, 0., 0.), zigzag(8, 0., 0.).reversed(), zigzag(8, 0., 5.)];
+    let results = match_many_bidirectional(&query, &candidates, 0.4).unwrap();
+    assert!(results[0].is_match() && !results[0].reversed);
+    assert!(results[1].is_match() && results[1].reversed);
+    assert!(!results[2].is_match());
+    // Steps are in parameters on the reversed query.
+    validate_steps(&query.reversed(), &candidates[1], &results[1].steps, 0.4).unwrap();
+    assert!(!match_many(&query, &candidates, 0.4).unwrap()[1].is_match());
+}
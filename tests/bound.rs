@@ -0,0 +1,47 @@
+//! Failed decisions report a lower bound on the required threshold, just below the partial Fréchet distance.
+use pcm::*;
+
+fn curve(points: &[(f64, f64)]) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x, y)).collect()
+}
+
+#[test]
+fn reversed_segment() {
+    // Every vertex lies on the target, but walking it backwards needs half its length.
+    let (ps, qs) = (curve(&[(2., 0.), (0., 0.)]), curve(&[(0., 0.), (2., 0.)]));
+    assert_eq!(eps_lower_bound(&ps, &qs).unwrap(), 0.);
+    let bound = try_partial_curve_or_bound(ps.clone(), qs.clone(), 0.5).unwrap().unwrap_err();
+    assert!((bound - 1.).abs() < 1e-9);
+    assert!(try_partial_curve_or_bound(ps, qs, 1.01).unwrap().is_ok());
+}
+
+#[test]
+fn fails_at_distance() {
+    // Decision fails exactly at the distance (cornerpoints are free strictly below the threshold).
+    let (ps, qs) = (curve(&[(0., 1.), (1., 1.)]), curve(&[(0., 0.), (1., 0.)]));
+    for eps in [0.5, 1.] {
+        let bound = try_partial_curve_or_bound(ps.clone(), qs.clone(), eps).unwrap().unwrap_err();
+        assert!(bound >= 1. && bound - 1. < 1e-6);
+    }
+}
+
+#[test]
+fn random_bounds() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x510e527fade682d1u64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    for k in 0..100 {
+        let ps: Curve = (0..2 + k % 3).map(|_| Vector::new(2. * random(), 2. * random())).collect();
+        let qs: Curve = (0..2 + k % 5).map(|_| Vector::new(2. * random(), 2. * random())).collect();
+        let eps = 0.1 + 0.5 * random();
+        let (distance, _) = partial_frechet_distance(&ps, &qs).unwrap();
+        match try_partial_curve_or_bound(ps.clone(), qs.clone(), eps).unwrap() {
+            Ok(_) => assert!(eps >= distance - 1e-9),
+            Err(bound) => {
+                assert!(bound >= eps && bound <= distance + 1e-9);
+                assert!(bound >= eps_lower_bound(&ps, &qs).unwrap() - 1e-9);
+                assert!(distance - bound < 1e-9);
+            },
+        }
+    }
+}
@@ -0,0 +1,24 @@
+//! The match cache evicts the least recently used result once full.
+use pcm::*;
+
+#[test]
+fn evicts_least_recently_used() {
+    let qs: Curve = (0..6).map(|i| Vector::new(i as f64, 0.)).collect();
+    let probes: Vec<Curve> = (0..4).map(|k| vec![Vector::new(k as f64, 0.1), Vector::new(k as f64 + 1., 0.1)]).collect();
+    let cache = MatchCache::new(3);
+    for ps in &probes[..3] {
+        cache.partial_curve(ps, &qs, 0.5).unwrap();
+    }
+    assert_eq!((cache.len(), cache.stats()), (3, (0, 3)));
+
+    // Touch the oldest, so the second probe becomes least recently used and is evicted for the fourth.
+    cache.partial_curve(&probes[0], &qs, 0.5).unwrap();
+    cache.partial_curve(&probes[3], &qs, 0.5).unwrap();
+    assert_eq!((cache.len(), cache.stats()), (3, (1, 4)));
+
+    cache.partial_curve(&probes[0], &qs, 0.5).unwrap();
+    cache.partial_curve(&probes[2], &qs, 0.5).unwrap();
+    assert_eq!(cache.stats(), (3, 4));
+    cache.partial_curve(&probes[1], &qs, 0.5).unwrap();
+    assert_eq!(cache.stats(), (3, 5));
+}
@@ -0,0 +1,35 @@
+//! Calibration recommends the threshold separating matching from non-matching pairs.
+use pcm::*;
+
+#[test]
+fn separable_set() {
+    // Matching pairs are translated by at most 0.25, non-matching ones by at least 2.
+    let mut rng = seeded(7);
+    let mut pairs = vec![];
+    for k in 0..12 {
+        let ps = random_curve(5, 10., &mut rng);
+        let (offset, is_match) = if k % 2 == 0 { (0.05 + 0.02 * k as f64, true) } else { (2. + 0.1 * k as f64, false) };
+        let qs = translate_curve(&ps, Vector::new(offset, 0.));
+        pairs.push((ps, qs, is_match));
+    }
+    let calibration = calibrate(&pairs, 1e-6).unwrap();
+    assert!(calibration.points.windows(2).all(|w| w[0].eps < w[1].eps));
+    let point = calibration.recommendation().unwrap();
+    assert_eq!((point.precision, point.recall, point.f1()), (1., 1., 1.));
+    // The recommended threshold accepts every matching pair and rejects every other one.
+    for (ps, qs, is_match) in &pairs {
+        assert_eq!(FSD::new(ps.clone(), qs.clone(), point.eps + 1e-6).check_pcm(), *is_match);
+    }
+    assert!(point.eps <= 0.25 + 1e-5);
+}
+
+#[test]
+fn degenerate_sets() {
+    assert_eq!(calibrate(&[], 1e-6).unwrap().recommendation(), None);
+    // Without matching pairs recall is one at every threshold, precision zero.
+    let ps = vec![Vector::new(0., 0.), Vector::new(1., 0.)];
+    let calibration = calibrate(&[(ps.clone(), translate_curve(&ps, Vector::new(0., 3.)), false)], 1e-6).unwrap();
+    assert_eq!(calibration.points.len(), 1);
+    assert_eq!((calibration.points[0].precision, calibration.points[0].recall), (0., 1.));
+    assert!(calibrate(&[(ps.clone(), vec![ps[0]], true)], 1e-6).is_err());
+}
@@ -0,0 +1,53 @@
+//! Checkpoints let an interrupted batch resume with the jobs it has not completed.
+use pcm::*;
+
+fn path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("pcm_checkpoint_{name}_{}.txt", std::process::id()))
+}
+
+#[test]
+fn kill_and_resume() {
+    let path = path("resume");
+    let mut checkpoint = Checkpoint::open(&path, false).unwrap().with_interval(2);
+    for id in ["a", "b", "c"] {
+        checkpoint.mark_done(id).unwrap();
+    }
+    // Killed before the last (pending) job is written.
+    std::mem::forget(checkpoint);
+
+    let mut checkpoint = Checkpoint::open(&path, true).unwrap().with_interval(2);
+    assert!(checkpoint.is_done("a") && checkpoint.is_done("b") && !checkpoint.is_done("c"));
+    assert_eq!(checkpoint.len(), 2);
+    for id in ["c", "d"] {
+        if !checkpoint.is_done(id) {
+            checkpoint.mark_done_with(id, "{\"matched\": true}").unwrap();
+        }
+    }
+    drop(checkpoint);
+
+    // Completed on resume, with the records of the jobs.
+    let checkpoint = Checkpoint::open(&path, true).unwrap();
+    assert_eq!(checkpoint.len(), 4);
+    assert_eq!(checkpoint.record("a"), Some(""));
+    assert_eq!(checkpoint.record("d"), Some("{\"matched\": true}"));
+    assert_eq!(checkpoint.record("e"), None);
+    drop(checkpoint);
+
+    // Starting afresh forgets them.
+    let checkpoint = Checkpoint::open(&path, false).unwrap();
+    assert!(checkpoint.is_empty());
+    drop(checkpoint);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn rejects_multiline_jobs() {
+    let path = path("multiline");
+    let mut checkpoint = Checkpoint::open(&path, false).unwrap();
+    assert!(checkpoint.mark_done("a\nb").is_err());
+    assert!(checkpoint.mark_done_with("a", "1\n2").is_err());
+    assert!(checkpoint.mark_done_with("a\tb", "").is_err());
+    assert!(checkpoint.is_empty());
+    drop(checkpoint);
+    std::fs::remove_file(&path).unwrap();
+}
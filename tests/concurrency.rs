@@ -0,0 +1,67 @@
+//! Precomputed target-side structures are shared across threads (e.g. between request handlers of a server).
+use std::thread;
+
+use pcm::*;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn shared_structures_are_send_and_sync() {
+    assert_send_sync::<Graph>();
+    assert_send_sync::<ExponentialGraph>();
+    assert_send_sync::<FSD>();
+    assert_send_sync::<MatchCache>();
+    assert_send_sync::<TimedCurve>();
+}
+
+#[test]
+fn concurrent_graph_queries() {
+    // Zigzags between y = 0 at even and y = 1 at odd vertices.
+    let vertices: Vec<(usize, Vector)> = zigzag(Vector::new(0., 0.5), 1., -0.5, 20).into_iter().enumerate().collect();
+    let edges = (0..19).map(|i| (i, i + 1)).collect();
+    let graph = Graph::new(vertices, edges);
+    let queries: Vec<Curve> = (0..8).map(|k| zigzag(Vector::new(0., 0.01 * k as f64 + 0.5), 1., -0.5, 5).into_iter().map(|p| p + Vector::new(k as f64, 0.)).collect()).collect();
+    let expected: Vec<_> = queries.iter().map(|q| partial_curve_graph(&graph, q.clone(), 0.1)).collect();
+
+    thread::scope(|s| {
+        let handles: Vec<_> = queries.iter().map(|q| s.spawn(|| partial_curve_graph(&graph, q.clone(), 0.1))).collect();
+        for (handle, expected) in handles.into_iter().zip(&expected) {
+            assert_eq!(handle.join().unwrap(), *expected);
+        }
+    });
+}
+
+#[test]
+fn concurrent_diagram_queries() {
+    let fsd = FSD::new(zigzag(Vector::new(0., 0.5), 1., -0.5, 6), zigzag(Vector::new(0., 0.7), 1., -0.5, 12), 0.5);
+    let expected = fsd.to_rsd().pcm_steps();
+    thread::scope(|s| {
+        let handles: Vec<_> = (0..8).map(|_| s.spawn(|| fsd.to_rsd().pcm_steps())).collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), expected);
+        }
+    });
+}
+
+#[test]
+fn concurrent_cache_queries() {
+    let cache = MatchCache::new(4);
+    let qs = zigzag(Vector::new(0., 0.5), 1., -0.5, 12);
+    let probes: Vec<Curve> = (0..6).map(|k| zigzag(Vector::new(0., 0.05 * k as f64 + 0.5), 1., -0.5, 4)).collect();
+    let expected: Vec<_> = probes.iter().map(|ps| try_partial_curve(ps.clone(), qs.clone(), 0.5).unwrap()).collect();
+
+    thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(|| {
+                for _ in 0..10 {
+                    for (ps, expected) in probes.iter().zip(&expected) {
+                        assert_eq!(cache.partial_curve(ps, &qs, 0.5).unwrap(), *expected);
+                    }
+                }
+            });
+        }
+    });
+    assert!(cache.len() <= 4);
+    let (hits, misses) = cache.stats();
+    assert_eq!(hits + misses, 8 * 10 * 6);
+}
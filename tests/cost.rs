@@ -0,0 +1,59 @@
+//! Minimal-cost path extraction under the built-in cost models.
+use pcm::*;
+
+fn curve(points: &[(f64, f64)]) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x, y)).collect()
+}
+
+/// Query of length 4 above a long straight target, both sampled every unit at resolution 4.
+fn setting() -> (Curve, Curve) {
+    (curve(&[(0., 0.5), (4., 0.5)]), curve(&[(-4., 0.), (0., 0.), (4., 0.), (8., 0.)]))
+}
+
+/// Arc length walked along qs by the steps.
+fn covered_q(qs: &Curve, steps: &[(f64, f64)]) -> f64 {
+    steps.windows(2).map(|w| qs.point_at(w[0].1).unwrap().distance(qs.point_at(w[1].1).unwrap())).sum()
+}
+
+#[test]
+fn uniform_takes_fewest_moves() {
+    let (ps, qs) = setting();
+    let steps = min_cost_steps(&ps, &qs, 3., &Uniform, 4).unwrap().unwrap();
+    validate_steps(&ps, &qs, &steps, 3.).unwrap();
+    // Four samples to walk along ps, one move each.
+    assert_eq!(steps.len(), 5);
+    assert!(steps.windows(2).all(|w| w[1].0 > w[0].0));
+}
+
+#[test]
+fn distance_weighted_keeps_curves_close() {
+    let (ps, qs) = setting();
+    let steps = min_cost_steps(&ps, &qs, 3., &DistanceWeighted, 4).unwrap().unwrap();
+    validate_steps(&ps, &qs, &steps, 3.).unwrap();
+    // Stays below the query, rather than anywhere within the (wide) threshold as fewest moves do.
+    assert!(steps.iter().all(|&(_, t)| (1. ..=2.).contains(&t)));
+    let leash = |steps: &[(f64, f64)]| steps.iter().map(|&(s, t)| ps.point_at(s).unwrap().distance(qs.point_at(t).unwrap())).fold(0., f64::max);
+    assert!(leash(&steps) < 1.2);
+    assert!(leash(&steps) < leash(&min_cost_steps(&ps, &qs, 3., &Uniform, 4).unwrap().unwrap()));
+}
+
+#[test]
+fn slope_penalty_keeps_speeds_equal() {
+    let (ps, qs) = setting();
+    // Without penalty the shortest path stands still on qs.
+    let steps = min_cost_steps(&ps, &qs, 3., &SlopePenalty { weight: 0. }, 4).unwrap().unwrap();
+    validate_steps(&ps, &qs, &steps, 3.).unwrap();
+    assert_eq!(covered_q(&qs, &steps), 0.);
+    // Penalized it walks both curves at the same speed.
+    let steps = min_cost_steps(&ps, &qs, 3., &SlopePenalty { weight: 10. }, 4).unwrap().unwrap();
+    validate_steps(&ps, &qs, &steps, 3.).unwrap();
+    assert!((covered_q(&qs, &steps) - 4.).abs() < 1e-9);
+    assert!(steps.windows(2).all(|w| w[1].0 > w[0].0 && w[1].1 > w[0].1));
+}
+
+#[test]
+fn blocked_or_invalid() {
+    let (ps, qs) = setting();
+    assert_eq!(min_cost_steps(&ps, &qs, 0.4, &Uniform, 4).unwrap(), None);
+    assert!(matches!(min_cost_steps(&ps, &qs, 3., &Uniform, 0), Err(PcmError::InvalidArgument(_))));
+}
@@ -0,0 +1,31 @@
+//! Maximum-coverage extraction prefers the match covering most of the target over the first one found.
+use pcm::{curve::arc_length, *};
+
+fn curve(points: &[(f64, f64)]) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x, y)).collect()
+}
+
+#[test]
+fn beats_first_match() {
+    // Target passes the query once straight, later it zig-zags along it (covering more arc length).
+    let ps = curve(&[(0., 0.), (1., 0.)]);
+    let qs = curve(&[(0., 0.), (1., 0.), (1., 3.), (0., 3.), (0., 0.2), (0.25, -0.2), (0.5, 0.2), (0.75, -0.2), (1., 0.2)]);
+    let eps = 0.5;
+    let first = partial_curve_matching(ps.clone(), qs.clone(), eps).unwrap().unwrap();
+    let steps = max_coverage_steps(ps.clone(), qs.clone(), eps).unwrap().unwrap();
+    validate_steps(&ps, &qs, &steps, eps).unwrap();
+    let best = Matching::new(ps.clone(), qs.clone(), steps).unwrap();
+    assert!(first.covered_interval().1 < 2.);
+    assert!(best.covered_interval().0 > 3.);
+    assert!(best.coverage() > first.coverage());
+    // Covers the zig-zag from within eps of the start of the query on.
+    let length = qs.length();
+    assert!((best.coverage() - (length - arc_length(&qs, 3. + 2.5 / 2.8)) / length).abs() < 1e-9);
+}
+
+#[test]
+fn no_match() {
+    let ps = curve(&[(0., 0.), (1., 0.)]);
+    let qs = curve(&[(0., 2.), (1., 2.)]);
+    assert!(max_coverage_steps(ps, qs, 0.5).unwrap().is_none());
+}
@@ -0,0 +1,63 @@
+//! Arc-length helpers, validation and normalization of curves.
+use pcm::*;
+
+#[test]
+fn length_and_point_at() {
+    let c = vec![Vector::new(0., 0.), Vector::new(3., 4.), Vector::new(3., 6.)];
+    assert_eq!(c.length(), 7.);
+    assert_eq!(c.point_at(0.5).unwrap(), Vector::new(1.5, 2.));
+    assert_eq!(c.point_at(1.5).unwrap(), Vector::new(3., 5.));
+    assert_eq!(c.point_at(2.).unwrap(), Vector::new(3., 6.));
+    assert_eq!(vec![Vector::new(1., 1.)].length(), 0.);
+}
+
+#[test]
+fn resample_evenly() {
+    let c = vec![Vector::new(0., 0.), Vector::new(3., 4.), Vector::new(3., 6.)];
+    let r = c.resample_by_arclength(2.);
+    // Samples at arc lengths 0, 2, 4, 6 and the endpoint at 7.
+    assert_eq!(r.len(), 5);
+    assert_eq!((r[0], r[4]), (c[0], c[2]));
+    assert!(r[1].distance(Vector::new(1.2, 1.6)) < EPS);
+    assert!(r[2].distance(Vector::new(2.4, 3.2)) < EPS);
+    assert!(r[3].distance(Vector::new(3., 5.)) < EPS);
+    // Invalid spacings leave the curve unchanged.
+    assert_eq!(c.resample_by_arclength(0.), c);
+    assert_eq!(c.resample_by_arclength(f64::NAN), c);
+}
+
+#[test]
+fn densify_evenly() {
+    let c = vec![Vector::new(0., 0.), Vector::new(3., 4.), Vector::new(3., 6.)];
+    let d = c.densify(1.5);
+    // Segments of length 5 and 2 split into 4 and 2 even parts.
+    assert_eq!(d.len(), 7);
+    assert!(d.windows(2).all(|w| w[0].distance(w[1]) <= 1.5 + 1e-12));
+    assert!((d.length() - c.length()).abs() < 1e-12);
+    // Original vertices are kept exactly.
+    assert_eq!((d[0], d[4], d[6]), (c[0], c[1], c[2]));
+    assert_eq!(d[2], Vector::new(1.5, 2.));
+    // Short enough already.
+    assert_eq!(c.densify(5.), c);
+}
+
+#[test]
+fn densify_invalid_length() {
+    let c = vec![Vector::new(0., 0.), Vector::new(3., 4.)];
+    for length in [0., -1., f64::NAN, f64::INFINITY] {
+        assert_eq!(c.densify(length), c);
+    }
+    assert!(Curve::new().densify(1.).is_empty());
+}
+
+#[test]
+fn validate_and_normalize() {
+    let c = vec![Vector::new(0., 0.), Vector::new(1., 0.), Vector::new(1., 0.), Vector::new(f64::NAN, 0.), Vector::new(1., 0.), Vector::new(2., 0.)];
+    assert!(matches!(c.validate(), Err(PcmError::InvalidPoint { index: 3, .. })));
+    assert!(matches!(c[..3].to_vec().validate(), Err(PcmError::DuplicatePoint { index: 2 })));
+    // Dropping the invalid point leaves a duplicate, which is dropped as well.
+    let normalized = c.normalize().unwrap();
+    assert_eq!(normalized, vec![Vector::new(0., 0.), Vector::new(1., 0.), Vector::new(2., 0.)]);
+    normalized.validate().unwrap();
+    assert!(matches!(vec![Vector::new(1., 1.), Vector::new(1., 1.), Vector::new(f64::INFINITY, 1.)].normalize(), Err(PcmError::EmptyCurve { len: 1 })));
+}
@@ -0,0 +1,33 @@
+//! Matching against closed loops, wrapping around the end of the target.
+use pcm::*;
+
+/// Unit square loop starting at the origin (not explicitly closed).
+fn square() -> Curve {
+    vec![Vector::new(0., 0.), Vector::new(1., 0.), Vector::new(1., 1.), Vector::new(0., 1.)]
+}
+
+#[test]
+fn wraps_around_the_start() {
+    // Query runs along the left side down and the bottom side right, across the start of the loop.
+    let ps = vec![Vector::new(0., 0.5), Vector::new(0., 0.), Vector::new(0.5, 0.)];
+    assert!(partial_curve_matching(ps.clone(), square(), 0.01).unwrap().is_none());
+    let cyclic = cyclic_matching(ps.clone(), square(), 0.01).unwrap().unwrap();
+    assert_eq!(cyclic.period, 4);
+    assert!(cyclic.wraps());
+    let (t0, t1) = cyclic.interval();
+    assert!((t0 - 3.5).abs() < 0.02 && (t1 - 0.5).abs() < 0.02);
+    validate_steps(&ps, &cyclic.matching.target, &cyclic.matching.steps, 0.01).unwrap();
+}
+
+#[test]
+fn plain_match_does_not_wrap() {
+    let ps = vec![Vector::new(0.2, 0.), Vector::new(1., 0.), Vector::new(1., 0.5)];
+    let cyclic = cyclic_matching(ps, square(), 0.01).unwrap().unwrap();
+    assert!(!cyclic.wraps());
+    let (t0, t1) = cyclic.interval();
+    assert!(t0 < t1 && (t0 - 0.2).abs() < 0.02 && (t1 - 1.5).abs() < 0.02);
+    // An explicitly closed loop unrolls the same.
+    let mut closed = square();
+    closed.push(closed[0]);
+    assert_eq!(unroll_loop(&closed), unroll_loop(&square()));
+}
@@ -0,0 +1,27 @@
+//! Streaming decision agrees with the decision on the stored diagram, in both sweep orientations.
+use pcm::*;
+
+#[test]
+fn agrees_with_diagram() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x6a09e667f3bcc909u64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    let mut matches = 0;
+    for k in 0..600 {
+        let (n, m) = [(4, 9), (9, 4), (6, 6), (2, 3)][k % 4];
+        let ps: Curve = (0..n).map(|i| Vector::new(i as f64 + random(), 3. * random())).collect();
+        let qs: Curve = (0..m).map(|j| Vector::new(j as f64 - 1. + random(), 3. * random())).collect();
+        let eps = 0.5 + 2. * random();
+        let expected = FSD::new(ps.clone(), qs.clone(), eps).check_pcm();
+        assert_eq!(pcm_decide(&ps, &qs, eps).unwrap(), expected, "{ps:?} {qs:?} {eps}");
+        matches += expected as usize;
+    }
+    assert!(matches > 100 && matches < 500);
+}
+
+#[test]
+fn invalid_input() {
+    let qs = vec![Vector::new(0., 0.), Vector::new(1., 0.)];
+    assert!(pcm_decide(&vec![Vector::new(0., 0.)], &qs, 1.).is_err());
+    assert!(pcm_decide(&qs, &qs, 0.).is_err());
+}
@@ -0,0 +1,33 @@
+//! Deduplication groups curves following the same route, keeping partial overlaps apart.
+use pcm::*;
+
+fn line(x0: f64, x1: f64, n: usize) -> Curve {
+    (0..n).map(|i| Vector::new(x0 + (x1 - x0) * i as f64 / (n - 1) as f64, 0.)).collect()
+}
+
+#[test]
+fn exact_and_near_duplicates() {
+    let c = line(0., 10., 2);
+    let groups = dedupe(&[c.clone(), c.clone()], 1.).unwrap();
+    assert_eq!(groups, vec![DuplicateGroup { representative: 0, members: vec![0, 1] }]);
+
+    // Near duplicates (resampled and slightly offset) join the group, a short probe along it and an unrelated curve do not.
+    let near: Curve = line(0., 10., 6).into_iter().map(|p| p + Vector::new(0., 0.2)).collect();
+    let probe = line(4., 6., 3);
+    let unrelated: Curve = line(0., 10., 2).into_iter().map(|p| p + Vector::new(0., 5.)).collect();
+    let groups = dedupe(&[c.clone(), probe.clone(), near, unrelated, c], 1.).unwrap();
+    assert_eq!(groups, vec![
+        DuplicateGroup { representative: 0, members: vec![0, 2, 4] },
+        DuplicateGroup { representative: 1, members: vec![1] },
+        DuplicateGroup { representative: 3, members: vec![3] },
+    ]);
+}
+
+#[test]
+fn probe_kept_separate_from_long_target() {
+    let (target, probe) = (line(0., 100., 11), line(40., 60., 3));
+    let groups = dedupe(&[target, probe], 1.).unwrap();
+    assert_eq!(groups.len(), 2);
+    // The target does not lie along the probe, so no coverage makes them duplicates.
+    assert_eq!(dedupe_with_coverage(&[line(0., 100., 11), line(40., 60., 3)], 1., 0.).unwrap().len(), 2);
+}
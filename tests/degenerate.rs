@@ -0,0 +1,91 @@
+//! Exact predicates decide degenerate input (collinear points, coincident vertices, tangent boundaries) consistently and deterministically.
+use pcm::*;
+
+/// Curve through the given lattice points, shifted by (dx, dy).
+fn lattice(points: &[(i32, i32)], dx: f64, dy: f64) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x as f64 + dx, y as f64 + dy)).collect()
+}
+
+#[test]
+fn tangent_and_touching() {
+    // Disc around (0, 1) touches the segment from (-1, 0) to (1, 0) in a single point.
+    assert!(free_interval_of(Vector::new(0., 1.), Vector::new(-1., 0.), Vector::new(1., 0.), 1.).is_none());
+    // Corner at exactly eps (3-4-5 triangle) is not free, and neither is the start of its boundary.
+    let ps = vec![Vector::new(0., 0.), Vector::new(1., 0.)];
+    let qs = vec![Vector::new(3., 4.), Vector::new(3., -4.)];
+    let fsd = FSD::new_with(ps, qs, 5., Predicates::Exact);
+    assert!(!fsd.corners[(0, 0)] && !fsd.corners[(0, 1)]);
+    assert!(matches!(fsd.segs[(0, 0, 0)], Some(LineBoundary { a, b }) if a > 0. && b < 1.));
+    fsd.validate().unwrap();
+}
+
+/// Free interval with exact predicates, through a diagram of single segments.
+fn free_interval_of(p: Vector, q0: Vector, q1: Vector, eps: f64) -> OptLineBoundary {
+    FSD::new_with(vec![p, p], vec![q0, q1], eps, Predicates::Exact).segs[(0, 0, 0)]
+}
+
+#[test]
+fn lattice_curves_consistent() {
+    // Collinear runs, coincident vertices and backtracking, with thresholds hitting lattice distances exactly.
+    let curves = [
+        vec![(0, 0), (1, 0), (2, 0), (2, 0), (3, 0)],
+        vec![(0, 1), (1, 1), (1, 1), (2, 1), (4, 1)],
+        vec![(0, 0), (2, 0), (1, 0), (3, 0)],
+        vec![(0, 0), (0, 1), (1, 1), (1, 0), (0, 0)],
+        vec![(-1, -1), (0, 0), (1, 1), (3, 3)],
+    ];
+    for ps in &curves {
+        for qs in &curves {
+            for eps in [1., 2., 5f64.sqrt()] {
+                let base = FSD::new_with(lattice(ps, 0., 0.), lattice(qs, 0., 0.), eps, Predicates::Exact);
+                base.validate().unwrap();
+                let rsd = base.to_rsd();
+                rsd.validate_reachability(&base).unwrap();
+                // Translation (exact on these coordinates) leaves every decision unchanged.
+                let shifted = FSD::new_with(lattice(ps, 1024.5, -4096.25), lattice(qs, 1024.5, -4096.25), eps, Predicates::Exact);
+                assert_eq!(base.corners, shifted.corners);
+                assert!(base.segs.iter().zip(shifted.segs.iter()).all(|(b1, b2)| b1.is_some() == b2.is_some()));
+                assert_eq!(rsd.check_pcm(), shifted.check_pcm());
+                if let Some(steps) = rsd.pcm_steps() {
+                    validate_steps(&lattice(ps, 0., 0.), &lattice(qs, 0., 0.), &steps, eps).unwrap();
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn agrees_with_float_in_general_position() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x2545f4914f6cdd1du64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    for _ in 0..200 {
+        let ps: Curve = (0..5).map(|_| Vector::new(10. * random(), 10. * random())).collect();
+        let qs: Curve = (0..7).map(|_| Vector::new(10. * random(), 10. * random())).collect();
+        let eps = 1. + 4. * random();
+        let float = FSD::new(ps.clone(), qs.clone(), eps);
+        let exact = FSD::new_with(ps, qs, eps, Predicates::Exact);
+        assert_eq!(float.corners, exact.corners);
+        assert_eq!(float.to_rsd().check_pcm(), exact.to_rsd().check_pcm());
+    }
+}
+
+#[test]
+fn float_boundaries_agree_with_corners() {
+    // Corners at exactly eps (3-4-5 triangles) on a regular and a zero-length segment, and a boundary touching a corner only.
+    let ps = vec![Vector::new(0., 0.), Vector::new(1., 0.)];
+    for qs in [vec![Vector::new(3., 4.), Vector::new(3., -4.)], vec![Vector::new(3., 4.), Vector::new(3., 4.), Vector::new(6., 8.)]] {
+        let fsd = FSD::new(ps.clone(), qs, 5.);
+        fsd.validate().unwrap();
+        assert!(fsd.segs[(0, 0, 0)].is_none_or(|LineBoundary { a, b }| a > 0. && b < 1.));
+    }
+    // Degenerate lattice curves with float predicates.
+    let curves = [vec![(0, 0), (1, 0), (2, 0), (2, 0), (3, 0)], vec![(0, 1), (1, 1), (1, 1), (2, 1), (4, 1)], vec![(0, 0), (0, 1), (1, 1), (1, 0), (0, 0)]];
+    for ps in &curves {
+        for qs in &curves {
+            for eps in [1., 2., 5f64.sqrt()] {
+                FSD::new(lattice(ps, 0., 0.), lattice(qs, 0., 0.), eps).validate().unwrap();
+            }
+        }
+    }
+}
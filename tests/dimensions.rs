@@ -0,0 +1,39 @@
+//! Curves in other dimensions than the plane.
+use pcm::*;
+
+fn zigzag(n: usize, offset: f64) -> Curve {
+    (0..n).map(|i| Vector::new(i as f64, offset + (i % 2) as f64)).collect()
+}
+
+/// Embed a planar curve in three dimensions at height z.
+fn lift(c: &Curve, z: f64) -> Curve<Vector3> {
+    c.iter().map(|p| Vector3::new(p.x, p.y, z)).collect()
+}
+
+#[test]
+fn embedded_planar_curves_match_alike() {
+    let (ps, qs) = (zigzag(4, 0.3), zigzag(9, 0.));
+    for eps in [0.2, 0.4, 0.8] {
+        let planar = FSD::new(ps.clone(), qs.clone(), eps).to_rsd().pcm_steps();
+        let spatial = FSD::new(lift(&ps, 1.), lift(&qs, 1.), eps).to_rsd().pcm_steps();
+        assert_eq!(planar, spatial);
+        assert_eq!(try_partial_curve(ps.clone(), qs.clone(), eps).unwrap(), try_partial_curve(lift(&ps, 2.), lift(&qs, 2.), eps).unwrap());
+    }
+}
+
+#[test]
+fn height_separates_curves() {
+    let qs = lift(&zigzag(9, 0.), 0.);
+    let ps = lift(&zigzag(4, 0.), 1.);
+    assert!(!FSD::new(ps.clone(), qs.clone(), 0.9).check_pcm());
+    let steps = FSD::new(ps.clone(), qs.clone(), 1.1).to_rsd().pcm_steps().unwrap();
+    validate_steps(&ps, &qs, &steps, 1.1).unwrap();
+}
+
+#[test]
+fn higher_dimensions() {
+    let ps: Curve<VectorN<4>> = vec![VectorN([0., 0., 0., 1.]), VectorN([1., 1., 1., 1.])];
+    let qs: Curve<VectorN<4>> = vec![VectorN([-1., -1., -1., 1.]), VectorN([2., 2., 2., 1.])];
+    assert_eq!(try_partial_curve(ps.clone(), qs.clone(), 0.1).unwrap().map(|(a, b)| (a > 0.3, b < 0.7)), Some((true, true)));
+    assert!(FSD::try_new(vec![VectorN([f64::NAN; 4]), ps[0]], qs, 1.).is_err());
+}
@@ -0,0 +1,56 @@
+//! Discrete diagrams only couple vertices, approximating the continuous ones for densely sampled curves.
+use pcm::*;
+
+fn curve(points: &[(f64, f64)]) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x, y)).collect()
+}
+
+#[test]
+fn threshold_is_strict() {
+    // Vertices at exactly eps are not free, as in the continuous diagram.
+    let ps = curve(&[(0., 0.), (1., 0.)]);
+    let qs = curve(&[(0., 1.), (1., 1.)]);
+    assert!(!DiscreteFSD::new(ps.clone(), qs.clone(), 1.).check_pcm());
+    assert!(!FSD::new(ps.clone(), qs.clone(), 1.).check_pcm());
+    assert!(DiscreteFSD::new(ps.clone(), qs.clone(), 1. + 1e-9).check_pcm());
+}
+
+#[test]
+fn steps_couple_vertices() {
+    let ps = curve(&[(0., 0.), (1., 0.), (2., 0.)]);
+    let qs = curve(&[(-2., 0.2), (-1., 0.2), (0., 0.2), (1., 0.2), (2., 0.2), (3., 0.2)]);
+    let steps = DiscreteFSD::new(ps.clone(), qs.clone(), 0.5).pcm_steps().unwrap();
+    assert_eq!(steps, vec![(0., 2.), (1., 3.), (2., 4.)]);
+    validate_steps(&ps, &qs, &steps, 0.5).unwrap();
+    // The continuous diagram matches where the discrete one cannot (qs has no vertex near the middle of ps).
+    let qs = curve(&[(-2., 0.2), (3., 0.2)]);
+    assert!(DiscreteFSD::new(ps.clone(), qs.clone(), 0.5).pcm_steps().is_none());
+    assert!(FSD::new(ps, qs, 0.5).check_pcm());
+}
+
+#[test]
+fn discrete_match_is_continuous_match() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x2545f4914f6cdd1du64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    let mut matched = 0;
+    for _ in 0..300 {
+        let ps: Curve = (0..4).map(|_| Vector::new(4. * random(), 4. * random())).collect();
+        let qs: Curve = (0..8).map(|_| Vector::new(4. * random(), 4. * random())).collect();
+        let eps = 0.5 + 2. * random();
+        let discrete = DiscreteFSD::new(ps.clone(), qs.clone(), eps);
+        if let Some(steps) = discrete.pcm_steps() {
+            matched += 1;
+            validate_steps(&ps, &qs, &steps, eps).unwrap();
+            assert!(FSD::new(ps, qs, eps).check_pcm());
+        }
+    }
+    assert!(matched > 10, "{matched}");
+}
+
+#[test]
+fn rejects_malformed_input() {
+    let ps = curve(&[(0., 0.), (1., 0.)]);
+    assert!(DiscreteFSD::try_new(ps.clone(), ps.clone(), 0.).is_err());
+    assert!(DiscreteFSD::try_new(vec![ps[0]], ps, 1.).is_err());
+}
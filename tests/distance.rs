@@ -0,0 +1,52 @@
+//! The partial Fréchet distance is the smallest threshold with a partial match, returned with a matching at it.
+use pcm::*;
+
+fn curve(points: &[(f64, f64)]) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x, y)).collect()
+}
+
+#[test]
+fn parallel_offset() {
+    // A segment above a longer one is at its offset, matched straight below it.
+    let ps = curve(&[(1., 0.3), (2., 0.3)]);
+    let qs = curve(&[(0., 0.), (4., 0.)]);
+    let (distance, matching) = partial_frechet_distance(&ps, &qs).unwrap();
+    assert!((distance - 0.3).abs() < 1e-6, "{distance}");
+    validate_steps(&ps, &qs, &matching.steps, distance).unwrap();
+    let (start, end) = matching.covered_interval();
+    assert!((start - 0.25).abs() < 1e-3 && (end - 0.5).abs() < 1e-3, "{start} {end}");
+}
+
+#[test]
+fn endpoints_decide() {
+    // The farthest endpoint pair bounds it from below: ps starts 0.5 beyond the start of qs.
+    let ps = curve(&[(-0.5, 0.), (2., 0.1)]);
+    let qs = curve(&[(0., 0.), (4., 0.)]);
+    let (distance, matching) = partial_frechet_distance(&ps, &qs).unwrap();
+    assert!((distance - 0.5).abs() < 1e-6, "{distance}");
+    validate_steps(&ps, &qs, &matching.steps, distance).unwrap();
+}
+
+#[test]
+fn smallest_matching_threshold() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x853c49e6748fea9bu64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    for _ in 0..50 {
+        let ps: Curve = (0..3).map(|_| Vector::new(4. * random(), 4. * random())).collect();
+        let qs: Curve = (0..6).map(|_| Vector::new(4. * random(), 4. * random())).collect();
+        let (distance, matching) = partial_frechet_distance(&ps, &qs).unwrap();
+        validate_steps(&ps, &qs, &matching.steps, distance).unwrap();
+        assert!(FSD::new(ps.clone(), qs.clone(), distance).check_pcm());
+        assert!(!FSD::new(ps.clone(), qs.clone(), distance * (1. - 1e-6)).check_pcm());
+        // Never beyond the full Fréchet distance.
+        assert!(distance <= try_frechet_distance(&ps, &qs).unwrap() + 1e-9);
+    }
+}
+
+#[test]
+fn rejects_malformed_input() {
+    let ps = curve(&[(0., 0.), (1., 0.)]);
+    assert!(partial_frechet_distance(&vec![ps[0]], &ps).is_err());
+    assert!(partial_frechet_distance(&ps, &curve(&[(0., 0.), (f64::NAN, 0.)])).is_err());
+}
@@ -0,0 +1,41 @@
+//! Dynamic time warping couplings, full and partial.
+use pcm::*;
+
+fn line(xs: &[f64]) -> Curve {
+    xs.iter().map(|&x| Vector::new(x, 0.)).collect()
+}
+
+#[test]
+fn subsequence_finds_piece() {
+    let ps = line(&[2., 3., 4.]);
+    let qs = line(&[0., 1., 2., 3., 3., 4., 5., 6.]);
+    let dtw = DTW::new(ps.clone(), qs.clone());
+    assert_eq!(dtw.cost(), 0.);
+    let steps = dtw.steps();
+    assert_eq!((steps[0], steps[steps.len() - 1]), ((0., 2.), (2., 5.)));
+    // Full DTW pays for the start and end of qs.
+    let full = DTW::new_with(ps, qs, MatchMode::Full);
+    assert_eq!(full.cost(), (2. + 1.) + (1. + 2.));
+}
+
+#[test]
+fn steps_sum_to_cost() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x6a09e667f3bcc909u64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    for _ in 0..100 {
+        let ps: Curve = (0..5).map(|_| Vector::new(10. * random(), 10. * random())).collect();
+        let qs: Curve = (0..7).map(|_| Vector::new(10. * random(), 10. * random())).collect();
+        let mut costs = vec![];
+        for mode in [MatchMode::Full, MatchMode::PrefixOfQ, MatchMode::SuffixOfQ, MatchMode::SubcurveOfQ, MatchMode::SubcurveOfBoth] {
+            let dtw = DTW::new_with(ps.clone(), qs.clone(), mode);
+            let steps = dtw.steps();
+            assert!(steps.windows(2).all(|w| w[0].0 <= w[1].0 && w[0].1 <= w[1].1 && w[0] != w[1]));
+            let sum: f64 = steps.iter().map(|&(i, j)| ps[i as usize].distance(qs[j as usize])).sum();
+            assert!((sum - dtw.cost()).abs() < EPS);
+            costs.push(dtw.cost());
+        }
+        // Looser boundary conditions never cost more.
+        assert!(costs[1] <= costs[0] && costs[2] <= costs[0] && costs[3] <= costs[1].min(costs[2]) && costs[4] <= costs[3]);
+    }
+}
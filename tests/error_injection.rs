@@ -0,0 +1,145 @@
+//! Deliberately corrupt diagrams and steps to verify the validators catch the failure modes they are written for.
+use pcm::*;
+
+/// Curve pairs with partial matches, covering fully free, partially free and blocked cells.
+fn cases() -> Vec<(Curve, Curve, f64)> {
+    let zigzag = |n: usize, offset: f64| -> Curve { (0..n).map(|i| Vector::new(i as f64, offset + (i % 2) as f64)).collect() };
+    vec![
+        (zigzag(4, 0.), zigzag(6, 0.2), 0.5),
+        (zigzag(5, 0.3), zigzag(5, 0.), 0.6),
+        (vec![Vector::new(1., 0.), Vector::new(2., 0.5), Vector::new(3., 0.)], (0..6).map(|i| Vector::new(i as f64, 0.)).collect(), 0.7),
+    ]
+}
+
+/// All boundaries of the diagram which are set.
+fn boundaries(fsd: &FSD) -> Vec<(usize, usize, usize)> {
+    fsd.segs.indexed_iter().filter(|(_, b)| b.is_some()).map(|(curr, _)| curr).collect()
+}
+
+#[test]
+fn valid_input_passes() {
+    for (ps, qs, eps) in cases() {
+        let fsd = FSD::new(ps.clone(), qs.clone(), eps);
+        fsd.validate().unwrap();
+        for mode in [MatchMode::Full, MatchMode::PrefixOfQ, MatchMode::SuffixOfQ, MatchMode::SubcurveOfQ, MatchMode::SubcurveOfBoth] {
+            let rsd = fsd.to_rsd_with(mode);
+            rsd.validate_reachability(&fsd).unwrap();
+            if let Some(steps) = rsd.pcm_steps() {
+                validate_steps(&ps, &qs, &steps, eps).unwrap();
+            }
+        }
+    }
+}
+
+#[test]
+fn flipped_corner_is_caught() {
+    for (ps, qs, eps) in cases() {
+        let fsd = FSD::new(ps, qs, eps);
+        for ((i, j), _) in fsd.corners.indexed_iter() {
+            let mut corrupt = fsd.clone();
+            corrupt.corners[(i, j)] = !corrupt.corners[(i, j)];
+            assert!(corrupt.validate().is_err(), "flipping corner ({i},{j}) went unnoticed");
+        }
+    }
+}
+
+#[test]
+fn shrunk_free_interval_at_corner_is_caught() {
+    // Without the curves only shrinking away from a free cornerpoint is detectable on a FSD.
+    let mut tested = 0;
+    for (ps, qs, eps) in cases() {
+        let fsd = FSD::new(ps, qs, eps);
+        for curr in boundaries(&fsd) {
+            let LineBoundary { a, b } = fsd.segs[curr].unwrap();
+            if a == 0. {
+                let mut corrupt = fsd.clone();
+                corrupt.segs[curr] = Some(LineBoundary { a: 0.5 * b, b });
+                assert!(corrupt.validate().is_err(), "shrinking start of {curr:?} went unnoticed");
+                tested += 1;
+            }
+            if b == 1. {
+                let mut corrupt = fsd.clone();
+                corrupt.segs[curr] = Some(LineBoundary { a, b: 0.5 * (a + 1.) });
+                assert!(corrupt.validate().is_err(), "shrinking end of {curr:?} went unnoticed");
+                tested += 1;
+            }
+        }
+    }
+    assert!(tested > 0);
+}
+
+#[test]
+fn malformed_interval_is_caught() {
+    let (ps, qs, eps) = cases().remove(0);
+    let fsd = FSD::new(ps, qs, eps);
+    let curr = boundaries(&fsd)[0];
+    let LineBoundary { a, b } = fsd.segs[curr].unwrap();
+    for corrupted in [LineBoundary { a: b, b: a }, LineBoundary { a: -0.5, b }, LineBoundary { a, b: 1.5 }, LineBoundary { a: f64::NAN, b }] {
+        let mut corrupt = fsd.clone();
+        corrupt.segs[curr] = Some(corrupted);
+        assert!(corrupt.validate().is_err(), "{corrupted:?} at {curr:?} went unnoticed");
+    }
+    // Boundary outside of the diagram (in the padding of the array).
+    let mut corrupt = fsd.clone();
+    let (w, _) = fsd.dims[0];
+    if w < corrupt.segs.shape()[1] {
+        corrupt.segs[(0, w, 0)] = Some(LineBoundary { a: 0.2, b: 0.4 });
+        assert!(corrupt.validate().is_err());
+    }
+}
+
+#[test]
+fn corrupted_reachable_interval_is_caught() {
+    let mut tested = 0;
+    for (ps, qs, eps) in cases() {
+        let fsd = FSD::new(ps, qs, eps);
+        let rsd = fsd.to_rsd();
+        for curr in boundaries(&rsd) {
+            let LineBoundary { a, b } = rsd.segs[curr].unwrap();
+            // Shrink, drop and extend the reachable interval.
+            let shrunk = Some(LineBoundary { a: a + 0.5 * (b - a), b });
+            let extended = fsd.segs[curr].filter(|f| f.a < a).map(|f| LineBoundary { a: f.a, b });
+            for corrupted in [shrunk, None].into_iter().chain(extended.map(Some)) {
+                let mut corrupt = rsd.clone();
+                corrupt.segs[curr] = corrupted;
+                assert!(corrupt.validate_reachability(&fsd).is_err(), "{corrupted:?} at {curr:?} went unnoticed");
+                tested += 1;
+            }
+        }
+        // Marking an unreachable (but free) boundary as reachable.
+        for curr in boundaries(&fsd) {
+            if rsd.segs[curr].is_none() {
+                let mut corrupt = rsd.clone();
+                corrupt.segs[curr] = fsd.segs[curr];
+                assert!(corrupt.validate_reachability(&fsd).is_err(), "reaching {curr:?} went unnoticed");
+                tested += 1;
+            }
+        }
+    }
+    assert!(tested > 0);
+}
+
+#[test]
+fn corrupted_steps_are_caught() {
+    for (ps, qs, eps) in cases() {
+        let steps = FSD::new(ps.clone(), qs.clone(), eps).to_rsd().pcm_steps().unwrap();
+        let k = steps.len() / 2;
+
+        let mut decreasing = steps.clone();
+        decreasing.swap(0, steps.len() - 1);
+        let mut distant = steps.clone();
+        // Shift part of qs a full segment further (keeping the steps monotonic).
+        let j = (steps[k].1 + 1.).min(qs.len() as f64 - 1.);
+        distant[k..].iter_mut().for_each(|s| s.1 = s.1.max(j));
+        let mut outside = steps.clone();
+        outside.last_mut().unwrap().0 = ps.len() as f64;
+        let mut nan = steps.clone();
+        nan[k].0 = f64::NAN;
+
+        for corrupted in [vec![], decreasing, distant, outside, nan] {
+            assert!(validate_steps(&ps, &qs, &corrupted, eps).is_err(), "{corrupted:?} went unnoticed");
+        }
+        // A tighter threshold than matched with.
+        assert!(validate_steps(&ps, &qs, &steps, 0.5 * eps).is_err());
+    }
+}
@@ -0,0 +1,38 @@
+//! Segment matches map the steps of a matching back to the segments they pair.
+use pcm::*;
+
+fn segment(p_segment: usize, p_interval: (f64, f64), q_segment: usize, q_interval: (f64, f64)) -> SegmentMatch {
+    SegmentMatch { p_segment, p_interval, q_segment, q_interval }
+}
+
+#[test]
+fn step_crossing_cells() {
+    // A single diagonal step from (0, 0.5) to (2, 2.5) passes four cells.
+    let matches = segment_matches(&[(0., 0.5), (2., 2.5)], 3, 4);
+    assert_eq!(matches, vec![
+        segment(0, (0., 0.5), 0, (0.5, 1.)),
+        segment(0, (0.5, 1.), 1, (0., 0.5)),
+        segment(1, (0., 0.5), 1, (0.5, 1.)),
+        segment(1, (0.5, 1.), 2, (0., 0.5)),
+    ]);
+}
+
+#[test]
+fn step_ending_on_last_vertex() {
+    // Pieces on the last vertex of either curve belong to its last segment.
+    let matches = segment_matches(&[(0., 0.), (1., 0.), (2., 1.), (2., 2.)], 3, 3);
+    assert_eq!(matches, vec![
+        segment(0, (0., 1.), 0, (0., 0.)),
+        segment(1, (0., 1.), 0, (0., 1.)),
+        segment(1, (1., 1.), 1, (0., 1.)),
+    ]);
+}
+
+#[test]
+fn merges_within_cell() {
+    // Consecutive steps within one cell form a single match, repeated steps are skipped.
+    let matches = segment_matches(&[(0., 0.), (0.25, 0.1), (0.25, 0.1), (0.5, 0.6), (1., 1.)], 2, 2);
+    assert_eq!(matches, vec![segment(0, (0., 1.), 0, (0., 1.))]);
+    assert!(segment_matches(&[(0., 0.)], 2, 2).is_empty());
+    assert!(segment_matches(&[(0., 0.), (1., 1.)], 1, 2).is_empty());
+}
@@ -0,0 +1,61 @@
+//! Exporters round values to the configured precision, and keep full precision by default.
+//! Threshold matrices export one row per query curve.
+use pcm::{export::round_to, *};
+
+#[test]
+fn steps_precision() {
+    let steps = [(0., 1. / 3.), (0.123456, 2.)];
+    assert_eq!(steps_to_csv(&steps, &ExportOptions::default()), format!("i,j\n0,{}\n0.123456,2\n", 1. / 3.));
+    // Steps follow the step decimals only.
+    let options = ExportOptions::default().with_step_decimals(2).with_coordinate_decimals(5);
+    assert_eq!(steps_to_csv(&steps, &options), "i,j\n0,0.33\n0.12,2\n");
+    assert_eq!(steps_to_json(&steps, &options), "[[0.0,0.33],[0.12,2.0]]");
+    assert_eq!(steps_to_json(&steps, &ExportOptions::default()), format!("[[0.0,{}],[0.123456,2.0]]", 1. / 3.));
+}
+
+#[test]
+fn curve_precision() {
+    let c = vec![Vector::new(1. / 3., -2.5), Vector::new(1e6 + 0.123456, 0.)];
+    assert_eq!(curve_to_csv(&c, &ExportOptions::default()), format!("x,y\n{},-2.5\n1000000.123456,0\n", 1. / 3.));
+    // Coordinates follow the coordinate decimals only.
+    let options = ExportOptions::default().with_coordinate_decimals(3).with_step_decimals(1);
+    assert_eq!(curve_to_csv(&c, &options), "x,y\n0.333,-2.5\n1000000.123,0\n");
+    assert_eq!(curve_to_csv(&c, &ExportOptions::default().with_coordinate_decimals(0)), "x,y\n0,-3\n1000000,0\n");
+}
+
+#[test]
+fn rounding_leaves_extremes() {
+    // Non-finite values and decimals beyond double precision are left as is.
+    assert!(round_to(f64::NAN, Some(2)).is_nan());
+    assert_eq!(round_to(f64::INFINITY, Some(2)), f64::INFINITY);
+    assert_eq!(round_to(1. / 3., Some(20)), 1. / 3.);
+    assert_eq!(round_to(1e300, Some(10)), 1e300);
+}
+
+#[test]
+fn matrix_csv() {
+    let matrix = vec![vec![1. / 3., 2.], vec![0.5, f64::INFINITY]];
+    assert_eq!(matrix_to_csv(&matrix, &ExportOptions::default().with_coordinate_decimals(2)), ",q0,q1\np0,0.33,2\np1,0.5,inf\n");
+    assert_eq!(matrix_to_csv(&matrix, &ExportOptions::default()), format!(",q0,q1\np0,{},2\np1,0.5,inf\n", 1. / 3.));
+    assert_eq!(matrix_to_csv(&[], &ExportOptions::default()), "\n");
+}
+
+#[test]
+fn min_eps_matrix_entries() {
+    // Row i holds the thresholds of ps family member i, column j those against qs family member j.
+    let line = |y: f64| vec![Vector::new(0., y), Vector::new(1., y)];
+    let (ps_family, qs_family) = (vec![line(0.), line(1.), line(3.)], vec![line(0.5), line(2.)]);
+    let matrix = min_eps_matrix(&ps_family, &qs_family, 1e-6).unwrap();
+    assert_eq!(matrix.len(), 3);
+    for (i, row) in matrix.iter().enumerate() {
+        assert_eq!(row.len(), 2);
+        for (j, &eps) in row.iter().enumerate() {
+            assert_eq!(eps, min_partial_eps(&ps_family[i], &qs_family[j], 1e-6).unwrap());
+            assert!((eps - (ps_family[i][0].y - qs_family[j][0].y).abs()).abs() < 1e-5, "{i} {j} {eps}");
+        }
+    }
+    assert!(min_eps_matrix(&[], &qs_family, 1e-6).unwrap().is_empty());
+    assert!(min_eps_matrix(&ps_family, &[], 1e-6).unwrap().iter().all(|row| row.is_empty()));
+    // A single malformed curve fails the whole matrix.
+    assert!(min_eps_matrix(&ps_family, &[line(0.), vec![Vector::new(0., 0.)]], 1e-6).is_err());
+}
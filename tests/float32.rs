@@ -0,0 +1,45 @@
+//! Diagrams stored in f32 agree with the default f64 ones, up to rounding.
+use std::mem::size_of;
+
+use pcm::*;
+
+#[test]
+fn rounding_keeps_corners() {
+    // Endpoints close to (but not at) the cornerpoints must not round onto them.
+    let LineBoundary { a, b } = LineBoundary::<f32>::from_f64(LineBoundary { a: 1e-50, b: 1. - 1e-12 });
+    assert!(0. < a && a < b && b < 1.);
+    let LineBoundary { a, b } = LineBoundary::<f32>::from_f64(LineBoundary { a: 0.5, b: 0.5 + 1e-12 });
+    assert!(a < b);
+    let LineBoundary { a, b } = LineBoundary::<f32>::from_f64(LineBoundary { a: 0., b: 1. });
+    assert!(a == 0. && b == 1.);
+    assert!(size_of::<OptLineBoundary<f32>>() < size_of::<OptLineBoundary>());
+}
+
+#[test]
+fn agrees_with_f64() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x9e3779b97f4a7c15u64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    let mut compared = 0;
+    for _ in 0..200 {
+        let ps: Curve<Vector<f32>> = (0..5).map(|_| Vector::new(10. * random() as f32, 10. * random() as f32)).collect();
+        let qs: Curve<Vector<f32>> = (0..7).map(|_| Vector::new(10. * random() as f32, 10. * random() as f32)).collect();
+        let eps = 1. + 4. * random();
+        let fsd32 = FSD::<f32>::compute(&ps, &qs, eps, Predicates::Float);
+        fsd32.validate().unwrap();
+        let rsd32 = fsd32.to_rsd();
+        rsd32.validate_reachability(&fsd32).unwrap();
+        if let Some(steps) = rsd32.pcm_steps() {
+            validate_steps(&ps, &qs, &steps, eps).unwrap();
+        }
+
+        // Same (widened) curves in f64, the decision only agrees where rounding cannot flip it.
+        let (ps64, qs64): (Curve, Curve) = (ps.iter().map(|p| p.cast()).collect(), qs.iter().map(|q| q.cast()).collect());
+        let rsd64 = FSD::new(ps64, qs64, eps).to_rsd();
+        if rsd64.robustness() > 1e-4 && rsd32.robustness() > 1e-4 {
+            assert_eq!(rsd32.check_pcm(), rsd64.check_pcm());
+            compared += 1;
+        }
+    }
+    assert!(compared > 100);
+}
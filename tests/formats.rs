@@ -0,0 +1,44 @@
+//! Reading and writing curves as CSV, WKT and encoded polylines.
+use pcm::*;
+
+fn curve() -> Curve {
+    vec![Vector::new(-120.2, 38.5), Vector::new(-120.95, 40.7), Vector::new(-126.453, 43.252)]
+}
+
+#[test]
+fn csv() {
+    let c = curve();
+    assert_eq!(curve_from_csv(&curve_to_csv(&c, &ExportOptions::default())).unwrap(), c);
+    assert_eq!(curve_from_csv("0,1\n\n2,3\n").unwrap(), vec![Vector::new(0., 1.), Vector::new(2., 3.)]);
+    assert_eq!(curve_from_csv("time,lat,lon\n0,1,2\n1,3,4\n").unwrap(), vec![Vector::new(2., 1.), Vector::new(4., 3.)]);
+    assert!(curve_from_csv("x,y\n0,1\n2\n").is_err());
+    assert!(curve_from_csv("x,y\n0,1\n").is_err());
+}
+
+#[test]
+fn wkt() {
+    let c = curve();
+    assert_eq!(curve_from_wkt(&curve_to_wkt(&c, &ExportOptions::default())).unwrap(), c);
+    assert_eq!(curve_from_wkt("linestring z (0 1 5, 2 3 6)").unwrap(), vec![Vector::new(0., 1.), Vector::new(2., 3.)]);
+    let curves = curves_from_wkt("MULTILINESTRING ((0 0, 1 1), (2 2, 3 3, 4 4))").unwrap();
+    assert_eq!(curves.iter().map(|c| c.len()).collect::<Vec<_>>(), vec![2, 3]);
+    assert!(curve_from_wkt("MULTILINESTRING ((0 0, 1 1), (2 2, 3 3))").is_err());
+    assert!(curve_from_wkt("POINT (0 0)").is_err());
+    assert!(curve_from_wkt("LINESTRING (0 0, 1)").is_err());
+    assert!(curve_from_wkt("LINESTRING (0 0, 1 1").is_err());
+}
+
+#[test]
+fn polyline() {
+    // Example of the format documentation.
+    let encoded = "_p~iF~ps|U_ulLnnqC_mqNvxq`@";
+    assert_eq!(curve_to_polyline(&curve(), 5), encoded);
+    let decoded = curve_from_polyline(encoded, 5).unwrap();
+    for (p, q) in decoded.iter().zip(curve()) {
+        assert!(p.distance(q) < 1e-9);
+    }
+    let precise = vec![Vector::new(4.123456, 52.654321), Vector::new(-4.1, -52.2)];
+    assert!(curve_from_polyline(&curve_to_polyline(&precise, 6), 6).unwrap().iter().zip(&precise).all(|(p, q)| p.distance(*q) < 1e-9));
+    assert!(curve_from_polyline(&encoded[..encoded.len() - 1], 5).is_err());
+    assert!(curve_from_polyline("_p~iF~ps|U !", 5).is_err());
+}
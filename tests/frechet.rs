@@ -0,0 +1,44 @@
+//! The full Fréchet decision and distance match both curves end-to-end.
+use pcm::*;
+
+fn curve(points: &[(f64, f64)]) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x, y)).collect()
+}
+
+#[test]
+fn identical_curves() {
+    let c = curve(&[(0., 0.), (1., 2.), (3., 1.), (4., 3.)]);
+    // Bisection stops within the tolerance above zero.
+    assert!(try_frechet_distance(&c, &c).unwrap() < EPS);
+    assert!(FSD::new(c.clone(), c.clone(), 1e-3).check_frechet());
+}
+
+#[test]
+fn parallel_offset() {
+    // Vertices at different positions along the curves do not matter, the offset does.
+    let ps = curve(&[(0., 0.3), (1., 0.3), (4., 0.3)]);
+    let qs = curve(&[(0., 0.), (2.5, 0.), (4., 0.)]);
+    let distance = try_frechet_distance(&ps, &qs).unwrap();
+    assert!((distance - 0.3).abs() < 1e-6, "{distance}");
+    assert!(FSD::new(ps.clone(), qs.clone(), 0.31).check_frechet());
+    assert!(!FSD::new(ps, qs, 0.29).check_frechet());
+}
+
+#[test]
+fn mismatched_endpoints() {
+    // ps stops a unit short of the end of qs, though it partially matches a subcurve of qs closely.
+    let ps = curve(&[(0., 0.1), (3., 0.1)]);
+    let qs = curve(&[(0., 0.), (4., 0.)]);
+    assert!(FSD::new(ps.clone(), qs.clone(), 0.2).check_pcm());
+    assert!(!FSD::new(ps.clone(), qs.clone(), 0.99).check_frechet());
+    assert!(FSD::new(ps.clone(), qs.clone(), 1.01).check_frechet());
+    let distance = try_frechet_distance(&ps, &qs).unwrap();
+    assert!((distance - 1.01f64.sqrt()).abs() < 1e-6, "{distance}");
+}
+
+#[test]
+fn rejects_malformed_input() {
+    let c = curve(&[(0., 0.), (1., 0.)]);
+    assert!(try_frechet_distance(&vec![c[0]], &c).is_err());
+    assert!(try_frechet_distance(&c, &vec![]).is_err());
+}
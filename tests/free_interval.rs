@@ -0,0 +1,77 @@
+use pcm::*;
+
+const A: Vector = Vector { x: 0., y: 0. };
+const B: Vector = Vector { x: 4., y: 0. };
+
+#[test]
+fn segment_contained() {
+    let LineBoundary { a, b } = free_interval(Vector::new(2., 0.), A, B, 5.).unwrap();
+    assert_eq!((a, b), (0., 1.));
+}
+
+#[test]
+fn segment_disjoint() {
+    assert!(free_interval(Vector::new(2., 3.), A, B, 1.).is_none());
+}
+
+#[test]
+fn segment_disjoint_beyond_endpoints() {
+    // On the supporting line, but the disc lies before q0 or after q1.
+    assert!(free_interval(Vector::new(-3., 0.), A, B, 1.).is_none());
+    assert!(free_interval(Vector::new(7., 0.), A, B, 1.).is_none());
+}
+
+#[test]
+fn segment_tangent() {
+    // Touching in a single point is considered empty.
+    assert!(free_interval(Vector::new(2., 1.), A, B, 1.).is_none());
+}
+
+#[test]
+fn segment_crossing() {
+    let LineBoundary { a, b } = free_interval(Vector::new(2., 0.), A, B, 1.).unwrap();
+    assert!((a - 0.25).abs() < EPS);
+    assert!((b - 0.75).abs() < EPS);
+}
+
+#[test]
+fn segment_clamped_at_endpoints() {
+    let LineBoundary { a, b } = free_interval(Vector::new(0., 0.), A, B, 2.).unwrap();
+    assert_eq!(a, 0.);
+    assert!((b - 0.5).abs() < EPS);
+    let LineBoundary { a, b } = free_interval(Vector::new(4., 0.), A, B, 2.).unwrap();
+    assert!((a - 0.5).abs() < EPS);
+    assert_eq!(b, 1.);
+}
+
+#[test]
+fn segment_orientation() {
+    // Reversing the segment mirrors the interval.
+    let p = Vector::new(1., 0.5);
+    let LineBoundary { a: a1, b: b1 } = free_interval(p, A, B, 1.).unwrap();
+    let LineBoundary { a: a2, b: b2 } = free_interval(p, B, A, 1.).unwrap();
+    assert!((a1 - (1. - b2)).abs() < EPS);
+    assert!((b1 - (1. - a2)).abs() < EPS);
+}
+
+#[test]
+fn segment_nearly_tangent() {
+    // Short but well above the error bound on its endpoints, so kept.
+    let LineBoundary { a, b } = free_interval(Vector::new(2., 1. - 1e-9), A, B, 1.).unwrap();
+    assert!((b - a - 2e-9f64.sqrt() / 2.).abs() < 1e-9);
+    assert!(b - a > 2. * LineBoundary::compute_error(Vector::new(2., 1. - 1e-9), A, B, 1.));
+}
+
+#[test]
+fn segment_agrees_with_diagram() {
+    // Exactly at eps from q0 the cornerpoint is blocked (strict), so the interval does not start at it.
+    let (p, q1) = (Vector::new(3., 4.), Vector::new(8., 0.));
+    let LineBoundary { a, b } = free_interval(p, A, q1, 5.).unwrap();
+    assert!(a > 0. && a < EPS);
+    assert_eq!(b, 0.75);
+    for p in [p, Vector::new(2., 0.5), Vector::new(-1., 0.), Vector::new(9., 0.2)] {
+        let fsd = FSD::new(vec![p, p + Vector::new(0., 10.)], vec![A, q1], 5.);
+        let bounds = |opt_lb: OptLineBoundary| opt_lb.map(|LineBoundary { a, b }| (a, b));
+        assert_eq!(bounds(free_interval(p, A, q1, 5.)), bounds(fsd.segs[(0, 0, 0)]));
+    }
+}
@@ -0,0 +1,52 @@
+//! Streaming the reachable intervals column by column agrees with the full reachability diagram.
+use pcm::*;
+
+/// Interval endpoints, to compare boundaries.
+fn bounds<'a>(boundaries: impl IntoIterator<Item = &'a OptLineBoundary>) -> Vec<Option<(f64, f64)>> {
+    boundaries.into_iter().map(|b| b.map(|LineBoundary { a, b }| (a, b))).collect()
+}
+
+const MODES: [MatchMode; 5] = [MatchMode::Full, MatchMode::PrefixOfQ, MatchMode::SuffixOfQ, MatchMode::SubcurveOfQ, MatchMode::SubcurveOfBoth];
+
+#[test]
+fn collected_frontiers_equal_rsd() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0xda942042e4dd58b5u64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    for k in 0..100 {
+        let ps: Curve = (0..4).map(|_| Vector::new(4. * random(), 4. * random())).collect();
+        let qs: Curve = (0..6).map(|_| Vector::new(4. * random(), 4. * random())).collect();
+        let fsd = FSD::new(ps, qs, 1. + 2. * random());
+        let mode = MODES[k % MODES.len()];
+        let rsd = fsd.to_rsd_with(mode);
+        let mut frontiers = fsd.frontiers_with(mode);
+        let mut columns = 0;
+        for (i, frontier) in frontiers.by_ref().enumerate() {
+            assert_eq!(frontier.column, i);
+            assert_eq!(bounds(&frontier.verticals), bounds((0..fsd.m - 1).map(|j| &rsd.segs[(0, i, j)])));
+            let horizontals: Vec<_> = if i < fsd.n - 1 { (0..fsd.m).map(|j| &rsd.segs[(1, j, i)]).collect() } else { vec![] };
+            assert_eq!(bounds(&frontier.horizontals), bounds(horizontals));
+            columns += 1;
+        }
+        assert_eq!(columns, fsd.n);
+        assert_eq!(frontiers.slack(), rsd.slack);
+    }
+}
+
+#[test]
+fn stops_early() {
+    // The query leaves the target at its third point, nothing is reachable from that column on.
+    let ps = vec![Vector::new(0., 0.), Vector::new(1., 0.), Vector::new(1., 5.), Vector::new(2., 0.), Vector::new(3., 0.)];
+    let qs = vec![Vector::new(-1., 0.1), Vector::new(4., 0.1)];
+    let fsd = FSD::new(ps, qs, 0.5);
+    let reachable: Vec<Frontier> = fsd.frontiers().take_while(|frontier| frontier.verticals.iter().any(Option::is_some)).collect();
+    assert_eq!(reachable.len(), 2);
+    // Whatever was streamed agrees with the full diagram, which does not match.
+    assert!(!fsd.check_pcm());
+    let rsd = fsd.to_rsd();
+    assert!(reachable.iter().all(|frontier| bounds(&frontier.verticals) == bounds((0..fsd.m - 1).map(|j| &rsd.segs[(0, frontier.column, j)]))));
+    // The iterator is fused at the end.
+    let mut frontiers = fsd.frontiers();
+    assert_eq!(frontiers.by_ref().count(), 5);
+    assert!(frontiers.next().is_none());
+}
@@ -0,0 +1,71 @@
+//! Gap matching skips sections of either curve at a penalty per length skipped.
+use pcm::*;
+
+fn curve(points: &[(f64, f64)]) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x, y)).collect()
+}
+
+/// Straight road, and the same road with a detour of length 2√(4.25) between x = 1 and x = 2.
+fn road_and_detour() -> (Curve, Curve) {
+    (curve(&[(0., 0.), (3., 0.)]), curve(&[(0., 0.), (1., 0.), (1.5, 2.), (2., 0.), (3., 0.)]))
+}
+
+#[test]
+fn gap_along_ps() {
+    let (road, detour) = road_and_detour();
+    let matching = gap_matching(&detour, &road, 0.2, 1., 1., 6).unwrap().unwrap();
+    // A single horizontal gap skipping the detour on ps.
+    assert_eq!(matching.gaps.len(), 1, "{matching:?}");
+    let ((s0, t0), (s1, t1)) = matching.gaps[0];
+    assert!((s0 - 1.).abs() < 0.2 && (s1 - 3.).abs() < 0.2, "{matching:?}");
+    assert!((t0 - 1. / 3.).abs() < 0.1 && (t1 - 2. / 3.).abs() < 0.1, "{matching:?}");
+    // Both the detour on ps and the unit of road underneath it on qs are skipped.
+    assert!((matching.penalty - (2. * 4.25f64.sqrt() + 1.)).abs() < 1e-9, "{matching:?}");
+}
+
+#[test]
+fn gap_along_qs() {
+    let (road, detour) = road_and_detour();
+    let matching = gap_matching(&road, &detour, 0.2, 1., 1., 6).unwrap().unwrap();
+    // A single vertical gap skipping the detour on qs.
+    assert_eq!(matching.gaps.len(), 1, "{matching:?}");
+    let ((s0, t0), (s1, t1)) = matching.gaps[0];
+    assert!((s0 - 1. / 3.).abs() < 0.1 && (s1 - 2. / 3.).abs() < 0.1, "{matching:?}");
+    assert!((t0 - 1.).abs() < 0.2 && (t1 - 3.).abs() < 0.2, "{matching:?}");
+    assert!((matching.penalty - (2. * 4.25f64.sqrt() + 1.)).abs() < 1e-9, "{matching:?}");
+    // Without a detour nothing is skipped.
+    let matching = gap_matching(&road, &road, 0.2, 1., 1., 6).unwrap().unwrap();
+    assert!(matching.gaps.is_empty() && matching.penalty == 0.);
+}
+
+#[test]
+fn penalty_scales() {
+    let (road, detour) = road_and_detour();
+    let single = gap_matching(&detour, &road, 0.2, 1., 1., 6).unwrap().unwrap();
+    let double = gap_matching(&detour, &road, 0.2, 2., 2., 6).unwrap().unwrap();
+    assert!((double.penalty - 2. * single.penalty).abs() < 1e-9, "{single:?} {double:?}");
+    assert_eq!(double.steps, single.steps);
+    // Mostly ps is skipped, doubling only its penalty nearly doubles the total.
+    let double_p = gap_matching(&detour, &road, 0.2, 2., 1., 6).unwrap().unwrap();
+    assert!(double_p.penalty > 1.8 * single.penalty && double_p.penalty < double.penalty, "{single:?} {double_p:?}");
+}
+
+#[test]
+fn blocked_start() {
+    // Skipping is only allowed in between, both ends have to lie within eps.
+    let (road, _) = road_and_detour();
+    let ps = curve(&[(0., 5.), (1., 0.), (3., 0.)]);
+    assert_eq!(gap_matching(&ps, &road, 0.2, 1., 1., 6).unwrap(), None);
+    assert!(matches!(gap_matching(&ps, &road, 0.2, -1., 1., 6), Err(PcmError::InvalidArgument(_))));
+}
+
+#[test]
+fn exact_gap_length() {
+    // A spike of height 3 on ps at x = 2, sampled every 0.75 along it: only its foot lies within eps of the road.
+    let ps = curve(&[(0., 0.), (2., 0.), (2., 3.), (2., 0.), (4., 0.)]);
+    let road = curve(&[(0., 0.), (4., 0.)]);
+    let matching = gap_matching(&ps, &road, 0.5, 1.5, 1., 4).unwrap().unwrap();
+    // Skipping from the foot up the spike and back down to it, including the moves into and out of blocked space.
+    assert_eq!(matching.gaps, vec![((1., 0.5), (3., 0.5))]);
+    assert!((matching.penalty - 1.5 * 6.).abs() < 1e-9, "{matching:?}");
+}
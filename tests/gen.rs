@@ -0,0 +1,74 @@
+//! Generated curves are reproducible from their seed and have their documented ground truth.
+use pcm::*;
+
+#[test]
+fn deterministic() {
+    assert_eq!(random_curve(8, 10., &mut seeded(7)), random_curve(8, 10., &mut seeded(7)));
+    assert_ne!(random_curve(8, 10., &mut seeded(7)), random_curve(8, 10., &mut seeded(8)));
+    assert_eq!(subcurve_pair(6, 10., 0.5, &mut seeded(3)), subcurve_pair(6, 10., 0.5, &mut seeded(3)));
+    let c = random_curve(5, 2., &mut seeded(1));
+    assert!(c.iter().all(|p| (0. ..2.).contains(&p.x) && (0. ..2.).contains(&p.y)));
+}
+
+#[test]
+fn translated_and_perturbed() {
+    let mut rng = seeded(11);
+    for _ in 0..20 {
+        let c = random_curve(6, 10., &mut rng);
+        let offset = Vector::new(0.3, -0.4);
+        assert!((try_frechet_distance(&c, &translate_curve(&c, offset)).unwrap() - 0.5).abs() < 1e-3);
+        let perturbed = perturb_curve(&c, 0.5, &mut rng);
+        assert!(c.iter().zip(&perturbed).all(|(p, q)| p.distance(*q) <= 0.5 + 1e-12));
+        assert!(try_frechet_distance(&c, &perturbed).unwrap() <= 0.5 + 1e-3);
+    }
+}
+
+#[test]
+fn perturbation_scales_linearly() {
+    // The robustness sweep relies on the same seed giving the same directions at every level, scaled by it.
+    let c = random_curve(200, 10., &mut seeded(3));
+    let offsets = |deviation: f64| -> Vec<Vector> { c.iter().zip(perturb_curve(&c, deviation, &mut seeded(5))).map(|(p, q)| q - *p).collect() };
+    let (small, large) = (offsets(0.1), offsets(0.3));
+    assert!(small.iter().zip(&large).all(|(s, l)| s.scale(3.).distance(*l) < 1e-12));
+    // Isotropic: offsets point into every quadrant, and fill the disk rather than its boundary only.
+    for (sx, sy) in [(1., 1.), (-1., 1.), (-1., -1.), (1., -1.)] {
+        assert!(large.iter().any(|o| o.x * sx > 0.1 && o.y * sy > 0.1));
+    }
+    let origin = Vector::new(0., 0.);
+    assert!(large.iter().any(|o| o.distance(origin) < 0.15) && large.iter().any(|o| o.distance(origin) > 0.25));
+}
+
+#[test]
+fn arcs() {
+    let center = Vector::new(1., 2.);
+    let inner = circular_arc(center, 3., 0.5, 2., 12);
+    let outer = circular_arc(center, 3.5, 0.5, 2., 12);
+    assert_eq!(inner.len(), 12);
+    assert!(inner.iter().all(|p| (p.distance(center) - 3.).abs() < 1e-12));
+    assert!((inner[11].distance(center + Vector::new(3. * 2.5_f64.cos(), 3. * 2.5_f64.sin()))) < 1e-12);
+    assert!((try_frechet_distance(&outer, &inner).unwrap() - 0.5).abs() < 1e-3);
+    assert!((min_partial_eps(&outer, &inner, 1e-6).unwrap() - 0.5).abs() < 1e-3);
+}
+
+#[test]
+fn zigzags() {
+    let start = Vector::new(-1., 4.);
+    let zz = zigzag(start, 0.5, 0.25, 9);
+    let line = vec![start, start + Vector::new(4., 0.)];
+    assert_eq!(zz[1], Vector::new(-0.5, 3.75));
+    assert!((try_frechet_distance(&zz, &line).unwrap() - 0.25).abs() < 1e-3);
+    assert!((min_partial_eps(&zz, &line, 1e-6).unwrap() - 0.25).abs() < 1e-3);
+}
+
+#[test]
+fn subcurve_pairs() {
+    let mut rng = seeded(5);
+    for _ in 0..50 {
+        let pair = subcurve_pair(6, 10., 0.3, &mut rng);
+        let (t0, t1) = pair.interval;
+        assert!(0. <= t0 && t0 <= t1 && t1 <= 5.);
+        assert_eq!(pair.eps, 0.3);
+        assert!(pcm_decide(&pair.ps, &pair.qs, pair.eps + 1e-6).unwrap());
+        assert!(min_partial_eps(&pair.ps, &pair.qs, 1e-6).unwrap() <= pair.eps + 1e-3);
+    }
+}
@@ -0,0 +1,50 @@
+//! Reading curves from and writing matchings to GeoJSON.
+use pcm::*;
+use serde_json::Value;
+
+#[test]
+fn curve_round_trip() {
+    let c = vec![Vector::new(4.9, 52.3), Vector::new(4.91, 52.31), Vector::new(4.93, 52.3)];
+    assert_eq!(curve_from_geojson(&curve_to_geojson(&c, &ExportOptions::default())).unwrap(), c);
+}
+
+#[test]
+fn collections() {
+    let input = r#"{ "type": "FeatureCollection", "features": [
+        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [0, 0] }, "properties": {} },
+        { "type": "Feature", "geometry": { "type": "MultiLineString", "coordinates": [[[0, 0, 10], [1, 0, 11]], [[2, 2], [3, 3], [4, 2]]] }, "properties": {} },
+        { "type": "Feature", "geometry": null, "properties": {} },
+        { "type": "GeometryCollection", "geometries": [{ "type": "LineString", "coordinates": [[5, 5], [6, 6]] }] }
+    ]}"#;
+    let curves = curves_from_geojson(input).unwrap();
+    assert_eq!(curves.iter().map(|c| c.len()).collect::<Vec<_>>(), vec![2, 3, 2]);
+    assert_eq!(curves[0][1], Vector::new(1., 0.));
+    assert!(curve_from_geojson(input).is_err());
+}
+
+#[test]
+fn malformed() {
+    assert!(curves_from_geojson("{").is_err());
+    assert!(curves_from_geojson(r#"{ "coordinates": [] }"#).is_err());
+    assert!(curves_from_geojson(r#"{ "type": "LineString", "coordinates": [[0, 0]] }"#).is_err());
+    assert!(curves_from_geojson(r#"{ "type": "LineString", "coordinates": [[0, 0], [1]] }"#).is_err());
+    assert!(curves_from_geojson(r#"{ "type": "LineString", "coordinates": [[0, 0], ["1", 1]] }"#).is_err());
+}
+
+#[test]
+fn matching_features() {
+    let ps = vec![Vector::new(1., 0.2), Vector::new(2., 0.2)];
+    let qs = vec![Vector::new(0., 0.), Vector::new(4., 0.)];
+    let matching = partial_curve_matching(ps.clone(), qs, 0.5).unwrap().unwrap();
+    let output: Value = serde_json::from_str(&matching_to_geojson(&matching, 0.5, &ExportOptions::default().with_coordinate_decimals(3))).unwrap();
+    let features = output["features"].as_array().unwrap();
+    assert_eq!(features.len(), 1 + matching.steps.len());
+    assert_eq!(features[0]["properties"]["kind"], "subcurve");
+    assert_eq!(features[0]["properties"]["eps"], 0.5);
+    assert_eq!(features[0]["properties"]["coverage"].as_f64().unwrap(), matching.coverage());
+    assert_eq!(curves_from_geojson(&output.to_string()).unwrap()[0], matching.subcurve().iter().map(|p| Vector::new((p.x * 1000.).round() / 1000., p.y)).collect::<Curve>());
+    for feature in &features[1..] {
+        assert_eq!(feature["properties"]["kind"], "correspondence");
+        assert!(feature["properties"]["distance"].as_f64().unwrap() <= 0.5);
+    }
+}
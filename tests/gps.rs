@@ -0,0 +1,81 @@
+//! Reading GPX and GeoLife PLT trajectories, and projecting them to meters.
+use pcm::*;
+
+const GPX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="test">
+  <trk>
+    <name>Morning ride</name>
+    <trkseg>
+      <trkpt lat="52.0907" lon="5.1214"><ele>5</ele><time>2024-05-01T08:00:00Z</time></trkpt>
+      <trkpt lon='5.1230' lat='52.0911'/>
+      <trkpt
+        lat="52.0920" lon="5.1241"></trkpt>
+    </trkseg>
+    <trkseg>
+      <trkpt lat="52.1" lon="5.2"/>
+    </trkseg>
+    <trkseg>
+      <trkpt lat="52.1" lon="5.2"/>
+      <trkpt lat="52.2" lon="5.3"/>
+    </trkseg>
+  </trk>
+  <rte>
+    <rtept lat="1" lon="2"/>
+    <rtept lat="3" lon="4"/>
+  </rte>
+</gpx>"#;
+
+const PLT: &str = "Geolife trajectory
+WGS 84
+Altitude is in Feet
+Reserved 3
+0,2,255,My Track,0,0,2,8421376
+0
+39.984702,116.318417,0,492,39744.1201851852,2008-10-23,02:53:04
+39.984683,116.31845,0,492,39744.1202546296,2008-10-23,02:53:10
+39.984686,116.318417,0,492,39744.1203125,2008-10-23,02:53:15
+";
+
+#[test]
+fn gpx() {
+    let curves = curves_from_gpx(GPX).unwrap();
+    // The single-point segment is skipped.
+    assert_eq!(curves.len(), 3);
+    assert_eq!(curves[0], vec![Vector::new(5.1214, 52.0907), Vector::new(5.1230, 52.0911), Vector::new(5.1241, 52.0920)]);
+    assert_eq!(curves[2], vec![Vector::new(2., 1.), Vector::new(4., 3.)]);
+    assert!(curve_from_gpx(GPX).is_err());
+    assert_eq!(curve_from_gpx(r#"<trkseg><trkpt lat="0" lon="1"/><trkpt lat="2" lon="3"/></trkseg>"#).unwrap().len(), 2);
+    assert!(curves_from_gpx(r#"<trkseg><trkpt lat="0"/><trkpt lat="2" lon="3"/></trkseg>"#).is_err());
+    assert!(curves_from_gpx(r#"<trkseg><trkpt lat="x" lon="1"/><trkpt lat="2" lon="3"/></trkseg>"#).is_err());
+    assert!(curves_from_gpx("<gpx></gpx>").unwrap().is_empty());
+}
+
+#[test]
+fn plt() {
+    let c = curve_from_plt(PLT).unwrap();
+    assert_eq!(c, vec![Vector::new(116.318417, 39.984702), Vector::new(116.31845, 39.984683), Vector::new(116.318417, 39.984686)]);
+    assert!(curve_from_plt(&PLT.replace("116.31845", "east")).is_err());
+    // Header only.
+    assert!(curve_from_plt(&PLT.lines().take(6).collect::<Vec<_>>().join("\n")).is_err());
+}
+
+#[test]
+fn projection() {
+    let c = curve_from_plt(PLT).unwrap();
+    let projection = LocalProjection::centered(std::slice::from_ref(&c)).unwrap();
+    let projected = projection.project(&c);
+    for (p, q) in projection.unproject(&projected).iter().zip(&c) {
+        assert!(p.distance(*q) < 1e-9);
+    }
+    // A degree of latitude is about 111 km, a degree of longitude shrinks with the cosine of the latitude.
+    let projection = LocalProjection::new(Vector::new(5., 60.));
+    assert!((projection.apply(Vector::new(5., 61.)).y - 111_195.).abs() < 1.);
+    assert!((projection.apply(Vector::new(6., 60.)).x - 55_597.).abs() < 1.);
+    assert_eq!(projection.apply(Vector::new(5., 60.)), Vector::new(0., 0.));
+    assert!(LocalProjection::centered(&[]).is_none());
+
+    // Matching in meters: the track matches itself, shifted by 2 meters it matches below 3 meters but not below 1.
+    let shifted: Curve = projected.iter().map(|&p| p + Vector::new(2., 0.)).collect();
+    assert!(pcm_decide(&shifted, &projected, 3.).unwrap());
+    assert!(!pcm_decide(&shifted, &projected, 1.).unwrap());
+}
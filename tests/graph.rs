@@ -0,0 +1,48 @@
+//! Matching a curve against paths in a graph.
+use pcm::*;
+
+/// Grid graph of w by h nodes (node id `y * w + x` at position (x, y)).
+fn grid(w: usize, h: usize) -> Graph {
+    let vertices = (0..w * h).map(|u| (u, Vector::new((u % w) as f64, (u / w) as f64))).collect();
+    let mut edges = vec![];
+    for u in 0..w * h {
+        if u % w + 1 < w { edges.push((u, u + 1)); }
+        if u / w + 1 < h { edges.push((u, u + w)); }
+    }
+    Graph::new(vertices, edges)
+}
+
+#[test]
+fn trace_along_route() {
+    let graph = grid(4, 4);
+    // Along the bottom row, turning up along the third column.
+    let trace = vec![Vector::new(0.5, 0.1), Vector::new(2.1, -0.1), Vector::new(1.9, 1.5), Vector::new(2.1, 2.5)];
+    let matching = match_graph(&graph, &trace, 0.3).unwrap().unwrap();
+    assert_eq!(matching.path, vec![0, 1, 2, 6, 10, 14]);
+    assert_eq!(matching.edges(), vec![(0, 1), (1, 2), (2, 6), (6, 10), (10, 14)]);
+    let polyline: Curve = matching.path.iter().map(|&u| graph.position(u).unwrap()).collect();
+    validate_steps(&trace, &polyline, &matching.steps, 0.3).unwrap();
+
+    let ((u, v), offset) = matching.start();
+    let start = graph.position(u).unwrap() + offset * (graph.position(v).unwrap() - graph.position(u).unwrap());
+    assert!(start.distance(trace[0]) <= 0.3 + EPS);
+    let ((u, v), offset) = matching.end();
+    let end = graph.position(u).unwrap() + offset * (graph.position(v).unwrap() - graph.position(u).unwrap());
+    assert!(end.distance(trace[3]) <= 0.3 + EPS);
+}
+
+#[test]
+fn no_route() {
+    let graph = grid(3, 3);
+    // Cutting diagonally through the cells.
+    let trace = vec![Vector::new(0.5, 0.5), Vector::new(1.5, 1.5)];
+    assert_eq!(match_graph(&graph, &trace, 0.3).unwrap(), None);
+    assert!(match_graph(&graph, &trace, 0.6).unwrap().is_some());
+}
+
+#[test]
+fn malformed_input() {
+    let graph = grid(2, 2);
+    assert!(match_graph(&graph, &vec![Vector::new(0., 0.)], 1.).is_err());
+    assert!(match_graph(&graph, &vec![Vector::new(0., 0.), Vector::new(1., 0.)], -1.).is_err());
+}
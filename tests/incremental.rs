@@ -0,0 +1,69 @@
+//! Pushing points one by one agrees with recomputing the diagram of every prefix.
+use pcm::*;
+
+/// Deterministic curves with coordinates on a half-unit grid in [0,2]².
+fn curves(count: usize) -> Vec<(Curve, Curve)> {
+    // Deterministic xorshift so failures are reproducible.
+    let mut seed: u64 = 0xD1B54A32D192ED03;
+    let mut rnd = || { seed ^= seed << 13; seed ^= seed >> 7; seed ^= seed << 17; (seed % 1_000_000) as f64 / 1_000_000. };
+    (0..count).map(|_| {
+        let n = 2 + (rnd() * 8.) as usize;
+        let m = 2 + (rnd() * 8.) as usize;
+        let ps: Curve = (0..n).map(|_| Vector::new((rnd() * 4.).round() / 2., (rnd() * 4.).round() / 2.)).collect();
+        let qs: Curve = (0..m).map(|_| Vector::new((rnd() * 4.).round() / 2., (rnd() * 4.).round() / 2.)).collect();
+        (ps, qs)
+    }).collect()
+}
+
+#[test]
+fn agrees_with_recomputing() {
+    let mut matches = 0;
+    for (ps, qs) in curves(300) {
+        for eps in [0.3, 0.6, 1.] {
+            let mut incremental = IncrementalFSD::new(qs.clone(), eps);
+            let mut dead = false;
+            for i in 0..ps.len() {
+                let is_match = incremental.push_point(ps[i]).unwrap();
+                assert_eq!(incremental.len(), i + 1);
+                if i == 0 { continue; }
+                let prefix = ps[..=i].to_vec();
+                let rsd = FSD::new(prefix.clone(), qs.clone(), eps).to_rsd();
+                assert_eq!(is_match, rsd.check_pcm());
+                assert_eq!(incremental.pcm_steps(), rsd.pcm_steps());
+                if let Some(steps) = incremental.pcm_steps() {
+                    validate_steps(&prefix, &qs, &steps, eps).unwrap();
+                    assert_eq!(incremental.end(), Some(steps.last().unwrap().1));
+                    matches += 1;
+                }
+                assert!(!(dead && is_match), "Matches again after being dead.");
+                dead |= incremental.is_dead();
+            }
+        }
+    }
+    assert!(matches > 0);
+}
+
+#[test]
+fn detects_divergence() {
+    let qs: Curve = (0..10).map(|j| Vector::new(j as f64, 0.)).collect();
+    let mut incremental = IncrementalFSD::try_new(qs, 0.5).unwrap();
+    assert!(!incremental.is_dead());
+    for i in 0..5 {
+        assert!(incremental.push_point(Vector::new(i as f64 + 2., 0.2)).unwrap());
+    }
+    assert!(incremental.end().is_some_and(|t| (5.5..6.).contains(&t)));
+    assert!(!incremental.push_point(Vector::new(6., 3.)).unwrap());
+    assert!(incremental.is_dead());
+    assert!(!incremental.push_point(Vector::new(7., 0.)).unwrap());
+}
+
+#[test]
+fn malformed_input() {
+    let c: Curve = vec![Vector::new(0., 0.), Vector::new(1., 0.)];
+    assert!(IncrementalFSD::try_new(c.clone(), 0.).is_err());
+    assert!(IncrementalFSD::try_new(vec![c[0]], 1.).is_err());
+    let mut incremental = IncrementalFSD::try_new(c, 1.).unwrap();
+    assert!(incremental.push_point(Vector::new(f64::NAN, 0.)).is_err());
+    assert!(incremental.is_empty());
+    assert_eq!(incremental.pcm_steps(), None);
+}
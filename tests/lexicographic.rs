@@ -0,0 +1,32 @@
+//! Lexicographic matching keeps the leash short where the plain partial matching walks at the threshold.
+use pcm::*;
+
+fn curve(points: &[(f64, f64)]) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x, y)).collect()
+}
+
+/// Largest leash length along the steps.
+fn leash(ps: &Curve, qs: &Curve, steps: &[(f64, f64)]) -> f64 {
+    steps.iter().map(|&(s, t)| ps.point_at(s).unwrap().distance(qs.point_at(t).unwrap())).fold(0., f64::max)
+}
+
+#[test]
+fn differs_from_plain_steps() {
+    let ps = curve(&[(0., 0.5), (2., 0.5), (4., 0.5)]);
+    let qs = curve(&[(-3., 0.), (0., 0.), (2., 0.), (4., 0.), (7., 0.)]);
+    let plain = FSD::new(ps.clone(), qs.clone(), 2.).to_rsd().pcm_steps().unwrap();
+    let lexicographic = lexicographic_steps(&ps, &qs, 2., 8).unwrap().unwrap();
+    validate_steps(&ps, &qs, &lexicographic, 2.).unwrap();
+    // The plain matching runs ahead along qs as far as the threshold allows, the lexicographic one stays right below the query.
+    assert!(leash(&ps, &qs, &plain) > 1.5);
+    assert!(leash(&ps, &qs, &lexicographic) < 0.6);
+    assert!((lexicographic[0].1 - 1.).abs() < 0.2 && (lexicographic[lexicographic.len() - 1].1 - 3.).abs() < 0.2);
+}
+
+#[test]
+fn blocked_or_invalid() {
+    let ps = curve(&[(0., 1.), (1., 1.)]);
+    let qs = curve(&[(0., 0.), (1., 0.)]);
+    assert_eq!(lexicographic_steps(&ps, &qs, 0.5, 4).unwrap(), None);
+    assert!(matches!(lexicographic_steps(&ps, &qs, 2., 0), Err(PcmError::InvalidArgument(_))));
+}
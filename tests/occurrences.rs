@@ -0,0 +1,73 @@
+//! Every occurrence of a query along a target revisiting it, each with its own matching.
+use pcm::*;
+
+#[test]
+fn every_visit() {
+    // Target loops back onto the query twice.
+    let ps = vec![Vector::new(0., 0.), Vector::new(1., 0.)];
+    let qs: Curve = [(0., 0.), (1., 0.), (1., 2.), (0., 2.), (0., 0.), (1., 0.), (1., -2.), (0., -2.), (0., 0.), (1., 0.)]
+        .into_iter().map(|(x, y)| Vector::new(x, y)).collect();
+    let occurrences = all_matchings(ps.clone(), qs.clone(), 0.1).unwrap();
+    assert_eq!(occurrences.len(), 3);
+    for (k, Occurrence { exit, matching }) in occurrences.iter().enumerate() {
+        let (t0, t1) = matching.covered_interval();
+        let visit = 4. * k as f64;
+        assert!((t0 - visit).abs() <= 0.1 && (t1 - (visit + 1.)).abs() <= 0.1);
+        assert!(exit.0 == t1 && exit.0 < exit.1);
+        validate_steps(&ps, &qs, &matching.steps, 0.1).unwrap();
+    }
+    // A single match is one of them.
+    assert_eq!(partial_curve_matching(ps, qs, 0.1).unwrap().unwrap(), occurrences[0].matching);
+}
+
+#[test]
+fn chunked_agrees_with_all_matchings() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0xa54ff53a5f1d36f1u64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    for k in 0..300 {
+        let (n, m) = (2 + k % 4, 2 + k % 23);
+        let ps: Curve = (0..n).map(|_| Vector::new(2. * random(), 2. * random())).collect();
+        let qs: Curve = (0..m).map(|_| Vector::new(2. * random(), 2. * random())).collect();
+        let eps = 0.3 + random();
+        let expected = all_matchings(ps.clone(), qs.clone(), eps).unwrap();
+        let chunked = chunked_matchings(&ps, &qs, eps).unwrap();
+        assert_eq!(chunked.len(), expected.len());
+        for (occurrence, Occurrence { exit, matching }) in chunked.iter().zip(&expected) {
+            assert_eq!((occurrence.exit, &occurrence.steps), (*exit, &matching.steps));
+            assert_eq!(occurrence.covered_interval().unwrap().1, occurrence.exit.0);
+        }
+    }
+}
+
+#[test]
+fn chunked_long_target() {
+    // Query is a bump, the target a long trace passing over it every 1000 points (the full diagram would not fit in memory).
+    let bump = |x: f64| Vector::new(x, (1. - x.abs()).max(0.));
+    let ps: Curve = (0..=8).map(|i| bump(0.25 * i as f64 - 1.)).collect();
+    let qs: Curve = (0..100_000).map(|j| bump(0.1 * (j % 1000) as f64 - 50.)).collect();
+    let occurrences = chunked_matchings(&ps, &qs, 0.1).unwrap();
+    assert_eq!(occurrences.len(), 100);
+    for (k, occurrence) in occurrences.iter().enumerate() {
+        let (t0, t1) = occurrence.covered_interval().unwrap();
+        let center = 1000. * k as f64 + 500.;
+        assert!((t0 - (center - 10.)).abs() <= 1. && (t1 - (center + 10.)).abs() <= 1.);
+        validate_steps(&ps, &qs, &occurrence.steps, 0.1).unwrap();
+    }
+}
+
+#[test]
+fn chunked_spans_whole_target() {
+    // Query and target are the same wave (the target sampled ten times as dense), the matching walks back through every chunk.
+    let wave = |k: usize, n: usize| { let x = 20. * k as f64 / (n - 1) as f64; Vector::new(x, x.sin()) };
+    let ps: Curve = (0..200).map(|k| wave(k, 200)).collect();
+    let qs: Curve = (0..2000).map(|k| wave(k, 2000)).collect();
+    let occurrences = chunked_matchings(&ps, &qs, 0.05).unwrap();
+    assert_eq!(occurrences.len(), 1);
+    // Ends of the wave may be left out within eps (five target points).
+    let (t0, t1) = occurrences[0].covered_interval().unwrap();
+    assert!(t0 <= 6. && t1 >= 1993.);
+    validate_steps(&ps, &qs, &occurrences[0].steps, 0.05).unwrap();
+    let expected = all_matchings(ps, qs, 0.05).unwrap();
+    assert_eq!(occurrences[0].steps, expected[0].matching.steps);
+}
@@ -0,0 +1,32 @@
+//! Oriented matching returns steps in the orientation of the arguments, whichever curve is matched onto the other.
+use pcm::*;
+
+fn curve(points: &[(f64, f64)]) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x, y)).collect()
+}
+
+fn transposed(steps: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    steps.iter().map(|&(i, j)| (j, i)).collect()
+}
+
+#[test]
+fn swapped_steps_in_argument_orientation() {
+    // Long target ps, short probe qs lying along its middle.
+    let ps = curve(&[(0., 0.), (2., 0.), (4., 0.), (6., 0.)]);
+    let qs = curve(&[(2.5, 0.2), (3.5, 0.3)]);
+    assert_eq!(oriented_match_steps(ps.clone(), qs.clone(), 0.5, MatchMode::SubcurveOfQ, false).unwrap(), None);
+
+    let OrientedSteps { steps, swapped } = oriented_match_steps(ps.clone(), qs.clone(), 0.5, MatchMode::SubcurveOfQ, true).unwrap().unwrap();
+    assert!(swapped);
+    assert_eq!(steps, transposed(&match_steps(qs.clone(), ps.clone(), 0.5, MatchMode::SubcurveOfQ).unwrap().unwrap()));
+    // First coordinate on ps (partially covered), second on qs (fully covered).
+    let (first, last) = (steps[0], *steps.last().unwrap());
+    assert!(first.0 > 0. && last.0 < 3.);
+    assert_eq!((first.1, last.1), (0., 1.));
+    validate_steps(&ps, &qs, &steps, 0.5).unwrap();
+
+    // Symmetric with matching the swapped arguments unswapped.
+    let unswapped = oriented_match_steps(qs, ps, 0.5, MatchMode::SubcurveOfQ, false).unwrap().unwrap();
+    assert!(!unswapped.swapped);
+    assert_eq!(steps, transposed(&unswapped.steps));
+}
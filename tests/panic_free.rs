@@ -0,0 +1,112 @@
+//! Malformed input to the public API should result in errors, not panics.
+use pcm::*;
+
+fn line(n: usize) -> Curve {
+    (0..n).map(|i| Vector::new(i as f64, 0.)).collect()
+}
+
+#[test]
+fn empty_and_single_point_curves() {
+    for (ps, qs) in [(line(0), line(3)), (line(3), line(0)), (line(1), line(3)), (line(3), line(1))] {
+        assert!(matches!(FSD::try_new(ps.clone(), qs.clone(), 1.), Err(PcmError::EmptyCurve { .. })));
+        assert!(try_partial_curve(ps.clone(), qs.clone(), 1.).is_err());
+        assert!(max_coverage_steps(ps.clone(), qs.clone(), 1.).is_err());
+        assert!(lexicographic_steps(&ps, &qs, 1., 2).is_err());
+        assert!(free_space_mask(&ps, &qs, 1., 2).is_err());
+        let _ = simplify_douglas_peucker(&ps, 1.);
+        let _ = simplify_imai_iri(&ps, 1.);
+        let _ = simplify_to_n(&ps, 2);
+    }
+}
+
+#[test]
+fn absurd_epsilon() {
+    for eps in [0., -1., f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        assert!(matches!(FSD::try_new(line(3), line(4), eps), Err(PcmError::InvalidThreshold(_))));
+        assert!(try_partial_curve(line(3), line(4), eps).is_err());
+        assert!(lexicographic_steps(&line(3), &line(4), eps, 2).is_err());
+    }
+    // Huge but finite thresholds are fine.
+    assert!(try_partial_curve(line(3), line(4), 1e300).unwrap().is_some());
+}
+
+#[test]
+fn non_finite_coordinates() {
+    for v in [f64::NAN, f64::INFINITY] {
+        let mut ps = line(3);
+        ps[1].y = v;
+        assert!(matches!(FSD::try_new(ps.clone(), line(4), 1.), Err(PcmError::InvalidPoint { index: 1, .. })));
+        assert!(FSD::try_new(line(4), ps.clone(), 1.).is_err());
+    }
+}
+
+#[test]
+fn duplicate_points() {
+    let ps = vec![Vector::new(0., 0.), Vector::new(0., 0.), Vector::new(1., 0.)];
+    let qs = vec![Vector::new(-1., 0.), Vector::new(2., 0.), Vector::new(2., 0.)];
+    assert!(try_partial_curve(ps.clone(), qs.clone(), 0.1).unwrap().is_some());
+    assert!(try_partial_curve(qs, ps, 0.1).unwrap().is_none());
+}
+
+#[test]
+fn malformed_steps_and_timed_curves() {
+    assert!(residuals(&line(3), &line(3), &[]).is_empty());
+    assert!(TimedCurve::new(vec![], vec![]).is_err());
+    assert!(TimedCurve::new(line(2), vec![0.]).is_err());
+    assert!(TimedCurve::new(line(2), vec![1., 0.]).is_err());
+    assert!(TimedCurve::new(line(2), vec![0., f64::NAN]).is_err());
+}
+
+#[test]
+fn empty_and_single_point_helpers() {
+    let p = Vector::new(1., 2.);
+    assert_eq!(curve::interpolate(&line(0), 0.5), None);
+    assert_eq!(line(0).point_at(0.5), None);
+    assert_eq!(curve::arc_length(&line(0), 0.5), 0.);
+    assert!(curve::subcurve(&line(0), 0., 1.).is_empty());
+    assert_eq!(curve::interpolate(&vec![p], 3.), Some(p));
+    assert_eq!(vec![p].point_at(-1.), Some(p));
+    assert_eq!(curve::arc_length(&vec![p], 0.5), 0.);
+    assert_eq!(curve::subcurve(&vec![p], 0., 1.), vec![p, p]);
+
+    assert!(matches!(Matching::new(line(2), line(3), vec![]), Err(PcmError::EmptySteps)));
+    assert!(matches!(Matching::new(line(0), line(3), vec![(0., 0.)]), Err(PcmError::EmptyCurve { .. })));
+    assert!(matches!(Matching::new(line(2), line(0), vec![(0., 0.)]), Err(PcmError::EmptyCurve { .. })));
+    let matching = Matching::new(vec![p], vec![p], vec![(0., 0.)]).unwrap();
+    assert_eq!(matching.position(5.), (0., 0.));
+    assert_eq!(matching.covered_interval(), (0., 0.));
+    assert_eq!((matching.max_distance(), matching.mean_distance(), matching.coverage()), (0., 0., 1.));
+    assert_eq!(matching.subcurve(), vec![p, p]);
+
+    assert_eq!(ChunkedOccurrence { exit: (0., 0.), steps: vec![] }.covered_interval(), None);
+    assert_eq!(ChunkedOccurrence { exit: (0., 0.), steps: vec![(0., 1.)] }.covered_interval(), Some((1., 1.)));
+}
+
+#[test]
+fn fuzz_random_input() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut seed: u64 = 0x2545F4914F6CDD1D;
+    let mut rnd = || { seed ^= seed << 13; seed ^= seed >> 7; seed ^= seed << 17; (seed % 1_000_000) as f64 / 1_000_000. };
+    let specials = [0., 1., f64::NAN, f64::INFINITY, -1e300];
+    for _ in 0..2000 {
+        let n = (rnd() * 7.) as usize;
+        let m = (rnd() * 7.) as usize;
+        let mut coordinate = || if rnd() < 0.02 { specials[(rnd() * 5.) as usize] } else { (rnd() * 4.).round() / 2. };
+        let ps: Curve = (0..n).map(|_| Vector::new(coordinate(), coordinate())).collect();
+        let qs: Curve = (0..m).map(|_| Vector::new(coordinate(), coordinate())).collect();
+        let eps = if rnd() < 0.1 { specials[(rnd() * 5.) as usize] } else { rnd() * 2. };
+
+        let _ = try_partial_curve(ps.clone(), qs.clone(), eps);
+        let _ = max_coverage_steps(ps.clone(), qs.clone(), eps);
+        let _ = lexicographic_steps(&ps, &qs, eps, 2);
+        if let Ok(fsd) = FSD::try_new(ps.clone(), qs.clone(), eps) {
+            let rsd = fsd.to_rsd();
+            let _ = rsd.check_pcm();
+            let _ = rsd.robustness();
+            let _ = rsd.to_ascii(None);
+            if let Some(steps) = rsd.pcm_steps() {
+                let _ = residuals(&ps, &qs, &steps);
+            }
+        }
+    }
+}
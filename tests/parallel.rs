@@ -0,0 +1,45 @@
+//! Parallel diagram construction and propagation agree exactly with the sequential path.
+use pcm::*;
+
+fn assert_identical(actual: &FSD, expected: &FSD) {
+    let bits = |fsd: &FSD| fsd.segs.iter().map(|b| b.map(|b| (b.a.to_bits(), b.b.to_bits()))).collect::<Vec<_>>();
+    assert_eq!(bits(actual), bits(expected));
+    assert_eq!(actual.errs, expected.errs);
+    assert_eq!(actual.corners, expected.corners);
+    assert_eq!(actual.slack.to_bits(), expected.slack.to_bits());
+}
+
+#[test]
+fn agrees_with_sequential() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x3c6ef372fe94f82bu64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    for k in 0..100 {
+        // Wide, tall and square diagrams.
+        let (n, m) = (2 + k % 9, 2 + (k / 9) % 11);
+        let ps: Curve = (0..n).map(|_| Vector::new(3. * random(), 3. * random())).collect();
+        let qs: Curve = (0..m).map(|_| Vector::new(3. * random(), 3. * random())).collect();
+        let eps = 0.3 + 2. * random();
+        let fsd = FSD::new(ps.clone(), qs.clone(), eps);
+        let parallel = FSD::new_parallel(ps.clone(), qs.clone(), eps);
+        assert_identical(&parallel, &fsd);
+        assert_identical(&FSD::compute_parallel(&ps, &qs, eps, Predicates::Exact), &FSD::new_with(ps, qs, eps, Predicates::Exact));
+        for mode in [MatchMode::Full, MatchMode::PrefixOfQ, MatchMode::SuffixOfQ, MatchMode::SubcurveOfQ, MatchMode::SubcurveOfBoth] {
+            let (rsd, rsd_parallel) = (fsd.to_rsd_with(mode), parallel.to_rsd_parallel_with(mode));
+            assert_identical(&rsd_parallel, &rsd);
+            assert_eq!(rsd_parallel.pcm_steps(), rsd.pcm_steps());
+        }
+    }
+}
+
+#[test]
+fn long_curves() {
+    // Wave ps lies along the longer wave qs (shifted up by 0.2).
+    let wave = |x: f64, dy: f64| Vector::new(x, (0.1 * x).sin() + dy);
+    let ps: Curve = (0..200).map(|i| wave(i as f64, 0.)).collect();
+    let qs: Curve = (0..800).map(|i| wave(0.5 * i as f64 - 100., 0.2)).collect();
+    let fsd = FSD::new_parallel(ps.clone(), qs.clone(), 0.5);
+    let rsd = fsd.to_rsd_parallel();
+    assert_identical(&rsd, &FSD::new(ps, qs, 0.5).to_rsd());
+    assert!(rsd.check_pcm());
+}
@@ -0,0 +1,47 @@
+//! Precision checks compare the decision in single and double precision.
+use pcm::*;
+
+fn curve(points: &[(f64, f64)]) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x, y)).collect()
+}
+
+#[test]
+fn clear_cases_agree() {
+    let ps = curve(&[(0., 0.), (1., 0.), (2., 0.)]);
+    let qs = curve(&[(-1., 0.1), (1., 0.2), (3., 0.1)]);
+    let report = precision_check(&ps, &qs, 0.5).unwrap();
+    assert!(report.decision_f64 && report.decision_f32 && report.decision_exact);
+    assert!(report.is_consistent(1e-3) && !report.is_fragile(), "{report:?}");
+    let report = precision_check(&ps, &qs, 0.05).unwrap();
+    assert!(!report.decision_f64 && !report.decision_f32 && !report.decision_exact && report.end_f64.is_none());
+    assert!(report.is_consistent(1e-3), "{report:?}");
+}
+
+#[test]
+fn rounding_flips_decision() {
+    // The query ends just beyond eps of the target, rounded to f32 it ends within.
+    let ps = curve(&[(0., 0.), (1., 1.00000003)]);
+    let qs = curve(&[(0., 0.), (1., 0.)]);
+    let report = precision_check(&ps, &qs, 1.00000002).unwrap();
+    assert!(!report.decision_f64 && !report.decision_exact && report.decision_f32, "{report:?}");
+    assert!(!report.is_consistent(1e-3));
+}
+
+#[test]
+fn rejects_malformed_input() {
+    let ps = curve(&[(0., 0.), (1., 0.)]);
+    assert!(precision_check(&ps, &ps, 0.).is_err());
+    assert!(precision_check(&ps[..1].to_vec(), &ps, 1.).is_err());
+}
+
+#[test]
+fn exact_decision_is_reference() {
+    // qs passes within a hair of the threshold circle around the end of ps, too narrow an interval for floating point predicates.
+    let ps = curve(&[(0., 0.), (2., 0.)]);
+    let qs = curve(&[(-1., 0.5), (1., 1. - 1e-15), (2., 0.)]);
+    let report = precision_check(&ps, &qs, 1.).unwrap();
+    assert!(report.decision_exact && !report.decision_f64 && !report.decision_f32, "{report:?}");
+    // Both precisions agree with each other (and the error bound sees no risk), yet not with the exact decision.
+    assert!(!report.is_fragile());
+    assert!(!report.is_consistent(1e-3));
+}
@@ -0,0 +1,34 @@
+//! Rejecting by the segment index before building the diagram never changes the outcome.
+use pcm::*;
+
+#[test]
+fn agrees_without_index() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x4f1bbcdcbfa53e0bu64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    let mut rejected = 0;
+    for _ in 0..300 {
+        let ps: Curve = (0..4).map(|_| Vector::new(10. * random(), 10. * random())).collect();
+        let qs: Curve = (0..8).map(|_| Vector::new(10. * random(), 10. * random())).collect();
+        let eps = 0.5 + 4. * random();
+        let index = SegmentBvh::new(&qs);
+        for p in &ps {
+            assert_eq!(index.any_within(*p, eps), index.nearest_distance(*p) <= eps);
+        }
+        let plain = try_partial_curve(ps.clone(), qs.clone(), eps).unwrap();
+        if !index.may_match(&ps, eps) {
+            assert!(plain.is_none());
+            rejected += 1;
+        }
+        assert_eq!(try_partial_curve_indexed(&ps, &qs, &index, eps).unwrap(), plain);
+    }
+    assert!(rejected > 30);
+}
+
+#[test]
+fn invalid_input() {
+    let qs = vec![Vector::new(0., 0.), Vector::new(1., 0.)];
+    let index = SegmentBvh::new(&qs);
+    assert!(try_partial_curve_indexed(&vec![Vector::new(5., 5.)], &qs, &index, 1.).is_err());
+    assert!(try_partial_curve_indexed(&qs, &qs, &index, -1.).is_err());
+}
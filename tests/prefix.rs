@@ -0,0 +1,51 @@
+//! Longest prefix of the query matching a subcurve of the target, also when it ends inside a cell of the diagram.
+use pcm::{curve::subcurve, *};
+
+fn curve(points: &[(f64, f64)]) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x, y)).collect()
+}
+
+#[test]
+fn ends_inside_cell() {
+    // Query turns away from the target, it stays within eps up to a tenth along its second segment.
+    let (ps, qs) = (curve(&[(0., 0.), (5., 0.), (5., 10.)]), curve(&[(-1., 0.), (10., 0.)]));
+    let (t, steps) = longest_prefix_steps(ps.clone(), qs.clone(), 1.).unwrap().unwrap();
+    assert!((t - 1.1).abs() < 1e-9);
+    assert_eq!(steps.last().unwrap().0, t);
+    validate_steps(&ps, &qs, &steps, 1.).unwrap();
+
+    // Query crosses the target, it stays within eps up to a tenth along its only segment.
+    let (ps, qs) = (curve(&[(0., 0.), (10., 0.)]), curve(&[(0., -1.), (0., 1.)]));
+    let (t, steps) = longest_prefix_steps(ps.clone(), qs.clone(), 1.).unwrap().unwrap();
+    assert!((t - 0.1).abs() < 1e-9);
+    assert_eq!(steps[0], (0., 0.));
+    validate_steps(&ps, &qs, &steps, 1.).unwrap();
+}
+
+#[test]
+fn full_and_no_prefix() {
+    let (ps, qs) = (curve(&[(0., 0.), (1., 0.)]), curve(&[(-1., 0.1), (2., 0.1)]));
+    let (t, steps) = longest_prefix_steps(ps.clone(), qs.clone(), 0.5).unwrap().unwrap();
+    assert_eq!(t, 1.);
+    validate_steps(&ps, &qs, &steps, 0.5).unwrap();
+    assert!(longest_prefix_steps(ps, qs, 0.05).unwrap().is_none());
+}
+
+#[test]
+fn random_steps_are_valid() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x3c6ef372fe94f82bu64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    for k in 0..300 {
+        let ps: Curve = (0..2 + k % 5).map(|_| Vector::new(4. * random(), 4. * random())).collect();
+        let qs: Curve = (0..2 + k % 7).map(|_| Vector::new(4. * random(), 4. * random())).collect();
+        let eps = 0.5 + random();
+        let Some((t, steps)) = longest_prefix_steps(ps.clone(), qs.clone(), eps).unwrap() else { continue };
+        assert_eq!(steps.last().unwrap().0, t);
+        validate_steps(&ps, &qs, &steps, eps).unwrap();
+        // Slightly shorter prefixes match, slightly longer ones do not.
+        let matches = |t: f64| partial_curve_matching(subcurve(&ps, 0., t), qs.clone(), eps).unwrap().is_some();
+        assert!(t < 0.01 || matches(t - 0.01));
+        assert!(t + 0.01 > (ps.len() - 1) as f64 || !matches(t + 0.01));
+    }
+}
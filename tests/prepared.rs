@@ -0,0 +1,39 @@
+//! Matching against a prepared target agrees with matching against the plain curve.
+use pcm::*;
+
+#[test]
+fn agrees_with_plain() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x3c6ef372fe94f82bu64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    // Long random walk as the reference curve.
+    let mut position = Vector::new(0., 0.);
+    let qs: Curve = (0..200).map(|_| { position = position + Vector::new(random() - 0.5, random() - 0.5); position }).collect();
+    let target = PreparedCurve::try_new(qs.clone()).unwrap();
+    let mut matches = 0;
+    for _ in 0..300 {
+        // Noisy copy of a piece of the reference curve, sometimes reversed (rarely matching).
+        let start = (random() * 190.) as usize;
+        let mut ps: Curve = qs[start..start + 2 + (random() * 8.) as usize].iter().map(|&q| q + Vector::new(0.2 * random(), 0.2 * random())).collect();
+        if random() < 0.3 { ps.reverse(); }
+        let eps = 0.05 + 0.3 * random();
+        let plain = try_partial_curve(ps.clone(), qs.clone(), eps).unwrap();
+        let prepared = target.partial_curve(&ps, eps).unwrap();
+        assert_eq!(plain.is_some(), prepared.is_some());
+        if let (Some((a1, b1)), Some((a2, b2))) = (plain, prepared) {
+            assert!((a1 - a2).abs() < 1e-9 && (b1 - b2).abs() < 1e-9);
+            let matching = target.partial_curve_matching(&ps, eps).unwrap().unwrap();
+            validate_steps(&ps, &qs, &matching.steps, eps).unwrap();
+            matches += 1;
+        }
+    }
+    assert!(matches > 50);
+}
+
+#[test]
+fn invalid_input() {
+    assert!(PreparedCurve::try_new(vec![Vector::new(0., 0.)]).is_err());
+    let target = PreparedCurve::new(vec![Vector::new(0., 0.), Vector::new(1., 0.)]);
+    assert!(target.partial_curve(&vec![Vector::new(0., 0.)], 1.).is_err());
+    assert!(target.partial_curve(&vec![Vector::new(0., 0.), Vector::new(1., 0.)], f64::NAN).is_err());
+}
@@ -0,0 +1,41 @@
+//! Invariants on arbitrary cases, proptest shrinks a failing case to a minimal counterexample (store it with `pcm_testdata::write_case`).
+use pcm::*;
+use pcm_testdata::State;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn corners_consistent(state in any::<State>()) {
+        let State { ps, qs, eps } = state;
+        let fsd = FSD::new(ps, qs, eps);
+        prop_assert_eq!(fsd.validate().map_err(|e| e.to_string()), Ok(()));
+    }
+
+    #[test]
+    fn reachability_consistent(state in any::<State>()) {
+        let State { ps, qs, eps } = state;
+        let fsd = FSD::new(ps, qs, eps);
+        for mode in [MatchMode::Full, MatchMode::PrefixOfQ, MatchMode::SuffixOfQ, MatchMode::SubcurveOfQ, MatchMode::SubcurveOfBoth] {
+            prop_assert_eq!(fsd.to_rsd_with(mode).validate_reachability(&fsd).map_err(|e| e.to_string()), Ok(()));
+        }
+    }
+
+    #[test]
+    fn steps_valid(state in any::<State>()) {
+        let State { ps, qs, eps } = state;
+        let rsd = FSD::new(ps.clone(), qs.clone(), eps).to_rsd();
+        let opt_steps = rsd.pcm_steps();
+        prop_assert_eq!(rsd.check_pcm(), opt_steps.is_some());
+        if let Some(steps) = opt_steps {
+            prop_assert_eq!(validate_steps(&ps, &qs, &steps, eps).map_err(|e| e.to_string()), Ok(()));
+        }
+    }
+
+    #[test]
+    fn sparse_agrees(state in any::<State>()) {
+        let State { ps, qs, eps } = state;
+        let dense = FSD::new(ps.clone(), qs.clone(), eps).to_rsd();
+        let sparse = SparseFSD::new(ps, qs, eps).to_rsd();
+        prop_assert_eq!(dense.check_pcm(), sparse.check_pcm());
+    }
+}
@@ -0,0 +1,55 @@
+//! Quality of a matching beyond the decision: realized, mean and integral distance.
+use pcm::*;
+
+#[test]
+fn parallel_lines() {
+    // Target runs parallel at distance 1, extending beyond ps on both sides.
+    let ps = vec![Vector::new(0., 0.), Vector::new(2., 0.), Vector::new(4., 0.)];
+    let qs = vec![Vector::new(-2., 1.), Vector::new(1., 1.), Vector::new(6., 1.)];
+    let matching = partial_curve_matching(ps.clone(), qs, 1.5).unwrap().unwrap();
+    assert!(matching.max_distance() >= 1. && matching.max_distance() < 1.5 + EPS);
+    assert!(matching.mean_distance() >= 1. - EPS && matching.mean_distance() <= matching.max_distance() + EPS);
+    assert!(matching.coverage() > 0. && matching.coverage() < 1.);
+
+    // Straight across, the distance is exactly 1 everywhere.
+    let straight = Matching::new(ps.clone(), vec![Vector::new(-2., 1.), Vector::new(1., 1.), Vector::new(6., 1.)], vec![(0., 2. / 3.), (0.5, 1.), (2., 1.6)]).unwrap();
+    assert!((straight.max_distance() - 1.).abs() < EPS);
+    assert!((straight.mean_distance() - 1.).abs() < EPS);
+    assert!((straight.integral_distance() - 8.).abs() < EPS);
+}
+
+#[test]
+fn evaluation() {
+    let ps = vec![Vector::new(0., 0.), Vector::new(2., 0.), Vector::new(4., 0.)];
+    let qs = vec![Vector::new(-2., 1.), Vector::new(1., 1.), Vector::new(6., 1.)];
+    let matching = Matching::new(ps, qs, vec![(0., 2. / 3.), (0.5, 1.), (2., 1.6)]).unwrap();
+    assert_eq!(matching.to_steps(), matching.steps);
+    assert_eq!(matching.covered_interval(), (2. / 3., 1.6));
+    assert_eq!(matching.position(0.), (0., 2. / 3.));
+    let (i, j) = matching.position(1.5);
+    assert!((i - 1.25).abs() < EPS && (j - 1.3).abs() < EPS);
+    // Clamped to the steps.
+    assert_eq!(matching.position(-1.), (0., 2. / 3.));
+    assert_eq!(matching.position(7.), (2., 1.6));
+    let (p, q) = matching.evaluate(1.5);
+    assert!(p.distance(Vector::new(2.5, 0.)) < EPS && q.distance(Vector::new(2.5, 1.)) < EPS);
+    // Every coupled pair along the walk is within the realized distance.
+    assert!((0..=20).all(|k| { let (p, q) = matching.evaluate(k as f64 / 10.); p.distance(q) <= matching.max_distance() + EPS }));
+}
+
+#[test]
+fn bounded_by_threshold() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x3c6ef372fe94f82bu64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    for _ in 0..100 {
+        let ps: Curve = (0..4).map(|_| Vector::new(10. * random(), 10. * random())).collect();
+        let qs: Curve = (0..8).map(|_| Vector::new(10. * random(), 10. * random())).collect();
+        let eps = 3. + 4. * random();
+        if let Some(matching) = partial_curve_matching(ps.clone(), qs, eps).unwrap() {
+            let max = matching.max_distance();
+            assert!(max < eps + EPS);
+            assert!(matching.mean_distance() <= max + EPS);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! Ranked retrieval agrees with computing the minimal threshold of every candidate.
+use pcm::*;
+
+#[test]
+fn agrees_with_exhaustive() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x9b05688c2b3e6c1fu64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    let query: Curve = (0..4).map(|i| Vector::new(i as f64, random())).collect();
+    let dataset: Vec<Curve> = (0..40).map(|_| {
+        let (dx, dy) = (4. * random() - 2., 4. * random() - 2.);
+        (0..6).map(|i| Vector::new(i as f64 - 1. + dx, 2. * random() + dy)).collect()
+    }).collect();
+    let tolerance = 1e-4;
+    let mut exhaustive: Vec<(f64, usize)> = dataset.iter().enumerate().map(|(k, qs)| (min_partial_eps(&query, qs, tolerance).unwrap(), k)).collect();
+    exhaustive.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    for k in [0, 1, 5, 40, 50] {
+        let nearest = nearest_curves(&query, &dataset, k, tolerance).unwrap();
+        assert_eq!(nearest.len(), k.min(dataset.len()));
+        for (neighbour, &(eps, _)) in nearest.iter().zip(&exhaustive) {
+            // Bisection ends within tolerance of the true threshold, the order may differ within it.
+            assert!((neighbour.eps - eps).abs() <= 2. * tolerance);
+            assert!((neighbour.eps - min_partial_eps(&query, &dataset[neighbour.index], tolerance).unwrap()).abs() <= 2. * tolerance);
+        }
+    }
+}
+
+#[test]
+fn malformed_candidate() {
+    let query = vec![Vector::new(0., 0.), Vector::new(1., 0.)];
+    let error = nearest_curves(&query, &[query.clone(), vec![]], 1, 1e-3).unwrap_err();
+    assert!(matches!(error, PcmError::Candidate { index: 1, .. }));
+}
@@ -0,0 +1,79 @@
+//! Diagrams exported as numeric arrays and dense grids agree with their boundaries and the rasterized free space.
+use pcm::*;
+
+fn curves() -> (Curve, Curve) {
+    let ps = vec![Vector::new(0., 0.), Vector::new(2., 0.3), Vector::new(4., -0.2)];
+    let qs = vec![Vector::new(-1., 0.5), Vector::new(1., 0.4), Vector::new(3., 0.6), Vector::new(5., 0.)];
+    (ps, qs)
+}
+
+#[test]
+fn arrays() {
+    let (ps, qs) = curves();
+    let fsd = FSD::new(ps, qs, 0.8);
+    let arrays = fsd.to_arrays();
+    assert_eq!((arrays.verticals.dim(), arrays.horizontals.dim(), arrays.corners.dim()), ((3, 3, 2), (4, 2, 2), (3, 4)));
+    for (axis, intervals) in [(0, &arrays.verticals), (1, &arrays.horizontals)] {
+        for ((x, y, k), &v) in intervals.indexed_iter() {
+            match fsd.segs[(axis, x, y)] {
+                Some(LineBoundary { a, b }) => assert_eq!(v, [a, b][k]),
+                None => assert!(v.is_nan()),
+            }
+        }
+    }
+    assert_eq!(arrays.corners, fsd.corners);
+}
+
+#[test]
+fn mask_corners() {
+    // The first corner lies exactly at eps, which is blocked.
+    let ps = vec![Vector::new(0., 0.), Vector::new(2., 0.), Vector::new(4., 1.)];
+    let qs = vec![Vector::new(3., 4.), Vector::new(0., 1.)];
+    let (eps, resolution) = (5., 3);
+    let fsd = FSD::new(ps.clone(), qs.clone(), eps);
+    let mask = free_space_mask(&ps, &qs, eps, resolution).unwrap();
+    assert!(!fsd.corners[(0, 0)]);
+    for ((i, j), &free) in fsd.corners.indexed_iter() {
+        assert_eq!(mask[(i * resolution, j * resolution)], free, "corner ({i}, {j})");
+    }
+}
+
+#[test]
+fn dense_grid() {
+    let (ps, qs) = curves();
+    let (eps, resolution) = (0.8, 4);
+    let fsd = FSD::new(ps.clone(), qs.clone(), eps);
+    let grid = fsd.to_dense_grid(resolution).unwrap();
+    let mask = free_space_mask(&ps, &qs, eps, resolution).unwrap();
+    assert_eq!(grid.dim(), mask.dim());
+    for ((x, y), &v) in grid.indexed_iter() {
+        if x % resolution == 0 || y % resolution == 0 {
+            assert_eq!(v == 1., mask[(x, y)], "sample ({x}, {y})");
+        } else {
+            assert!(v.is_nan());
+        }
+    }
+    // Reachable samples are free.
+    let reachable = fsd.to_rsd().to_dense_grid(resolution).unwrap();
+    assert!(reachable.iter().zip(&grid).all(|(&r, &f)| r != 1. || f == 1.));
+    assert!(reachable.iter().any(|&r| r == 1.));
+    assert!(matches!(fsd.to_dense_grid(0), Err(PcmError::InvalidArgument(_))));
+}
+
+#[test]
+fn npy_f64() {
+    let (ps, qs) = curves();
+    let verticals = FSD::new(ps, qs, 0.8).to_arrays().verticals;
+    let path = std::env::temp_dir().join(format!("pcm_raster_verticals_{}.npy", std::process::id()));
+    write_npy_f64(&path, &verticals).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(&bytes[..8], b"\x93NUMPY\x01\x00");
+    let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+    let header = std::str::from_utf8(&bytes[10..10 + header_len]).unwrap();
+    assert!(header.contains("'descr': '<f8'") && header.contains("'shape': (3, 3, 2)"));
+    assert_eq!((10 + header_len) % 64, 0);
+    let data: Vec<f64> = bytes[10 + header_len..].chunks(8).map(|c| f64::from_le_bytes(c.try_into().unwrap())).collect();
+    assert_eq!(data.len(), verticals.len());
+    assert!(data.iter().zip(&verticals).all(|(a, b)| a.to_bits() == b.to_bits()));
+}
@@ -0,0 +1,96 @@
+//! Replay test cases stored by the visualizer (in `testdata/` at the crate root).
+use std::{fs, path::{Path, PathBuf}};
+
+use pcm::*;
+use pcm_testdata::{case_filename, migrate, read_case, read_cases, write_case, write_case_as, CaseFormat, State, TESTDATA_DIR};
+
+#[test]
+fn stored_cases() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(TESTDATA_DIR);
+    for (path, State { ps, qs, eps }) in read_cases(dir).unwrap() {
+        let fsd = FSD::try_new(ps.clone(), qs.clone(), eps).unwrap();
+        fsd.validate().unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+        let rsd = fsd.to_rsd();
+        rsd.validate_reachability(&fsd).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+        let opt_steps = rsd.pcm_steps();
+        assert_eq!(opt_steps.is_some(), rsd.check_pcm(), "{}", path.display());
+        if let Some(steps) = opt_steps {
+            validate_steps(&ps, &qs, &steps, eps).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+        }
+    }
+}
+
+fn states() -> Vec<State> {
+    (0..3).map(|k| State {
+        ps: vec![Vector::new(0., 0.), Vector::new(1., k as f64)],
+        qs: vec![Vector::new(0., 0.5), Vector::new(2., 0.5), Vector::new(3., 1.)],
+        eps: 0.5 + k as f64,
+    }).collect()
+}
+
+/// Fresh folder in the temporary directory.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("pcm_testdata_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn cases_round_trip() {
+    let dir = scratch_dir("round_trip");
+    let states = states();
+    let paths: Vec<_> = states.iter().map(|state| write_case(&dir, state).unwrap()).collect();
+    assert_eq!(read_case(&paths[1]).unwrap(), states[1]);
+    // Writing a duplicate ends up in the same file.
+    assert_eq!(write_case(&dir, &states[1]).unwrap(), paths[1]);
+    let mut read: Vec<State> = read_cases(&dir).unwrap().into_iter().map(|(_, state)| state).collect();
+    fs::remove_dir_all(&dir).unwrap();
+    let mut expected = states.clone();
+    read.sort_by(|a, b| a.eps.total_cmp(&b.eps));
+    expected.sort_by(|a, b| a.eps.total_cmp(&b.eps));
+    assert_eq!(read, expected);
+}
+
+#[test]
+fn json_cases() {
+    let dir = scratch_dir("json");
+    let states = states();
+    let path = write_case_as(&dir, &states[2], CaseFormat::Json).unwrap();
+    assert_eq!(path.extension().unwrap(), "json");
+    let text = fs::read_to_string(&path).unwrap();
+    assert!(text.contains("\"ps\"") && text.contains("\"x\": 1.0") && text.contains("\"eps\": 2.5"));
+    write_case(&dir, &states[0]).unwrap();
+    // Both formats are listed side by side.
+    let read: Vec<State> = read_cases(&dir).unwrap().into_iter().map(|(_, state)| state).collect();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(read.len(), 2);
+    assert!(read.contains(&states[0]) && read.contains(&states[2]));
+}
+
+#[test]
+fn case_ids_depend_on_content() {
+    let states = states();
+    assert_eq!(states[0].id(), states[0].clone().id());
+    assert_ne!(states[0].id(), states[1].id());
+    let swapped = State { ps: states[0].qs.clone(), qs: states[0].ps.clone(), eps: states[0].eps };
+    assert_ne!(states[0].id(), swapped.id());
+}
+
+#[test]
+fn migrate_legacy_names() {
+    let dir = scratch_dir("migrate");
+    fs::create_dir_all(&dir).unwrap();
+    let states = states();
+    // Legacy naming by folder size, with a duplicate failure.
+    for (n, state) in states.iter().chain([&states[0]]).enumerate() {
+        fs::rename(write_case(&dir, state).unwrap(), dir.join(format!("case_{n}.bin"))).unwrap();
+    }
+    assert_eq!(migrate(&dir).unwrap(), 4);
+    assert_eq!(migrate(&dir).unwrap(), 0);
+    let mut names: Vec<String> = read_cases(&dir).unwrap().into_iter().map(|(path, _)| path.file_name().unwrap().to_string_lossy().to_string()).collect();
+    fs::remove_dir_all(&dir).unwrap();
+    let mut expected: Vec<String> = states.iter().map(case_filename).collect();
+    names.sort();
+    expected.sort();
+    assert_eq!(names, expected);
+}
@@ -0,0 +1,39 @@
+//! Residuals give the distance of every vertex of ps to the point of qs the steps match it against.
+use pcm::*;
+
+fn curve(points: &[(f64, f64)]) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x, y)).collect()
+}
+
+#[test]
+fn parallel_offset() {
+    let d = 0.3;
+    // Unevenly spaced vertices at distance d above a single long segment.
+    let ps = curve(&[(0., d), (1., d), (3., d)]);
+    let qs = curve(&[(-1., 0.), (4., 0.)]);
+    let steps = vec![(0., 0.2), (1., 0.4), (2., 0.8)];
+    validate_steps(&ps, &qs, &steps, d + 0.01).unwrap();
+    let residuals = residuals(&ps, &qs, &steps);
+    assert_eq!(residuals.len(), 3);
+    assert!(residuals.iter().all(|r| (r - d).abs() < 1e-12));
+
+    // Matched at a larger threshold every vertex stays within it.
+    let steps = partial_curve_matching(ps.clone(), qs.clone(), 0.5).unwrap().unwrap().steps;
+    assert!(pcm::residuals(&ps, &qs, &steps).iter().all(|&r| d - 1e-12 <= r && r <= 0.5 + EPS));
+}
+
+#[test]
+fn bump_vertex() {
+    // Only the vertex at index 2 leaves the line.
+    let ps = curve(&[(0., 0.), (1., 0.), (2., 1.), (3., 0.), (4., 0.)]);
+    let qs = curve(&[(0., 0.), (4., 0.)]);
+    let per_vertex = vec![(0., 0.), (1., 0.25), (2., 0.5), (3., 0.75), (4., 1.)];
+    assert_eq!(residuals(&ps, &qs, &per_vertex), vec![0., 0., 1., 0., 0.]);
+    // Vertices between steps are matched by interpolating the steps.
+    assert_eq!(residuals(&ps, &qs, &[(0., 0.), (4., 1.)]), vec![0., 0., 1., 0., 0.]);
+    // Standing still on qs first, vertices are matched against points lagging behind (by 1, 2/3 and 1/3 along the line).
+    let lagging = residuals(&ps, &qs, &[(0., 0.), (1., 0.), (4., 1.)]);
+    let expected = [0., 1., 13f64.sqrt() / 3., 1. / 3., 0.];
+    assert_eq!(lagging.len(), 5);
+    assert!(lagging.iter().zip(expected).all(|(r, e)| (r - e).abs() < 1e-12));
+}
@@ -0,0 +1,27 @@
+//! Robustness of the decision: the margin of every reachability transition over its floating point error bound.
+use pcm::*;
+
+fn curve(points: &[(f64, f64)]) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x, y)).collect()
+}
+
+#[test]
+fn clear_match_is_robust() {
+    let (ps, qs) = (curve(&[(0., 0.), (1., 0.), (2., 0.)]), curve(&[(-1., 0.1), (1., 0.2), (3., 0.1)]));
+    let rsd = FSD::new(ps, qs, 0.5).to_rsd();
+    assert!(rsd.check_pcm());
+    assert!(rsd.robustness() > 0.01);
+}
+
+#[test]
+fn near_tangent_orthogonal_transition() {
+    // The target vertex touches the disc around the query only just, the match passes its free interval (shorter than its error bound)
+    // only through orthogonal transitions: into it from the left boundary, out of it onto the right boundary.
+    let ps = curve(&[(0., 0.), (2., 0.)]);
+    let rsd = |gap: f64| FSD::new_with(ps.clone(), curve(&[(-1., 0.5), (1., 1. - gap), (2., 0.)]), 1., Predicates::Exact).to_rsd();
+    assert!(rsd(1e-15).check_pcm());
+    assert!(rsd(1e-15).robustness() < 0.);
+    // Well clear of tangency it is robust again.
+    assert!(rsd(0.5).check_pcm());
+    assert!(rsd(0.5).robustness() > 0.1);
+}
@@ -0,0 +1,42 @@
+//! Same-route checks compare the largest coverage of mutual partial matches against a minimum.
+use pcm::*;
+
+fn line(x0: f64, x1: f64, n: usize) -> Curve {
+    (0..n).map(|i| Vector::new(x0 + (x1 - x0) * i as f64 / (n - 1) as f64, 0.)).collect()
+}
+
+#[test]
+fn identical_curves() {
+    let c = line(0., 10., 2);
+    assert_eq!(match_coverage(&c, &c, 1.).unwrap(), 1.);
+    assert!(try_same_route(&c, &c, 1., 0.95).unwrap());
+    assert!(try_same_route(&c, &c, 1., 1.).unwrap());
+    // Resampled the curve follows the same route as well.
+    assert!(try_same_route(&c, &line(0., 10., 7), 1., 0.95).unwrap());
+}
+
+#[test]
+fn short_probe_along_long_target() {
+    // Probe lies along the target, but only covers a fifth of it.
+    let (probe, target) = (line(4., 6., 3), line(0., 10., 11));
+    assert!(partial_curve_matching(probe.clone(), target.clone(), 0.5).unwrap().is_some());
+    let coverage = match_coverage(&probe, &target, 0.5).unwrap();
+    assert!((coverage - 0.3).abs() < 1e-9);
+    assert!(!try_same_route(&probe, &target, 0.5, 0.95).unwrap());
+    assert!(!try_same_route(&target, &probe, 0.5, 0.95).unwrap());
+}
+
+#[test]
+fn coverage_thresholds() {
+    // Target turns back halfway along the curve, both match each other without covering the other entirely.
+    let c = line(0., 10., 2);
+    let target: Curve = [(0., 0.), (10., 0.), (5., 0.)].into_iter().map(|(x, y)| Vector::new(x, y)).collect();
+    assert!((match_coverage(&c, &target, 3.).unwrap() - 13. / 15.).abs() < 1e-9);
+    assert!((match_coverage(&target, &c, 3.).unwrap() - 0.8).abs() < 1e-9);
+    assert!(try_same_route(&c, &target, 3., 0.75).unwrap());
+    assert!(!try_same_route(&c, &target, 3., 0.85).unwrap());
+    assert!(!try_same_route(&target, &c, 3., 0.85).unwrap());
+    // Without a match (the target turns back further than twice the threshold) the coverage is zero.
+    assert_eq!(match_coverage(&target, &c, 2.).unwrap(), 0.);
+    assert!(!try_same_route(&c, &target, 2., 0.).unwrap());
+}
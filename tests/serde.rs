@@ -0,0 +1,34 @@
+//! Curves, diagrams and matchings survive a round trip through JSON.
+use pcm::*;
+
+#[test]
+fn round_trip() {
+    let ps = vec![Vector::new(0., 0.), Vector::new(1., 0.2), Vector::new(2., 0.)];
+    let qs = vec![Vector::new(-1., 0.), Vector::new(0.5, 0.5), Vector::new(3., 0.)];
+    let eps = 0.6;
+
+    let text = serde_json::to_string(&ps).unwrap();
+    assert_eq!(serde_json::from_str::<Curve>(&text).unwrap(), ps);
+
+    let rsd = FSD::new(ps.clone(), qs.clone(), eps).to_rsd();
+    let read: FSD = serde_json::from_str(&serde_json::to_string(&rsd).unwrap()).unwrap();
+    assert_eq!((read.n, read.m, read.is_rsd, read.mode), (rsd.n, rsd.m, rsd.is_rsd, rsd.mode));
+    assert_eq!(read.corners, rsd.corners);
+    // Parsing JSON numbers may be off in the last digit.
+    let close = |(a1, b1): (f64, f64), (a2, b2): (f64, f64)| (a1 - a2).abs() < 1e-12 && (b1 - b2).abs() < 1e-12;
+    assert!(read.segs.iter().zip(rsd.segs.iter()).all(|(b1, b2)| match (b1, b2) {
+        (Some(b1), Some(b2)) => close((b1.a, b1.b), (b2.a, b2.b)),
+        (b1, b2) => b1.is_none() && b2.is_none(),
+    }));
+    assert_eq!(read.check_pcm(), rsd.check_pcm());
+
+    let matching = partial_curve_matching(ps, qs, eps).unwrap().unwrap();
+    let read: Matching = serde_json::from_str(&serde_json::to_string(&matching).unwrap()).unwrap();
+    assert_eq!(read.target, matching.target);
+    assert!(read.steps.len() == matching.steps.len() && read.steps.iter().zip(&matching.steps).all(|(s1, s2)| close(*s1, *s2)));
+
+    // Matchings are checked like on construction.
+    let single = r#"{"steps":[[0,0]],"query":[{"x":0,"y":0}],"target":[{"x":0,"y":0}]}"#;
+    assert!(serde_json::from_str::<Matching>(single).is_ok());
+    assert!(serde_json::from_str::<Matching>(&single.replace("[[0,0]]", "[]")).is_err());
+}
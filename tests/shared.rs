@@ -0,0 +1,32 @@
+//! Subcurve-to-subcurve matching finds the section two trajectories share.
+use pcm::*;
+
+fn curve(points: &[(f64, f64)]) -> Curve {
+    points.iter().map(|&(x, y)| Vector::new(x, y)).collect()
+}
+
+#[test]
+fn shared_road_segment() {
+    // Both arrive from different directions, share the stretch from x = 0 to x = 4, and leave in different directions.
+    let ps = curve(&[(0., -3.), (0., 0.), (4., 0.), (4., 3.)]);
+    let qs = curve(&[(-3., 0.1), (0., 0.1), (4., 0.1), (7., 0.1), (7., -2.)]);
+    let shared = longest_shared_subcurve(&ps, &qs, 0.2, 1., 10).unwrap().unwrap();
+    assert!((shared.length - 4.).abs() < 0.5, "{shared:?}");
+    let ((p0, p1), (q0, q1)) = (shared.interval_p(), shared.interval_q());
+    assert!((p0 - 1.).abs() < 0.1 && (p1 - 2.).abs() < 0.1);
+    assert!((q0 - 1.).abs() < 0.1 && (q1 - 2.).abs() < 0.1);
+    validate_steps(&ps, &qs, &shared.steps, 0.2).unwrap();
+    // Too short to count.
+    assert!(longest_shared_subcurve(&ps, &qs, 0.2, 5., 10).unwrap().is_none());
+}
+
+#[test]
+fn neither_curve_fully_matched() {
+    // Plain partial matching needs all of ps, the shared section is only part of both.
+    let ps = curve(&[(0., -3.), (0., 0.), (4., 0.), (4., 3.)]);
+    let qs = curve(&[(-3., 0.1), (0., 0.1), (4., 0.1), (7., 0.1)]);
+    assert!(try_partial_curve(ps.clone(), qs.clone(), 0.2).unwrap().is_none());
+    assert!(try_partial_curve(qs.clone(), ps.clone(), 0.2).unwrap().is_none());
+    assert!(longest_shared_subcurve(&ps, &qs, 0.2, 3., 4).unwrap().is_some());
+    assert!(longest_shared_subcurve(&ps, &qs, 0.2, 1., 0).is_err());
+}
@@ -0,0 +1,31 @@
+//! Free-space columns (vectorized with the `simd` feature) agree bit for bit with the boundaries computed one at a time.
+use pcm::*;
+
+#[test]
+fn agrees_with_scalar() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0xbb67ae8584caa73bu64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    for k in 0..300 {
+        // Lengths around multiples of the lane count, with coincident vertices (degenerate segments) now and then.
+        let (n, m) = (2 + k % 11, 2 + (k / 11) % 13);
+        let mut point = || if random() < 0.1 { Vector::new(1., 1.) } else { Vector::new(3. * random(), 3. * random()) };
+        let ps: Curve = (0..n).map(|_| point()).collect();
+        let qs: Curve = (0..m).map(|_| point()).collect();
+        let eps = 0.2 + 2. * random();
+        let fsd = FSD::new(ps.clone(), qs.clone(), eps);
+        for (axis, (c1, c2)) in [(&ps, &qs), (&qs, &ps)].into_iter().enumerate() {
+            for (x, &p) in c1.iter().enumerate() {
+                for y in 0..c2.len() - 1 {
+                    let expected = free_interval(p, c2[y], c2[y+1], eps).and_then(|LineBoundary { a, b }| LineBoundary::new(
+                        if p.distance(c2[y]) < eps { 0. } else { a.max(f64::MIN_POSITIVE) },
+                        if p.distance(c2[y+1]) < eps { 1. } else { b.min(1. - f64::EPSILON / 2.) },
+                    ));
+                    let actual = fsd.segs[(axis, x, y)];
+                    assert_eq!(actual.map(|b| (b.a.to_bits(), b.b.to_bits())), expected.map(|b| (b.a.to_bits(), b.b.to_bits())));
+                }
+            }
+        }
+        fsd.validate().unwrap();
+    }
+}
@@ -0,0 +1,52 @@
+//! Simplified curves stay within the requested Fréchet distance, so matching errors compose additively.
+use pcm::*;
+
+/// Whether curves c and d are within Fréchet distance eps.
+fn within_frechet(c: &Curve, d: &Curve, eps: f64) -> bool {
+    FSD::new(c.clone(), d.clone(), eps).to_rsd_with(MatchMode::Full).check_pcm()
+}
+
+#[test]
+fn within_epsilon() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x853c49e6748fea9bu64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    for _ in 0..50 {
+        // Noisy random walk, dense compared to eps.
+        let mut p = Vector::new(0., 0.);
+        let c: Curve = (0..40).map(|_| { p = p + Vector::new(random() - 0.3, random() - 0.5); p }).collect();
+        let eps = 0.2 + random();
+        let dp = simplify_douglas_peucker(&c, eps);
+        let ii = simplify_imai_iri(&c, eps);
+        assert!(ii.len() <= dp.len() && dp.len() <= c.len());
+        assert_eq!((dp[0], dp[dp.len() - 1]), (c[0], c[c.len() - 1]));
+        assert!(within_frechet(&c, &dp, eps + EPS));
+        assert!(within_frechet(&c, &ii, eps + EPS));
+    }
+}
+
+#[test]
+fn to_n_vertices() {
+    let mut state = 0x2545f4914f6cdd1du64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    let mut p = Vector::new(0., 0.);
+    let c: Curve = (0..40).map(|_| { p = p + Vector::new(random() - 0.3, random() - 0.5); p }).collect();
+    for n in [2, 3, 5, 10, 20] {
+        let (simplified, tolerance) = simplify_to_n(&c, n);
+        assert!(simplified.len() <= n);
+        assert_eq!((simplified[0], simplified[simplified.len() - 1]), (c[0], c[c.len() - 1]));
+        assert!(within_frechet(&c, &simplified, tolerance + EPS));
+        // The bisection is tight: slightly below the tolerance more vertices are needed.
+        assert!(simplify_imai_iri(&c, 0.99 * tolerance).len() > n);
+    }
+    // Curves which are short enough are left unchanged.
+    assert_eq!(simplify_to_n(&c, 40), (c.clone(), 0.));
+}
+
+#[test]
+fn rejects_backtracking() {
+    // Hausdorff-based Douglas–Peucker would drop the backtracking vertices, the Fréchet test keeps them.
+    let c = vec![Vector::new(0., 0.), Vector::new(2., 0.), Vector::new(1., 0.), Vector::new(3., 0.)];
+    assert_eq!(simplify_douglas_peucker(&c, 0.1), c);
+    assert_eq!(simplify_douglas_peucker(&c, 0.6).len(), 2);
+}
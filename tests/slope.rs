@@ -0,0 +1,54 @@
+//! Bounding the slope of the path through the free space limits how far one curve may advance while the other stands still.
+use pcm::*;
+
+const MODES: [MatchMode; 5] = [MatchMode::Full, MatchMode::PrefixOfQ, MatchMode::SuffixOfQ, MatchMode::SubcurveOfQ, MatchMode::SubcurveOfBoth];
+
+/// Check the slope between consecutive steps lies within [lo, hi] (up to rounding).
+fn within_slopes(steps: &[(f64, f64)], lo: f64, hi: f64) -> bool {
+    steps.windows(2).all(|w| {
+        let (di, dj) = (w[1].0 - w[0].0, w[1].1 - w[0].1);
+        dj >= lo * di - 1e-6 && (hi.is_infinite() || dj <= hi * di + 1e-6)
+    })
+}
+
+#[test]
+fn unbounded_agrees() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x510e527fade682d1u64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    for _ in 0..200 {
+        let ps: Curve = (0..5).map(|_| Vector::new(10. * random(), 10. * random())).collect();
+        let qs: Curve = (0..7).map(|_| Vector::new(10. * random(), 10. * random())).collect();
+        let eps = 1. + 4. * random();
+        let fsd = FSD::new(ps.clone(), qs.clone(), eps);
+        for mode in MODES {
+            let rsd = fsd.to_rsd_with_slopes(mode, 0., f64::INFINITY).unwrap();
+            assert_eq!(rsd.check_pcm(), fsd.to_rsd_with(mode).check_pcm());
+            if let Some(steps) = rsd.pcm_steps() {
+                validate_steps(&ps, &qs, &steps, eps).unwrap();
+            }
+            // Bounded slopes only ever reach less.
+            let bounded = fsd.to_rsd_with_slopes(mode, 0.5, 2.).unwrap();
+            assert!(!bounded.check_pcm() || rsd.check_pcm());
+            if let Some(steps) = bounded.pcm_steps() {
+                validate_steps(&ps, &qs, &steps, eps).unwrap();
+                assert!(within_slopes(&steps, 0.5, 2.));
+            }
+        }
+    }
+}
+
+#[test]
+fn rejects_standing_still() {
+    // Halfway along qs, ps covers a single unit over a whole segment (qs advances a tenth meanwhile, a slope of 0.1).
+    let ps = vec![Vector::new(0., 0.), Vector::new(1., 0.), Vector::new(10., 0.)];
+    let qs = vec![Vector::new(0., 0.), Vector::new(10., 0.)];
+    let fsd = FSD::new(ps.clone(), qs.clone(), 0.1);
+    assert!(fsd.to_rsd_with_slopes(MatchMode::Full, 0., f64::INFINITY).unwrap().check_pcm());
+    assert!(fsd.to_rsd_with_slopes(MatchMode::Full, 0.05, 1.).unwrap().check_pcm());
+    assert!(!fsd.to_rsd_with_slopes(MatchMode::Full, 0.5, 2.).unwrap().check_pcm());
+    let steps = fsd.to_rsd_with_slopes(MatchMode::Full, 0.05, 1.).unwrap().pcm_steps().unwrap();
+    validate_steps(&ps, &qs, &steps, 0.1).unwrap();
+    assert!(within_slopes(&steps, 0.05, 1.));
+    assert!(fsd.to_rsd_with_slopes(MatchMode::Full, 2., 1.).is_err());
+}
@@ -0,0 +1,76 @@
+//! The sparse free-space diagram agrees with the dense one.
+use pcm::*;
+
+/// Deterministic curves of n points with coordinates on a half-unit grid in [0,2]².
+fn curves(count: usize) -> Vec<(Curve, Curve)> {
+    // Deterministic xorshift so failures are reproducible.
+    let mut seed: u64 = 0x9E3779B97F4A7C15;
+    let mut rnd = || { seed ^= seed << 13; seed ^= seed >> 7; seed ^= seed << 17; (seed % 1_000_000) as f64 / 1_000_000. };
+    (0..count).map(|_| {
+        let n = 2 + (rnd() * 6.) as usize;
+        let m = 2 + (rnd() * 10.) as usize;
+        let ps: Curve = (0..n).map(|_| Vector::new((rnd() * 4.).round() / 2., (rnd() * 4.).round() / 2.)).collect();
+        let qs: Curve = (0..m).map(|_| Vector::new((rnd() * 4.).round() / 2., (rnd() * 4.).round() / 2.)).collect();
+        (ps, qs)
+    }).collect()
+}
+
+/// Interval endpoints (LineBoundary is not comparable itself).
+fn endpoints(boundary: OptLineBoundary) -> Option<(f64, f64)> {
+    boundary.map(|LineBoundary { a, b }| (a, b))
+}
+
+/// Assert every cell boundary of the sparse diagram equals the dense one.
+fn assert_same_boundaries(sparse: &SparseFSD, dense: &FSD) {
+    let mut count = 0;
+    for axis in 0..2 {
+        let (w, h) = dense.dims[axis];
+        for x in 0..w {
+            for y in 0..h {
+                assert_eq!(endpoints(sparse.boundary((axis, x, y))), endpoints(dense.segs[(axis, x, y)]), "Boundary {:?}", (axis, x, y));
+                count += dense.segs[(axis, x, y)].is_some() as usize;
+            }
+        }
+    }
+    assert_eq!(sparse.len(), count);
+}
+
+#[test]
+fn agrees_with_dense() {
+    let mut matches = 0;
+    for (ps, qs) in curves(500) {
+        for eps in [0.3, 0.6, 1.] {
+            let dense = FSD::new(ps.clone(), qs.clone(), eps);
+            let sparse = SparseFSD::new(ps.clone(), qs.clone(), eps);
+            assert_same_boundaries(&sparse, &dense);
+            let (dense, sparse) = (dense.to_rsd(), sparse.to_rsd());
+            assert_same_boundaries(&sparse, &dense);
+            assert_eq!(sparse.check_pcm(), dense.check_pcm());
+            assert_eq!(sparse.pcm_steps(), dense.pcm_steps());
+            if let Some(steps) = sparse.pcm_steps() {
+                validate_steps(&ps, &qs, &steps, eps).unwrap();
+                matches += 1;
+            }
+        }
+    }
+    assert!(matches > 0);
+}
+
+#[test]
+fn stores_only_free_boundaries() {
+    let ps: Curve = (0..3).map(|i| Vector::new(i as f64, 0.)).collect();
+    let qs: Curve = (0..100).map(|j| Vector::new(j as f64, 0.)).collect();
+    let sparse = SparseFSD::new(ps.clone(), qs.clone(), 0.5);
+    assert!(sparse.len() < 20);
+    assert!(sparse.check_pcm());
+    assert!(SparseFSD::new(ps, qs.iter().map(|q| *q + Vector::new(0., 1.)).collect(), 0.5).is_empty());
+}
+
+#[test]
+fn malformed_input() {
+    let c: Curve = vec![Vector::new(0., 0.), Vector::new(1., 0.)];
+    assert!(SparseFSD::try_new(c.clone(), c.clone(), 0.).is_err());
+    assert!(SparseFSD::try_new(c.clone(), c.clone(), f64::NAN).is_err());
+    assert!(SparseFSD::try_new(vec![c[0]], c.clone(), 1.).is_err());
+    assert!(SparseFSD::try_new(c.clone(), c, 1.).is_ok());
+}
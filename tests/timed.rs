@@ -0,0 +1,96 @@
+//! Matching timestamped trajectories within a time window.
+use pcm::*;
+
+/// Straight road along the x-axis, driven at unit speed from time start.
+fn drive(xs: &[f64], start: f64) -> TimedCurve {
+    TimedCurve::new(xs.iter().map(|&x| Vector::new(x, 0.)).collect(), xs.iter().map(|&x| start + x - xs[0]).collect()).unwrap()
+}
+
+#[test]
+fn offset() {
+    let ps = drive(&[2., 4., 6.], 2.);
+    let qs = drive(&[0., 3., 5., 10.], 0.);
+    // Both pass every position at the same time.
+    assert!(timed_partial_curve_matching(&ps, &qs, 0.5, TimeWindow::max_offset(0.1)).unwrap().is_some());
+
+    // Geometrically qs driven an hour later matches, but not within a minute.
+    let late = drive(&[0., 3., 5., 10.], 3600.);
+    assert!(partial_curve_matching(ps.to_curve(), late.to_curve(), 0.5).unwrap().is_some());
+    assert!(timed_partial_curve_matching(&ps, &late, 0.5, TimeWindow::max_offset(60.)).unwrap().is_none());
+    let matching = timed_partial_curve_matching(&ps, &late, 0.5, TimeWindow::max_offset(3600.5)).unwrap().unwrap();
+    // Unbounded windows reach the geometric matching.
+    assert_eq!(timed_partial_curve_matching(&ps, &late, 0.5, TimeWindow::new(f64::NEG_INFINITY, f64::INFINITY)).unwrap(), partial_curve_matching(ps.to_curve(), late.to_curve(), 0.5).unwrap());
+    for k in 0..=20 {
+        let (i, j) = matching.position(k as f64 * (matching.steps.len() - 1) as f64 / 20.);
+        assert!((late.time_at(j) - ps.time_at(i)).abs() <= 3600.5 + EPS);
+    }
+}
+
+#[test]
+fn following() {
+    let ps = drive(&[2., 4., 6.], 2.);
+    let ahead = drive(&[0., 3., 5., 10.], -30.);
+    let behind = drive(&[0., 3., 5., 10.], 30.);
+    assert!(timed_partial_curve_matching(&ps, &behind, 0.5, TimeWindow::following(60.)).unwrap().is_some());
+    assert!(timed_partial_curve_matching(&ps, &ahead, 0.5, TimeWindow::following(60.)).unwrap().is_none());
+    assert!(timed_partial_curve_matching(&ps, &behind, 0.5, TimeWindow::following(10.)).unwrap().is_none());
+}
+
+#[test]
+fn waiting() {
+    // qs drives ahead and waits near x = 3 for ps, which falls up to 5 seconds behind before catching up.
+    let ps = TimedCurve::new(vec![Vector::new(0., 0.), Vector::new(6., 0.)], vec![0., 16.]).unwrap();
+    let qs = TimedCurve::new(vec![Vector::new(0., 0.), Vector::new(3., 0.), Vector::new(3., 0.1), Vector::new(6., 0.)], vec![0., 3., 13., 16.]).unwrap();
+    assert!(timed_partial_curve_matching(&ps, &qs, 0.5, TimeWindow::max_offset(1.)).unwrap().is_none());
+    assert!(timed_partial_curve_matching(&ps, &qs, 0.5, TimeWindow::max_offset(6.)).unwrap().is_some());
+}
+
+#[test]
+fn invalid() {
+    let ps = drive(&[2., 4.], 0.);
+    assert!(timed_partial_curve_matching(&ps, &ps, 0.5, TimeWindow::new(1., 0.)).is_err());
+    assert!(timed_partial_curve_matching(&ps, &ps, 0.5, TimeWindow::new(f64::NAN, 0.)).is_err());
+    assert!(timed_partial_curve_matching(&ps, &ps, 0., TimeWindow::max_offset(1.)).is_err());
+    assert_eq!(ps.time_at(0.5), 1.);
+    assert_eq!(ps.time_at(9.), 2.);
+}
+
+#[test]
+fn slice_by_time() {
+    let c = TimedCurve::new(vec![Vector::new(0., 0.), Vector::new(2., 0.), Vector::new(2., 4.)], vec![0., 2., 4.]).unwrap();
+    assert_eq!(c.point_at_time(1.), Vector::new(1., 0.));
+    assert_eq!(c.point_at_time(3.), Vector::new(2., 2.));
+    assert_eq!(c.point_at_time(-1.), Vector::new(0., 0.));
+    assert_eq!(c.point_at_time(9.), Vector::new(2., 4.));
+
+    // Endpoints are interpolated, the vertex in between is kept.
+    let slice = c.slice_by_time(1., 3.).unwrap();
+    assert_eq!(slice.points, vec![Vector::new(1., 0.), Vector::new(2., 0.), Vector::new(2., 2.)]);
+    assert_eq!(slice.times, vec![1., 2., 3.]);
+    // Windows are clamped to the curve, and are empty if they do not overlap it.
+    assert_eq!(c.slice_by_time(-5., 9.).unwrap(), c);
+    assert_eq!(c.slice_by_time(3., 1.), None);
+    assert_eq!(c.slice_by_time(5., 9.), None);
+    assert_eq!(c.slice_by_time(-9., -5.), None);
+    let instant = c.slice_by_time(4., 9.).unwrap();
+    assert_eq!(instant.points, vec![Vector::new(2., 4.); 2]);
+}
+
+#[test]
+fn speeds() {
+    // Standing still for a second, then jumping without elapsed time.
+    let c = TimedCurve::new(vec![Vector::new(0., 0.), Vector::new(3., 4.), Vector::new(3., 4.), Vector::new(4., 4.)], vec![0., 1., 2., 2.]).unwrap();
+    assert_eq!(c.speeds(), vec![5., 0., f64::INFINITY]);
+    let parked = TimedCurve::new(vec![Vector::new(1., 1.); 2], vec![3., 3.]).unwrap();
+    assert_eq!(parked.speeds(), vec![0.]);
+    assert_eq!(parked.point_at_time(3.), Vector::new(1., 1.));
+}
+
+#[test]
+fn invalid_timestamps() {
+    let points = vec![Vector::new(0., 0.), Vector::new(1., 0.), Vector::new(2., 0.)];
+    assert!(matches!(TimedCurve::new(points.clone(), vec![0., 2., 1.]), Err(PcmError::InvalidTimestamp { index: 2, time }) if time == 1.));
+    assert!(matches!(TimedCurve::new(points.clone(), vec![0., f64::NAN, 1.]), Err(PcmError::InvalidTimestamp { index: 1, .. })));
+    assert!(matches!(TimedCurve::new(points.clone(), vec![0., 1.]), Err(PcmError::InvalidArgument(_))));
+    assert!(matches!(TimedCurve::new(vec![], vec![]), Err(PcmError::EmptyCurve { len: 0 })));
+}
@@ -0,0 +1,30 @@
+//! Searching transforms of the query which minimize its partial Fréchet distance to the target.
+use std::f64::consts::PI;
+
+use pcm::*;
+
+/// Zigzag target with a recognizable shape.
+fn target() -> Curve {
+    [(0., 0.), (2., 0.), (3., 1.), (4., 0.), (6., 0.), (6., 3.)].into_iter().map(|(x, y)| Vector::new(x, y)).collect()
+}
+
+#[test]
+fn recovers_translation() {
+    // Middle of the target, moved far away.
+    let offset = Vector::new(-40., 25.);
+    let ps: Curve = target()[1..5].iter().map(|&p| p + offset).collect();
+    let alignment = best_transform(&ps, &target(), &TransformSearch::default()).unwrap();
+    assert!(alignment.eps < 0.05, "{alignment:?}");
+    assert!(alignment.transform.translation.distance(-1. * offset) < 0.1);
+}
+
+#[test]
+fn recovers_rotation() {
+    let ps = Transform::about(Vector::new(3., 0.5), 0.3, Vector::new(1., -1.)).apply_curve(&target()[1..5].to_vec());
+    let search = TransformSearch { max_rotation: PI / 4., resolution: 7, ..TransformSearch::default() };
+    let alignment = best_transform(&ps, &target(), &search).unwrap();
+    assert!(alignment.eps < 0.1, "{alignment:?}");
+    assert!((alignment.transform.rotation + 0.3).abs() < 0.05);
+    // Translations only cannot undo the rotation.
+    assert!(best_transform(&ps, &target(), &TransformSearch::default()).unwrap().eps > alignment.eps);
+}
@@ -0,0 +1,64 @@
+//! Matching with a threshold varying along the target curve.
+use pcm::*;
+
+#[test]
+fn constant_agrees() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x9e3779b97f4a7c15u64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    for _ in 0..200 {
+        let ps: Curve = (0..4).map(|_| Vector::new(5. * random(), 5. * random())).collect();
+        let qs: Curve = (0..6).map(|_| Vector::new(5. * random(), 5. * random())).collect();
+        let eps = 0.5 + 2. * random();
+        let varying = varying_partial_curve_matching(&ps, &qs, &vec![eps; qs.len()]).unwrap();
+        assert_eq!(varying, partial_curve_matching(ps.clone(), qs.clone(), eps).unwrap());
+        assert_eq!(FSD::new_varying_with(&ps, &qs, |_| eps).to_rsd().pcm_steps(), varying.map(|matching| matching.steps));
+    }
+}
+
+#[test]
+fn interpolated_boundaries() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0x2545f4914f6cdd1du64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    for _ in 0..500 {
+        let p = Vector::new(4. * random(), 4. * random());
+        let (q0, q1) = (Vector::new(4. * random(), 4. * random()), Vector::new(4. * random(), 4. * random()));
+        let (e0, e1) = (0.1 + 3. * random(), 0.1 + 3. * random());
+        let fsd = FSD::new_varying(&[p, p + Vector::new(1., 0.)], &[q0, q1], &[e0, e1]);
+        let boundary = fsd.segs[(0, 0, 0)];
+        for k in 0..=100 {
+            let t = k as f64 / 100.;
+            let slack = p.distance((1. - t) * q0 + t * q1) - ((1. - t) * e0 + t * e1);
+            let inside = boundary.is_some_and(|LineBoundary { a, b }| a <= t && t <= b);
+            // Away from the interval endpoints membership follows the interpolated threshold.
+            if slack.abs() > 1e-6 {
+                assert_eq!(inside, slack < 0., "t {t} slack {slack} boundary {boundary:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn wider_on_highway() {
+    // Straight target with a tight threshold at its start and a wide one at its end.
+    let qs = vec![Vector::new(0., 0.), Vector::new(10., 0.), Vector::new(20., 0.), Vector::new(30., 0.)];
+    let eps = [0.5, 0.5, 2., 2.];
+    let offset = |x0: f64| vec![Vector::new(x0, 1.5), Vector::new(x0 + 3., 1.5)];
+    let matching = varying_partial_curve_matching(&offset(24.), &qs, &eps).unwrap().unwrap();
+    assert!(matching.covered_interval().0 > 2.);
+    assert!(varying_partial_curve_matching(&offset(2.), &qs, &eps).unwrap().is_none());
+    // In between, the threshold reaches 1.5 two thirds along the middle segment.
+    assert!(varying_partial_curve_matching(&offset(17.), &qs, &eps).unwrap().is_some());
+    assert!(varying_partial_curve_matching(&offset(12.), &qs, &eps).unwrap().is_none());
+    assert!(FSD::new_varying_with(&offset(24.), &qs, |t| if t < 2. { 0.5 } else { 2. }).to_rsd().check_pcm());
+}
+
+#[test]
+fn invalid() {
+    let qs = vec![Vector::new(0., 0.), Vector::new(1., 0.)];
+    assert!(varying_partial_curve_matching(&qs, &qs, &[1.]).is_err());
+    assert!(varying_partial_curve_matching(&qs, &qs, &[1., 0.]).is_err());
+    assert!(varying_partial_curve_matching(&qs, &qs, &[1., f64::NAN]).is_err());
+    assert!(varying_partial_curve_matching(&qs, &vec![Vector::new(0., 0.)], &[1.]).is_err());
+}
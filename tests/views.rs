@@ -0,0 +1,23 @@
+//! Read-only views over the diagram internals (with the `ndarray` feature).
+#![cfg(feature = "ndarray")]
+use pcm::*;
+
+#[test]
+fn shapes_and_values() {
+    let ps = vec![Vector::new(0., 0.), Vector::new(2., 0.3), Vector::new(4., -0.2)];
+    let qs = vec![Vector::new(-1., 0.5), Vector::new(1., 0.4), Vector::new(3., 0.6), Vector::new(5., 0.)];
+    let fsd = FSD::new(ps, qs, 0.8);
+    let (n, m) = (fsd.n, fsd.m);
+    let (verticals, horizontals, corners) = (fsd.verticals(), fsd.horizontals(), fsd.corners());
+    assert_eq!((verticals.dim(), horizontals.dim(), corners.dim()), ((n, m - 1), (m, n - 1), (n, m)));
+
+    let bounds = |opt_lb: OptLineBoundary| opt_lb.map(|LineBoundary { a, b }| (a, b));
+    for ((x, y), &lb) in verticals.indexed_iter() {
+        assert_eq!(bounds(lb), bounds(fsd.segs[(0, x, y)]));
+    }
+    for ((x, y), &lb) in horizontals.indexed_iter() {
+        assert_eq!(bounds(lb), bounds(fsd.segs[(1, x, y)]));
+    }
+    assert_eq!(corners, fsd.corners);
+    assert!(verticals.iter().any(Option::is_some) && verticals.iter().any(Option::is_none));
+}
@@ -0,0 +1,43 @@
+//! Weak Fréchet matching drops monotonicity, accepting back-and-forth jitter the strict matching rejects.
+use pcm::*;
+
+#[test]
+fn accepts_backtracking() {
+    // Q backtracks by 0.5 halfway, strictly that costs 0.25 while weakly the curves coincide.
+    let ps = vec![Vector::new(0., 0.), Vector::new(4., 0.)];
+    let qs = vec![Vector::new(0., 0.), Vector::new(2., 0.), Vector::new(1.5, 0.), Vector::new(4., 0.)];
+    let fsd = FSD::new(ps.clone(), qs.clone(), 0.2);
+    assert!(!fsd.check_frechet());
+    let rsd = fsd.to_weak_rsd_with(MatchMode::Full);
+    rsd.validate_reachability(&fsd).unwrap();
+    assert!(rsd.check_pcm());
+    let steps = rsd.pcm_steps().unwrap();
+    assert_eq!((steps[0], steps[steps.len() - 1]), ((0., 0.), (1., 3.)));
+    validate_weak_steps(&ps, &qs, &steps, 0.2).unwrap();
+    assert!(validate_steps(&ps, &qs, &steps, 0.2).is_err());
+}
+
+#[test]
+fn weakens_strict() {
+    // Deterministic xorshift so failures are reproducible.
+    let mut state = 0xda942042e4dd58b5u64;
+    let mut random = move || { state ^= state << 13; state ^= state >> 7; state ^= state << 17; (state >> 11) as f64 / (1u64 << 53) as f64 };
+    let mut weaker = 0;
+    for _ in 0..200 {
+        let ps: Curve = (0..5).map(|_| Vector::new(10. * random(), 10. * random())).collect();
+        let qs: Curve = (0..7).map(|_| Vector::new(10. * random(), 10. * random())).collect();
+        let eps = 1. + 4. * random();
+        let fsd = FSD::new(ps.clone(), qs.clone(), eps);
+        for mode in [MatchMode::Full, MatchMode::PrefixOfQ, MatchMode::SuffixOfQ, MatchMode::SubcurveOfQ, MatchMode::SubcurveOfBoth] {
+            let (strict, weak) = (fsd.to_rsd_with(mode), fsd.to_weak_rsd_with(mode));
+            weak.validate_reachability(&fsd).unwrap();
+            // Every strict matching is a weak one.
+            assert!(!strict.check_pcm() || weak.check_pcm());
+            weaker += (weak.check_pcm() && !strict.check_pcm()) as usize;
+            if let Some(steps) = weak.pcm_steps() {
+                validate_weak_steps(&ps, &qs, &steps, eps).unwrap();
+            }
+        }
+    }
+    assert!(weaker > 0);
+}